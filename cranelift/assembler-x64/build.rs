@@ -9,7 +9,14 @@ fn main() {
 
     let out_dir = env::var("OUT_DIR").expect("The OUT_DIR environment variable must be set");
     let out_dir = Path::new(&out_dir);
-    let built_files = [meta::generate_rust_assembler(out_dir, "assembler.rs")];
+    let mut built_files = vec![meta::generate_rust_assembler(out_dir, "assembler.rs")];
+
+    // Optionally describe each instruction's encoding as JSON, for external
+    // tools.
+    println!("cargo:rerun-if-env-changed=CRANELIFT_ASSEMBLER_X64_JSON");
+    if env::var_os("CRANELIFT_ASSEMBLER_X64_JSON").is_some() {
+        built_files.push(meta::generate_json_metadata(out_dir, "assembler.json"));
+    }
 
     // Generating this additional bit of Rust is necessary for listing the
     // generated files.
@@ -0,0 +1,11 @@
+#![no_main]
+
+use cranelift_assembler_x64::{Inst, fuzz};
+use libfuzzer_sys::{arbitrary, fuzz_target};
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = arbitrary::Unstructured::new(data);
+    if let Ok(inst) = Inst::<fuzz::FuzzRegs>::arbitrary_rex_gpr(&mut u) {
+        fuzz::differential(&inst);
+    }
+});
@@ -8,35 +8,64 @@ mod custom;
 mod encoding;
 mod features;
 pub mod format;
+mod width;
 
 pub use custom::{Custom, Customization};
-pub use encoding::{Encoding, ModRmKind, OpcodeMod};
-pub use encoding::{Evex, Length, Vex, VexEscape, VexPrefix, evex, vex};
+pub use encoding::{Condition, Encoding, ModRmKind, OpcodeMod, Selector};
+pub use encoding::{Evex, Length, Rounding, Vex, VexEscape, VexPrefix, evex, vex};
 pub use encoding::{
-    Group1Prefix, Group2Prefix, Group3Prefix, Group4Prefix, Opcodes, Prefixes, Rex, TupleType, rex,
+    Group1Prefix, Group2Prefix, Group3Prefix, Group4Prefix, Opcodes, Prefixes, Repeat, Rex,
+    Segment, TupleType, rex,
 };
 pub use features::{ALL_FEATURES, Feature, Features};
 pub use format::{Eflags, Extension, Format, Location, Mutability, Operand, OperandKind, RegClass};
-pub use format::{align, fmt, implicit, r, rw, sxl, sxq, sxw, w};
+pub use format::{align, fmt, implicit, r, rw, sxl, sxq, sxw, symbolic, w};
+pub use width::{Width, bwlq};
 
 /// Abbreviated constructor for an x64 instruction.
+///
+/// # Panics
+///
+/// Panics if the encoding is invalid for the format's operands, e.g., a VEX or
+/// EVEX encoding without an opcode map:
+///
+/// ```should_panic
+/// # use cranelift_assembler_x64_meta::dsl::{Feature, Length, fmt, inst, r, vex, w};
+/// # use cranelift_assembler_x64_meta::dsl::format::Location::{xmm1, xmm2, xmm_m128};
+/// let format = fmt("B", [w(xmm1), r(xmm2), r(xmm_m128)]);
+/// inst("vaddps", format, vex(Length::L128).op(0x58).r(), Feature::avx);
+/// // panics: `vaddps_b`: missing opcode map (mmmmm)
+/// ```
 pub fn inst(
     mnemonic: impl Into<String>,
     format: Format,
     encoding: impl Into<Encoding>,
     features: impl Into<Features>,
 ) -> Inst {
-    let encoding = encoding.into();
-    encoding.validate(&format.operands);
-    Inst {
+    let mut inst = Inst {
         mnemonic: mnemonic.into(),
         format,
-        encoding,
+        encoding: encoding.into(),
         features: features.into(),
         alternate: None,
         has_trap: false,
         custom: Custom::default(),
+    };
+    // 16-bit integer operations need the operand-size override prefix; SIMD
+    // forms which use `0x66` as a mandatory prefix declare it themselves.
+    if let Encoding::Rex(rex) = &mut inst.encoding {
+        if inst.format.integer_operand_size() == Some(16) {
+            rex.opcodes.prefixes.group3 = Some(Group3Prefix::OperandSizeOverride);
+        }
     }
+    if let Some(field) = inst.encoding.missing_opcode_map() {
+        panic!("`{}`: missing opcode map ({field})", inst.name());
+    }
+    if let Some(prefix) = inst.encoding.forbidden_prefix() {
+        panic!("`{}`: NP forbids the {prefix} prefix", inst.name());
+    }
+    inst.encoding.validate(&inst.format.operands);
+    inst
 }
 
 /// An x64 instruction.
@@ -92,6 +121,47 @@ impl Inst {
         )
     }
 
+    /// Return `true` if this instruction is REX-encoded and all of its
+    /// register operands are general-purpose registers.
+    pub fn is_rex_gpr_only(&self) -> bool {
+        matches!(self.encoding, Encoding::Rex(_))
+            && self
+                .format
+                .locations()
+                .all(|l| matches!(l.reg_class(), None | Some(RegClass::Gpr)))
+    }
+
+    /// Return `true` if this instruction can macro-fuse with an immediately
+    /// following conditional branch.
+    ///
+    /// Both Intel and AMD cores fuse a REX-encoded `cmp` or `test` of
+    /// general-purpose operands with a following `jcc`, decoding the pair as
+    /// a single micro-op; Intel cores also fuse other arithmetic, such as
+    /// `add`, but AMD cores do not, so those are not reported here. Fusion
+    /// still depends on the operands chosen at emission time: neither vendor
+    /// fuses a comparison of memory with an immediate, and Intel does not fuse
+    /// RIP-relative addressing.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::instructions;
+    /// let fusible = |name: &str| {
+    ///     let insts = instructions::list();
+    ///     insts.iter().find(|i| i.name() == name).unwrap().is_macro_fusible()
+    /// };
+    /// assert!(fusible("cmpq_rm"));
+    /// assert!(fusible("testl_mr"));
+    /// assert!(!fusible("addq_rm"));
+    /// assert!(!fusible("ptest_rm"));
+    /// ```
+    #[must_use]
+    pub fn is_macro_fusible(&self) -> bool {
+        let base = self
+            .mnemonic
+            .strip_suffix(['b', 'w', 'l', 'q'])
+            .unwrap_or(&self.mnemonic);
+        matches!(base, "cmp" | "test") && self.format.eflags.is_write() && self.is_rex_gpr_only()
+    }
+
     /// Flags this instruction as being able to trap, so needs a `TrapCode` at
     /// compile time to track this.
     pub fn has_trap(mut self) -> Self {
@@ -27,6 +27,10 @@ pub fn rex(opcode: impl Into<Opcodes>) -> Rex {
         modrm: None,
         imm: Imm::None,
         opcode_mod: None,
+        segment: None,
+        repeat: None,
+        np: false,
+        acc_opcode: None,
     }
 }
 
@@ -57,6 +61,7 @@ pub fn evex(length: Length, tuple_type: TupleType) -> Evex {
         modrm: None,
         imm: Imm::None,
         tuple_type,
+        rounding: None,
     }
 }
 
@@ -78,6 +83,25 @@ impl Encoding {
         }
     }
 
+    /// Return the name of the opcode map field--`mmmmm` for VEX, `mmm` for
+    /// EVEX--if this encoding requires one but does not set it.
+    pub fn missing_opcode_map(&self) -> Option<&'static str> {
+        match self {
+            Encoding::Rex(_) => None,
+            Encoding::Vex(vex) => vex.mmmmm.is_none().then_some("mmmmm"),
+            Encoding::Evex(evex) => evex.mmm.is_none().then_some("mmm"),
+        }
+    }
+
+    /// Return a prefix this encoding emits despite being marked `NP`; see
+    /// [`Rex::np`].
+    pub fn forbidden_prefix(&self) -> Option<String> {
+        match self {
+            Encoding::Rex(rex) => rex.forbidden_prefix(),
+            Encoding::Vex(_) | Encoding::Evex(_) => None,
+        }
+    }
+
     /// Return the opcode for this encoding.
     pub fn opcode(&self) -> u8 {
         match self {
@@ -113,6 +137,11 @@ pub enum ModRmKind {
     /// From the reference manual: "indicates that the ModR/M byte of the
     /// instruction contains a register operand and an r/m operand."
     Reg,
+
+    /// Models a typed value occupying the `reg` field in place of a register
+    /// operand (e.g., a condition code); unlike [`ModRmKind::Digit`], the
+    /// value may need REX.R to hold its fourth bit.
+    Selector(Selector),
 }
 
 impl ModRmKind {
@@ -133,6 +162,27 @@ impl ModRmKind {
     pub fn unwrap_digit(&self) -> u8 {
         self.digit().expect("expected an extension digit")
     }
+
+    /// Return the bits fixed into the `reg` field (plus REX.R), if this kind
+    /// does not encode a register operand there.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::{Condition, ModRmKind, Selector};
+    /// for (i, cc) in Condition::ALL.into_iter().enumerate() {
+    ///     let kind = ModRmKind::Selector(Selector::Condition(cc));
+    ///     assert_eq!(kind.fixed_reg_bits(), Some(i as u8));
+    /// }
+    /// assert_eq!(ModRmKind::Digit(5).fixed_reg_bits(), Some(5));
+    /// assert_eq!(ModRmKind::Reg.fixed_reg_bits(), None);
+    /// ```
+    #[must_use]
+    pub fn fixed_reg_bits(&self) -> Option<u8> {
+        match self {
+            Self::Digit(digit) => Some(*digit),
+            Self::Selector(selector) => Some(selector.bits()),
+            Self::Reg => None,
+        }
+    }
 }
 
 impl fmt::Display for ModRmKind {
@@ -140,10 +190,92 @@ impl fmt::Display for ModRmKind {
         match self {
             ModRmKind::Digit(digit) => write!(f, "/{digit}"),
             ModRmKind::Reg => write!(f, "/r"),
+            ModRmKind::Selector(selector) => write!(f, "/{selector}"),
+        }
+    }
+}
+
+/// A typed value selecting an instruction variant through the ModR/M `reg`
+/// field.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Selector {
+    /// A condition code, encoded as in the low nibble of `Jcc`.
+    Condition(Condition),
+    /// An opmask register, `k0` through `k7`.
+    Mask(u8),
+}
+
+impl Selector {
+    /// Return the bits this selector places in the `reg` field; bit 3, if set,
+    /// belongs in REX.R.
+    #[must_use]
+    pub fn bits(&self) -> u8 {
+        match self {
+            Selector::Condition(cc) => *cc as u8,
+            Selector::Mask(k) => *k,
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Selector::Condition(cc) => write!(f, "cc={cc}"),
+            Selector::Mask(k) => write!(f, "k{k}"),
         }
     }
 }
 
+/// An x86 condition code, in encoding order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Condition {
+    O = 0x0,
+    No = 0x1,
+    B = 0x2,
+    Ae = 0x3,
+    E = 0x4,
+    Ne = 0x5,
+    Be = 0x6,
+    A = 0x7,
+    S = 0x8,
+    Ns = 0x9,
+    P = 0xa,
+    Np = 0xb,
+    L = 0xc,
+    Ge = 0xd,
+    Le = 0xe,
+    G = 0xf,
+}
+
+impl Condition {
+    /// All condition codes, in encoding order.
+    pub const ALL: [Condition; 16] = [
+        Condition::O,
+        Condition::No,
+        Condition::B,
+        Condition::Ae,
+        Condition::E,
+        Condition::Ne,
+        Condition::Be,
+        Condition::A,
+        Condition::S,
+        Condition::Ns,
+        Condition::P,
+        Condition::Np,
+        Condition::L,
+        Condition::Ge,
+        Condition::Le,
+        Condition::G,
+    ];
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = format!("{self:?}").to_lowercase();
+        f.write_str(&name)
+    }
+}
+
 /// The traditional x64 encoding.
 ///
 /// We use the "REX" name here in a slightly unorthodox way: "REX" is the name
@@ -188,6 +320,22 @@ pub struct Rex {
     /// encodes the register operand of the instruction. “+ro” is applicable
     /// only in 64-bit mode."
     pub opcode_mod: Option<OpcodeMod>,
+    /// Overrides the default segment of the instruction's memory operand; if
+    /// `Some`, this emits the corresponding [`Group2Prefix`] byte.
+    pub segment: Option<Segment>,
+    /// Repeats a string instruction; if `Some`, this emits the corresponding
+    /// [`Group1Prefix`] byte.
+    pub repeat: Option<Repeat>,
+    /// Indicates that no `0x66`, `0xF2`, or `0xF3` prefix is allowed.
+    ///
+    /// From the reference manual: "Indicates the use of 66/F2/F3 prefixes
+    /// (beyond those already part of the instructions opcode) are not allowed
+    /// with the instruction. Such use will either cause an invalid-opcode
+    /// exception (#UD) or result in the encoding for a different instruction."
+    pub np: bool,
+    /// The opcode of a compact `+r` form used when either of two register
+    /// operands is the accumulator; see [`Rex::or_acc`].
+    pub acc_opcode: Option<u8>,
 }
 
 impl Rex {
@@ -225,6 +373,30 @@ impl Rex {
         }
     }
 
+    /// Set a typed selector in the ModR/M `reg` field, rather than a bare
+    /// [`digit`](Rex::digit).
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::{rex, Condition, Selector};
+    /// let enc = rex([0x0F, 0x90]).selector(Selector::Condition(Condition::Ne));
+    /// assert_eq!(enc.to_string(), "0x0F + 0x90 /cc=ne");
+    /// assert_eq!(enc.modrm.unwrap().fixed_reg_bits(), Some(0x5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a mask register is not one of `k0` through `k7`.
+    #[must_use]
+    pub fn selector(self, selector: Selector) -> Self {
+        if let Selector::Mask(k) = selector {
+            assert!(k <= 0b111, "must fit in 3 bits");
+        }
+        Self {
+            modrm: Some(ModRmKind::Selector(selector)),
+            ..self
+        }
+    }
+
     /// Retrieve the digit extending the opcode, if available.
     #[must_use]
     pub fn unwrap_digit(&self) -> Option<u8> {
@@ -249,6 +421,32 @@ impl Rex {
         }
     }
 
+    /// Mark this encoding as implying an immediate of `value` which is not
+    /// emitted, e.g. for the shift-by-one forms (`Op/En` of `M1` in the
+    /// reference manual), which differ from the shift-by-`ib` forms only by
+    /// opcode.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::{fmt, inst, rex, rw, Feature::_64b};
+    /// # use cranelift_assembler_x64_meta::dsl::format::Location::rm32;
+    /// let enc = rex(0xD1).digit(4).implicit_imm(1);
+    /// assert_eq!(enc.to_string(), "0xD1 /4");
+    /// // The format must not also declare an immediate operand.
+    /// inst("shll", fmt("M1", [rw(rm32)]), enc, _64b);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an immediate operand is already set.
+    #[must_use]
+    pub fn implicit_imm(self, value: u8) -> Self {
+        assert_eq!(self.imm, Imm::None);
+        Self {
+            imm: Imm::Implicit(value),
+            ..self
+        }
+    }
+
     /// Append a word-sized immediate operand (16-bit); equivalent to `iw` in
     /// the reference manual.
     ///
@@ -334,6 +532,138 @@ impl Rex {
         }
     }
 
+    /// Access the memory operand relative to the `FS` segment base.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::rex;
+    /// let enc = rex(0x8B).w().r().fs();
+    /// assert_eq!(enc.to_string(), "0x64 + REX.W + 0x8B /r");
+    /// ```
+    #[must_use]
+    pub fn fs(self) -> Self {
+        assert!(self.segment.is_none());
+        Self {
+            segment: Some(Segment::FS),
+            ..self
+        }
+    }
+
+    /// Access the memory operand relative to the `GS` segment base.
+    #[must_use]
+    pub fn gs(self) -> Self {
+        assert!(self.segment.is_none());
+        Self {
+            segment: Some(Segment::GS),
+            ..self
+        }
+    }
+
+    /// Repeat the string instruction while `rcx` is not zero; equivalent to the
+    /// `REP` (or `REPE`) prefix in the manual.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::rex;
+    /// let enc = rex(0xA4).rep();
+    /// assert_eq!(enc.to_string(), "0xF3 + 0xA4");
+    /// ```
+    #[must_use]
+    pub fn rep(self) -> Self {
+        assert!(self.repeat.is_none());
+        Self {
+            repeat: Some(Repeat::REP),
+            ..self
+        }
+    }
+
+    /// Repeat the string instruction while `rcx` is not zero and the
+    /// comparison fails; equivalent to the `REPNE` (or `REPNZ`) prefix in the
+    /// manual. Only valid for `CMPS` and `SCAS`.
+    #[must_use]
+    pub fn repne(self) -> Self {
+        assert!(self.repeat.is_none());
+        Self {
+            repeat: Some(Repeat::REPNE),
+            ..self
+        }
+    }
+
+    /// Forbid the mandatory prefixes (`0x66`, `0xF2`, and `0xF3`); equivalent to
+    /// `NP` in the reference manual. [`inst`](super::inst) checks this after
+    /// adding any operand-size override prefix.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::rex;
+    /// let enc = rex([0x0F, 0x58]).np().r();
+    /// assert_eq!(enc.to_string(), "NP 0x0F + 0x58 /r");
+    /// ```
+    ///
+    /// ```should_panic
+    /// # use cranelift_assembler_x64_meta::dsl::{fmt, inst, r, rex, rw, Feature::sse};
+    /// # use cranelift_assembler_x64_meta::dsl::format::Location::{xmm1, xmm_m128};
+    /// // Panics: "`addps_a`: NP forbids the 0x66 prefix".
+    /// let enc = rex([0x66, 0x0F, 0x58]).np().r();
+    /// inst("addps", fmt("A", [rw(xmm1), r(xmm_m128)]), enc, sse);
+    /// ```
+    #[must_use]
+    pub fn np(self) -> Self {
+        Self { np: true, ..self }
+    }
+
+    /// Add a compact form, encoded as `opcode` plus the other register, for
+    /// when either register operand is the accumulator; e.g., `XCHG EAX, r32`
+    /// (`0x90 +rd`) alongside `XCHG r/m32, r32` (`0x87 /r`). The ModR/M form is
+    /// used otherwise.
+    ///
+    /// This assumes the two operands are interchangeable, as for `xchg`.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::rex;
+    /// let enc = rex(0x87).r().or_acc(0x90);
+    /// assert_eq!(enc.to_string(), "0x87 /r | 0x90 +r");
+    /// ```
+    #[must_use]
+    pub fn or_acc(self, opcode: u8) -> Self {
+        Self {
+            acc_opcode: Some(opcode),
+            ..self
+        }
+    }
+
+    /// Return the first prefix forbidden by [`Rex::np`] that this encoding
+    /// emits, if any.
+    pub fn forbidden_prefix(&self) -> Option<String> {
+        if !self.np {
+            return None;
+        }
+        if let Some(group3) = &self.opcodes.prefixes.group3 {
+            return Some(group3.to_string());
+        }
+        match self.group1() {
+            Some(group1 @ (Group1Prefix::REPNorBND | Group1Prefix::REP_)) => {
+                Some(group1.to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the group 1 prefix to emit, either from a repeat prefix or from
+    /// the prefix bytes passed as opcodes.
+    pub(crate) fn group1(&self) -> Option<Group1Prefix> {
+        match self.repeat {
+            Some(repeat) => Some(repeat.prefix()),
+            None => self.opcodes.prefixes.group1,
+        }
+    }
+
+    /// Return the group 2 prefix to emit, either from a segment override or
+    /// from the prefix bytes passed as opcodes.
+    pub(crate) fn group2(&self) -> Option<Group2Prefix> {
+        match self.segment {
+            Some(segment) => Some(segment.prefix()),
+            None => self.opcodes.prefixes.group2,
+        }
+    }
+
     /// Check a subset of the rules for valid encodings outlined in chapter 2,
     /// _Instruction Format_, of the Intel® 64 and IA-32 Architectures Software
     /// Developer’s Manual, Volume 2A.
@@ -343,6 +673,10 @@ impl Rex {
             .map(|o| o.location.kind())
             .find(|k| matches!(k, OperandKind::Imm(_)))
         {
+            assert!(
+                !matches!(self.imm, Imm::Implicit(_)),
+                "an implicit immediate has no immediate operand"
+            );
             assert_eq!(
                 op.bits(),
                 self.imm.bits(),
@@ -355,6 +689,10 @@ impl Rex {
                 self.opcodes.primary & 0b111 == 0,
                 "the lower three bits of the opcode byte should be 0"
             );
+            assert!(
+                self.modrm.is_none(),
+                "the register is encoded in the opcode byte so no ModR/M byte is expected"
+            );
             assert!(
                 operands
                     .iter()
@@ -363,6 +701,50 @@ impl Rex {
             );
         }
 
+        if let Some(acc_opcode) = self.acc_opcode {
+            assert!(
+                acc_opcode & 0b111 == 0,
+                "the lower three bits of the accumulator opcode byte should be 0"
+            );
+            assert!(
+                matches!(self.modrm, Some(ModRmKind::Reg)),
+                "the accumulator form is an alternative to a `/r` ModR/M form"
+            );
+            assert!(
+                matches!(
+                    operands,
+                    [a, b] if matches!(a.location.kind(), OperandKind::Reg(_))
+                        && matches!(b.location.kind(), OperandKind::Reg(_))
+                        && a.location.bits() == b.location.bits()
+                        && a.location.bits() > 8
+                ),
+                "the accumulator form needs two registers of the same, non-byte width"
+            );
+        }
+
+        if self.segment.is_some() {
+            assert!(
+                self.opcodes.prefixes.group2.is_none(),
+                "a segment override conflicts with an existing group 2 prefix"
+            );
+            assert!(
+                operands.iter().any(|o| o.location.uses_memory()),
+                "a segment override requires a memory operand"
+            );
+        }
+
+        if let Some(repeat) = self.repeat {
+            assert!(
+                self.opcodes.prefixes.group1.is_none(),
+                "a repeat prefix conflicts with an existing group 1 prefix"
+            );
+            assert!(
+                !self.opcodes.escape && repeat.applies_to(self.opcodes.primary),
+                "`{repeat}` is not valid for opcode {:#04X}; only string instructions repeat",
+                self.opcodes.primary
+            );
+        }
+
         assert!(!matches!(self.w, WBit::WIG));
     }
 }
@@ -375,10 +757,13 @@ impl From<Rex> for Encoding {
 
 impl fmt::Display for Rex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(group1) = &self.opcodes.prefixes.group1 {
+        if self.np {
+            write!(f, "NP ")?;
+        }
+        if let Some(group1) = self.group1() {
             write!(f, "{group1} + ")?;
         }
-        if let Some(group2) = &self.opcodes.prefixes.group2 {
+        if let Some(group2) = self.group2() {
             write!(f, "{group2} + ")?;
         }
         if let Some(group3) = &self.opcodes.prefixes.group3 {
@@ -403,9 +788,12 @@ impl fmt::Display for Rex {
         if let Some(opcode_mod) = &self.opcode_mod {
             write!(f, " {opcode_mod}")?;
         }
-        if self.imm != Imm::None {
+        if !matches!(self.imm, Imm::None | Imm::Implicit(_)) {
             write!(f, " {}", self.imm)?;
         }
+        if let Some(acc_opcode) = self.acc_opcode {
+            write!(f, " | {acc_opcode:#04X} +r")?;
+        }
         Ok(())
     }
 }
@@ -562,6 +950,7 @@ impl Prefixes {
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum Group1Prefix {
     /// The LOCK prefix (`0xf0`). From the reference manual:
     ///
@@ -616,6 +1005,7 @@ impl fmt::Display for Group1Prefix {
 /// used on a `Jcc` instruction. Note that using the segment override prefixes
 /// on a branch instruction is reserved. See section 2.1.1, "Instruction
 /// Prefixes," in the reference manual.
+#[derive(Clone, Copy)]
 pub enum Group2Prefix {
     /// The CS segment override prefix (`0x2e`); also the "branch not taken"
     /// hint.
@@ -660,6 +1050,75 @@ impl fmt::Display for Group2Prefix {
     }
 }
 
+/// The segment registers available for overriding the segment of a memory
+/// operand. In 64-bit mode, the bases of the CS, DS, ES, and SS segments are
+/// treated as zero so only FS and GS are useful here (e.g., for accessing
+/// thread-local storage).
+#[derive(Clone, Copy, PartialEq)]
+pub enum Segment {
+    FS,
+    GS,
+}
+
+impl Segment {
+    /// Return the segment override prefix corresponding to this segment.
+    #[must_use]
+    pub fn prefix(&self) -> Group2Prefix {
+        match self {
+            Segment::FS => Group2Prefix::FS,
+            Segment::GS => Group2Prefix::GS,
+        }
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Segment::FS => write!(f, "fs"),
+            Segment::GS => write!(f, "gs"),
+        }
+    }
+}
+
+/// The repeat prefixes available for string instructions (`MOVS`, `CMPS`,
+/// `SCAS`, `LODS`, `STOS`, `INS`, and `OUTS`). See "REP/REPE/REPZ/REPNE/REPNZ"
+/// in the reference manual.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Repeat {
+    REP,
+    REPNE,
+}
+
+impl Repeat {
+    /// Return the repeat prefix corresponding to this kind of repetition.
+    #[must_use]
+    pub fn prefix(&self) -> Group1Prefix {
+        match self {
+            Repeat::REP => Group1Prefix::REP_,
+            Repeat::REPNE => Group1Prefix::REPNorBND,
+        }
+    }
+
+    /// Check if this prefix can repeat the instruction with `opcode`: `REP`
+    /// applies to all string instructions but `REPNE` only to the comparing
+    /// ones (`CMPS` and `SCAS`).
+    fn applies_to(&self, opcode: u8) -> bool {
+        match self {
+            Repeat::REP => matches!(opcode, 0x6C..=0x6F | 0xA4..=0xA7 | 0xAA..=0xAF),
+            Repeat::REPNE => matches!(opcode, 0xA6 | 0xA7 | 0xAE | 0xAF),
+        }
+    }
+}
+
+impl fmt::Display for Repeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Repeat::REP => write!(f, "rep"),
+            Repeat::REPNE => write!(f, "repne"),
+        }
+    }
+}
+
 /// Contains the operand-size override prefix (`0x66`); also used as a SIMD
 /// prefix. From the reference manual:
 ///
@@ -733,12 +1192,15 @@ pub enum Imm {
     iw,
     id,
     io,
+    /// An immediate implied by the opcode, which is not emitted; see
+    /// [`Rex::implicit_imm`].
+    Implicit(u8),
 }
 
 impl Imm {
     fn bits(&self) -> u16 {
         match self {
-            Self::None => 0,
+            Self::None | Self::Implicit(_) => 0,
             Self::ib => 8,
             Self::iw => 16,
             Self::id => 32,
@@ -750,7 +1212,7 @@ impl Imm {
 impl fmt::Display for Imm {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::None => write!(f, ""),
+            Self::None | Self::Implicit(_) => write!(f, ""),
             Self::ib => write!(f, "ib"),
             Self::iw => write!(f, "iw"),
             Self::id => write!(f, "id"),
@@ -768,6 +1230,10 @@ impl fmt::Display for Imm {
 /// // The `bswap` instruction extends the opcode byte:
 /// let enc = rex([0x0F, 0xC8]).rd();
 /// assert_eq!(enc.to_string(), "0x0F + 0xC8 +rd");
+///
+/// // The `mov` instruction extends the opcode byte and appends an immediate:
+/// let enc = rex(0xB8).w().ro().io();
+/// assert_eq!(enc.to_string(), "REX.W + 0xB8 +ro io");
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[allow(non_camel_case_types, reason = "makes DSL definitions easier to read")]
@@ -938,7 +1404,7 @@ pub enum WBit {
 impl WBit {
     /// Return `true` if the `W` bit is ignored; this is useful to check in the
     /// DSL for the default case.
-    fn is_ignored(&self) -> bool {
+    pub(crate) fn is_ignored(&self) -> bool {
         match self {
             Self::WIG => true,
             Self::W0 | Self::W1 => false,
@@ -1182,7 +1648,6 @@ impl Vex {
 
     fn validate(&self, _operands: &[Operand]) {
         assert!(self.opcode != u8::MAX);
-        assert!(self.mmmmm.is_some());
         assert!(!matches!(self.length, Length::L512));
     }
 
@@ -1247,6 +1712,9 @@ pub struct Evex {
     /// The "Tuple Type" corresponding to scaling of the 8-bit displacement
     /// parameter for memory operands. See [`TupleType`] for more information.
     pub tuple_type: TupleType,
+    /// The static rounding mode to embed in the instruction, if any; see
+    /// [`Rounding`].
+    pub rounding: Option<Rounding>,
 }
 
 impl Evex {
@@ -1352,9 +1820,30 @@ impl Evex {
         }
     }
 
-    fn validate(&self, _operands: &[Operand]) {
+    /// Embed a static rounding mode, which also suppresses all floating-point
+    /// exceptions; equivalent to `{er}` in the reference manual.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::{evex, Length::LIG, Rounding, TupleType::Tuple1Scalar};
+    /// let enc = evex(LIG, Tuple1Scalar)._f2()._0f().w1().op(0x58).r().er(Rounding::RuSae);
+    /// assert_eq!(enc.to_string(), "EVEX.LIG.F2.0F.W1 0x58 /r {ru-sae}");
+    /// ```
+    pub fn er(self, rounding: Rounding) -> Self {
+        assert!(self.rounding.is_none());
+        Self {
+            rounding: Some(rounding),
+            ..self
+        }
+    }
+
+    fn validate(&self, operands: &[Operand]) {
         assert!(self.opcode != u8::MAX);
-        assert!(self.mmm.is_some());
+        if self.rounding.is_some() {
+            assert!(
+                !operands.iter().any(|o| o.location.uses_memory()),
+                "embedded rounding is only available for register-only operands"
+            );
+        }
     }
 
     /// Retrieve the digit extending the opcode, if available.
@@ -1419,15 +1908,61 @@ impl fmt::Display for Evex {
         if self.imm != Imm::None {
             write!(f, " {}", self.imm)?;
         }
+        if let Some(rounding) = self.rounding {
+            write!(f, " {rounding}")?;
+        }
         Ok(())
     }
 }
 
+/// Static rounding modes available to EVEX-encoded floating-point instructions
+/// with register-only operands.
+///
+/// From section 2.7.1, "Static Rounding Support in EVEX," in the reference
+/// manual: setting `EVEX.b` for a register-register instruction enables static
+/// rounding, taking the rounding mode from `EVEX.L'L` (instead of the vector
+/// length) and suppressing all floating-point exceptions ("SAE").
+#[derive(Clone, Copy, PartialEq)]
+pub enum Rounding {
+    /// Round to nearest (even); `{rn-sae}`.
+    RnSae,
+    /// Round down (toward -∞); `{rd-sae}`.
+    RdSae,
+    /// Round up (toward +∞); `{ru-sae}`.
+    RuSae,
+    /// Round toward zero (truncate); `{rz-sae}`.
+    RzSae,
+}
+
+impl Rounding {
+    /// Encode the `EVEX.L'L` bits.
+    pub fn evex_bits(&self) -> u8 {
+        match self {
+            Self::RnSae => 0b00,
+            Self::RdSae => 0b01,
+            Self::RuSae => 0b10,
+            Self::RzSae => 0b11,
+        }
+    }
+}
+
+impl fmt::Display for Rounding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::RnSae => write!(f, "{{rn-sae}}"),
+            Self::RdSae => write!(f, "{{rd-sae}}"),
+            Self::RuSae => write!(f, "{{ru-sae}}"),
+            Self::RzSae => write!(f, "{{rz-sae}}"),
+        }
+    }
+}
+
 /// Tuple Type definitions used in EVEX encodings.
 ///
 /// This enumeration corresponds to table 2-34 and 2-35 in the Intel manual.
 /// This is a property of all instruction formats listed in the encoding table
 /// for each instruction.
+#[derive(Debug)]
 #[expect(missing_docs, reason = "matching manual names")]
 pub enum TupleType {
     Full,
@@ -78,6 +78,26 @@ pub fn implicit(location: Location) -> Operand {
     }
 }
 
+/// An abbreviated constructor for an immediate which may instead refer to a
+/// symbol, e.g., the displacement of a `call` to an external function.
+///
+/// The instruction gains a `symbol` field; when set, encoding reports a
+/// relocation against that symbol at the offset of the immediate.
+///
+/// # Panics
+///
+/// This function panics if the operand is not a 32-bit immediate, the only
+/// width a PC-relative relocation can patch.
+#[must_use]
+pub fn symbolic(op: impl Into<Operand>) -> Operand {
+    let op = op.into();
+    assert_eq!(op.location, Location::imm32);
+    Operand {
+        symbolic: true,
+        ..op
+    }
+}
+
 /// An abbreviated constructor for a "read" operand that is sign-extended to 64
 /// bits (quadword).
 ///
@@ -156,6 +176,16 @@ impl Format {
         self.locations().copied().find(Location::uses_memory)
     }
 
+    /// Return the location of the immediate which may refer to a symbol (see
+    /// [`symbolic`]), if any; return `None` otherwise.
+    #[must_use]
+    pub fn symbolic_immediate(&self) -> Option<Location> {
+        let mut symbolic = self.operands.iter().filter(|o| o.symbolic);
+        let location = symbolic.next().map(|o| o.location);
+        assert!(symbolic.next().is_none(), "at most one symbolic operand");
+        location
+    }
+
     /// Return `true` if any of the operands accepts a register (i.e., not an
     /// immediate); return `false` otherwise.
     #[must_use]
@@ -163,6 +193,32 @@ impl Format {
         self.locations().any(Location::uses_register)
     }
 
+    /// Return the operand size of an integer instruction: the width of its
+    /// first explicit general-purpose register or memory operand. Return
+    /// `None` if there is no such operand or it is an XMM operand.
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::{fmt, implicit, r, rw, sxl, w};
+    /// # use cranelift_assembler_x64_meta::dsl::format::Location::*;
+    /// assert_eq!(fmt("RM", [rw(r16), r(rm16)]).integer_operand_size(), Some(16));
+    /// assert_eq!(fmt("RM", [w(r32), sxl(rm16)]).integer_operand_size(), Some(32));
+    /// assert_eq!(fmt("M", [rw(implicit(ax)), r(rm8)]).integer_operand_size(), Some(8));
+    /// assert_eq!(fmt("A", [rw(xmm1), r(xmm_m16)]).integer_operand_size(), None);
+    /// ```
+    #[must_use]
+    pub fn integer_operand_size(&self) -> Option<u16> {
+        let first = self
+            .operands
+            .iter()
+            .filter(|o| !o.implicit)
+            .map(|o| o.location)
+            .find(|l| !matches!(l.kind(), OperandKind::Imm(_)))?;
+        match first.reg_class() {
+            Some(RegClass::Xmm) => None,
+            _ => Some(first.bits()),
+        }
+    }
+
     /// Collect into operand kinds.
     pub fn operands_by_kind(&self) -> Vec<OperandKind> {
         self.locations().map(Location::kind).collect()
@@ -229,6 +285,9 @@ pub struct Operand {
     /// Some register operands are implicit: that is, they do not appear in the
     /// disassembled output even though they are used in the instruction.
     pub implicit: bool,
+    /// Some immediates may refer to a symbol, to be resolved by a relocation;
+    /// see [`symbolic`].
+    pub symbolic: bool,
 }
 
 impl core::fmt::Display for Operand {
@@ -239,6 +298,7 @@ impl core::fmt::Display for Operand {
             extension,
             align,
             implicit,
+            symbolic,
         } = self;
         write!(f, "{location}")?;
         let mut flags = vec![];
@@ -254,6 +314,9 @@ impl core::fmt::Display for Operand {
         if *implicit {
             flags.push("implicit".to_owned());
         }
+        if *symbolic {
+            flags.push("symbolic".to_owned());
+        }
         if !flags.is_empty() {
             write!(f, "[{}]", flags.join(","))?;
         }
@@ -267,12 +330,14 @@ impl From<Location> for Operand {
         let extension = Extension::default();
         let align = false;
         let implicit = false;
+        let symbolic = false;
         Self {
             location,
             mutability,
             extension,
             align,
             implicit,
+            symbolic,
         }
     }
 }
@@ -307,6 +372,8 @@ pub enum Location {
     rdx,
     cl,
     rcx,
+    rsi,
+    rdi,
     xmm0,
 
     // Immediate values.
@@ -356,7 +423,8 @@ impl Location {
             al | cl | imm8 | r8 | rm8 | m8 | xmm_m8 => 8,
             ax | dx | imm16 | r16 | rm16 | m16 | xmm_m16 => 16,
             eax | edx | imm32 | r32 | r32a | r32b | rm32 | m32 | xmm_m32 => 32,
-            rax | rbx | rcx | rdx | imm64 | r64 | r64a | r64b | rm64 | m64 | xmm_m64 => 64,
+            rax | rbx | rcx | rdx | rsi | rdi | imm64 | r64 | r64a | r64b | rm64 | m64
+            | xmm_m64 => 64,
             xmm1 | xmm2 | xmm3 | xmm_m128 | xmm0 | m128 => 128,
         }
     }
@@ -393,7 +461,7 @@ impl Location {
     pub fn kind(&self) -> OperandKind {
         use Location::*;
         match self {
-            al | ax | eax | rax | rbx | cl | rcx | dx | edx | rdx | xmm0 => {
+            al | ax | eax | rax | rbx | cl | rcx | dx | edx | rdx | rsi | rdi | xmm0 => {
                 OperandKind::FixedReg(*self)
             }
             imm8 | imm16 | imm32 | imm64 => OperandKind::Imm(*self),
@@ -416,8 +484,8 @@ impl Location {
         use Location::*;
         match self {
             imm8 | imm16 | imm32 | imm64 | m8 | m16 | m32 | m64 | m128 => None,
-            al | ax | eax | rax | rbx | cl | rcx | dx | edx | rdx | r8 | r16 | r32 | r32a
-            | r32b | r64 | r64a | r64b | rm8 | rm16 | rm32 | rm64 => Some(RegClass::Gpr),
+            al | ax | eax | rax | rbx | cl | rcx | dx | edx | rdx | rsi | rdi | r8 | r16 | r32
+            | r32a | r32b | r64 | r64a | r64b | rm8 | rm16 | rm32 | rm64 => Some(RegClass::Gpr),
             xmm1 | xmm2 | xmm3 | xmm_m8 | xmm_m16 | xmm_m32 | xmm_m64 | xmm_m128 | xmm0 => {
                 Some(RegClass::Xmm)
             }
@@ -444,6 +512,8 @@ impl core::fmt::Display for Location {
             dx => write!(f, "dx"),
             edx => write!(f, "edx"),
             rdx => write!(f, "rdx"),
+            rsi => write!(f, "rsi"),
+            rdi => write!(f, "rdi"),
             xmm0 => write!(f, "xmm0"),
 
             r8 => write!(f, "r8"),
@@ -0,0 +1,176 @@
+//! Generate the byte, word, doubleword, and quadword variants of an integer
+//! instruction from a single definition.
+//!
+//! Many integer instructions come in four operand widths which differ only in
+//! predictable ways: the byte form uses an even opcode and the wider forms set
+//! its low bit, the word form gains the operand-size override prefix (`0x66`)
+//! as any 16-bit form does (see [`inst`](super::inst)), and the quadword form
+//! sets REX.W. [`bwlq`] applies these rules to a byte-form
+//! definition:
+//!
+//! ```
+//! # use cranelift_assembler_x64_meta::dsl::{bwlq, r, rex, rw};
+//! let insts = bwlq("add", "MI", |w| [rw(w.rm()), r(w.imm())], || rex(0x80).digit(0).ib());
+//! let names: Vec<_> = insts.iter().map(|i| i.name()).collect();
+//! assert_eq!(names, ["addb_mi", "addw_mi", "addl_mi", "addq_mi_sxl"]);
+//! let encodings: Vec<_> = insts.iter().map(|i| i.encoding.to_string()).collect();
+//! assert_eq!(encodings, ["0x80 /0 ib", "0x66 + 0x81 /0 iw", "0x81 /0 id", "REX.W + 0x81 /0 id"]);
+//! ```
+
+use super::encoding::{Imm, WBit};
+use super::{Feature, Features, Inst, Location, Operand, Rex, fmt, inst, sxq};
+
+/// The operand width of an integer instruction, named by its AT&T mnemonic
+/// suffix.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Width {
+    /// Byte (8 bits).
+    B,
+    /// Word (16 bits).
+    W,
+    /// Doubleword (32 bits).
+    L,
+    /// Quadword (64 bits).
+    Q,
+}
+
+impl Width {
+    /// All widths, in increasing order.
+    pub const ALL: [Width; 4] = [Width::B, Width::W, Width::L, Width::Q];
+
+    /// Return the AT&T mnemonic suffix: `b`, `w`, `l`, or `q`.
+    #[must_use]
+    pub fn suffix(&self) -> char {
+        match self {
+            Width::B => 'b',
+            Width::W => 'w',
+            Width::L => 'l',
+            Width::Q => 'q',
+        }
+    }
+
+    /// Return the accumulator register of this width: `al`, `ax`, `eax`, or
+    /// `rax`.
+    #[must_use]
+    pub fn acc(&self) -> Location {
+        self.pick([Location::al, Location::ax, Location::eax, Location::rax])
+    }
+
+    /// Return the general-purpose register of this width, `r8` through `r64`.
+    #[must_use]
+    pub fn r(&self) -> Location {
+        self.pick([Location::r8, Location::r16, Location::r32, Location::r64])
+    }
+
+    /// Return the register-or-memory location of this width, `rm8` through
+    /// `rm64`.
+    #[must_use]
+    pub fn rm(&self) -> Location {
+        self.pick([
+            Location::rm8,
+            Location::rm16,
+            Location::rm32,
+            Location::rm64,
+        ])
+    }
+
+    /// Return the memory location of this width, `m8` through `m64`.
+    #[must_use]
+    pub fn m(&self) -> Location {
+        self.pick([Location::m8, Location::m16, Location::m32, Location::m64])
+    }
+
+    /// Return the immediate operand of this width; there is no 64-bit
+    /// immediate, so the quadword form sign-extends a 32-bit immediate.
+    #[must_use]
+    pub fn imm(&self) -> Operand {
+        match self {
+            Width::Q => sxq(Location::imm32),
+            _ => self
+                .pick([
+                    Location::imm8,
+                    Location::imm16,
+                    Location::imm32,
+                    Location::imm32,
+                ])
+                .into(),
+        }
+    }
+
+    fn pick<T>(&self, [b, w, l, q]: [T; 4]) -> T {
+        match self {
+            Width::B => b,
+            Width::W => w,
+            Width::L => l,
+            Width::Q => q,
+        }
+    }
+
+    /// Adapt the byte-form `rex` encoding to this width.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rex` is not a byte form: it must have an even opcode, no
+    /// operand-size override prefix, no REX.W bit, and either no immediate or
+    /// an `ib` immediate.
+    fn widen(&self, mut rex: Rex) -> Rex {
+        assert_eq!(rex.opcodes.primary & 1, 0, "byte forms use an even opcode");
+        assert!(
+            rex.opcodes.prefixes.group3.is_none(),
+            "byte forms have no 0x66 prefix"
+        );
+        assert!(matches!(rex.w, WBit::W0), "byte forms have no REX.W bit");
+        assert!(
+            matches!(rex.imm, Imm::None | Imm::ib),
+            "byte forms have an `ib` immediate"
+        );
+        if *self == Width::B {
+            return rex;
+        }
+        rex.opcodes.primary |= 1;
+        if rex.imm != Imm::None {
+            rex.imm = self.pick([Imm::ib, Imm::iw, Imm::id, Imm::id]);
+        }
+        if *self == Width::Q {
+            rex.w = WBit::W1;
+        }
+        rex
+    }
+}
+
+/// Define the `b`, `w`, `l`, and `q` variants of an integer instruction.
+///
+/// `operands` builds the operands for each [`Width`] and `encoding` builds the
+/// byte-form encoding, which is adapted to each wider form (see the
+/// [module documentation](self)). Since the quadword form sign-extends its
+/// immediate, its format name gains an `_SXL` suffix, as in the reference
+/// manual. The quadword form is only available in 64-bit mode.
+pub fn bwlq<const N: usize>(
+    mnemonic: &str,
+    format: &str,
+    operands: impl Fn(Width) -> [Operand; N],
+    encoding: impl Fn() -> Rex,
+) -> Vec<Inst> {
+    Width::ALL
+        .into_iter()
+        .map(|width| {
+            let operands = operands(width);
+            let extended = operands.iter().any(|op| op.extension.is_sign_extended());
+            let format = match width {
+                Width::Q if extended => format!("{format}_SXL"),
+                _ => format.to_string(),
+            };
+            let features: Features = match width {
+                Width::Q => Feature::_64b.into(),
+                _ => Feature::_64b | Feature::compat,
+            };
+            let mnemonic = format!("{mnemonic}{}", width.suffix());
+            inst(
+                mnemonic,
+                fmt(format, operands),
+                width.widen(encoding()),
+                features,
+            )
+        })
+        .collect()
+}
@@ -3,8 +3,11 @@
 mod features;
 mod format;
 mod inst;
+mod json;
 mod operand;
 
+pub use json::json;
+
 use crate::dsl;
 use cranelift_srcgen::{Formatter, fmtln};
 
@@ -14,15 +17,24 @@ pub fn rust_assembler(f: &mut Formatter, insts: &[dsl::Inst]) {
     generate_inst_enum(f, insts);
     generate_inst_display_impl(f, insts);
     generate_inst_encode_impl(f, insts);
+    generate_inst_encode_short_impl(f, insts);
+    generate_inst_encode_into_impl(f, insts);
+    generate_inst_encode_aligned_impl(f, insts);
     generate_inst_visit_impl(f, insts);
     generate_inst_is_available_impl(f, insts);
     generate_inst_features_impl(f, insts);
+    generate_inst_macro_fusible_impl(f, insts);
+    generate_inst_operand_strings_impl(f, insts);
+    generate_inst_arbitrary_rex_gpr_impl(f, insts);
 
     // Generate per-instruction structs.
     f.empty_line();
     for inst in insts {
         inst.generate_struct(f);
         inst.generate_struct_impl(f);
+        if let Some(short) = inst.short_immediate_form(insts) {
+            inst.generate_encode_short_impl(f, short);
+        }
         inst.generate_display_impl(f);
         inst.generate_from_impl(f);
         f.empty_line();
@@ -95,6 +107,89 @@ fn generate_inst_encode_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
     });
 }
 
+/// `impl Inst { fn encode_into... }`
+fn generate_inst_encode_into_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
+    f.add_block("impl<R: Registers> Inst<R>", |f| {
+        fmtln!(
+            f,
+            "/// Encode this instruction into the fixed-size `b`, returning the"
+        );
+        fmtln!(
+            f,
+            "/// encoded length or an error if `b` is too small or the instruction"
+        );
+        fmtln!(f, "/// refers to a label, constant or symbol.");
+        f.add_block(
+            "pub fn encode_into(&self, b: &mut [u8]) -> Result<usize, EncodeError>",
+            |f| {
+                f.add_block("match self", |f| {
+                    for inst in insts {
+                        let variant_name = inst.name();
+                        fmtln!(f, "Self::{variant_name}(i) => i.encode_into(b),");
+                    }
+                });
+            },
+        );
+    });
+}
+
+/// `impl Inst { fn encode_aligned... }`
+fn generate_inst_encode_aligned_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
+    f.add_block("impl<R: Registers> Inst<R>", |f| {
+        fmtln!(
+            f,
+            "/// Encode this instruction after padding `offset`, the current offset"
+        );
+        fmtln!(
+            f,
+            "/// into `b`, with multi-byte `nop`s up to a multiple of `align`."
+        );
+        f.add_block(
+            "pub fn encode_aligned(&self, b: &mut impl CodeSink, offset: usize, align: usize)",
+            |f| {
+                f.add_block("match self", |f| {
+                    for inst in insts {
+                        let variant_name = inst.name();
+                        fmtln!(
+                            f,
+                            "Self::{variant_name}(i) => i.encode_aligned(b, offset, align),"
+                        );
+                    }
+                });
+            },
+        );
+    });
+}
+
+/// `impl Inst { fn encode_short... }`
+fn generate_inst_encode_short_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
+    f.add_block("impl<R: Registers> Inst<R>", |f| {
+        fmtln!(
+            f,
+            "/// Encode this instruction like `encode` but, for instructions which"
+        );
+        fmtln!(
+            f,
+            "/// also have a form taking a sign-extended 8-bit immediate (e.g.,"
+        );
+        fmtln!(
+            f,
+            "/// `addl_mi` and `addl_mi_sxb`), use that form if the immediate fits."
+        );
+        f.add_block("pub fn encode_short(&self, b: &mut impl CodeSink)", |f| {
+            f.add_block("match self", |f| {
+                for inst in insts {
+                    if inst.short_immediate_form(insts).is_some() {
+                        let variant_name = inst.name();
+                        fmtln!(f, "Self::{variant_name}(i) => i.encode_short(b),");
+                    }
+                }
+                fmtln!(f, "_ => self.encode(b),");
+            });
+        });
+    });
+}
+
 /// `impl Inst { fn visit... }`
 fn generate_inst_visit_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
     fmtln!(f, "impl<R: Registers> Inst<R> {{");
@@ -148,3 +243,55 @@ fn generate_inst_features_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
         });
     });
 }
+
+/// `impl Inst { fn macro_fusible... }`
+fn generate_inst_macro_fusible_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
+    f.add_block("impl<R: Registers> Inst<R>", |f| {
+        f.add_block("pub fn macro_fusible(&self) -> bool", |f| {
+            f.add_block("match self", |f| {
+                for inst in insts {
+                    let variant_name = inst.name();
+                    fmtln!(f, "Self::{variant_name}(i) => i.macro_fusible(),");
+                }
+            });
+        });
+    });
+}
+
+/// `impl Inst { fn operand_strings... }`
+fn generate_inst_operand_strings_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
+    f.add_block("impl<R: Registers> Inst<R>", |f| {
+        f.add_block("pub fn operand_strings(&self) -> OperandStrings", |f| {
+            f.add_block("match self", |f| {
+                for inst in insts {
+                    let variant_name = inst.name();
+                    fmtln!(f, "Self::{variant_name}(i) => i.operand_strings(),");
+                }
+            });
+        });
+    });
+}
+
+/// `impl Inst { fn arbitrary_rex_gpr... }`
+fn generate_inst_arbitrary_rex_gpr_impl(f: &mut Formatter, insts: &[dsl::Inst]) {
+    let insts: Vec<_> = insts.iter().filter(|i| i.is_rex_gpr_only()).collect();
+    fmtln!(f, "#[cfg(any(test, feature = \"fuzz\"))]");
+    f.add_block("impl<R: crate::fuzz::RegistersArbitrary> Inst<R>", |f| {
+        fmtln!(f, "/// Generate an arbitrary REX-encoded, GPR-only instruction.");
+        f.add_block(
+            "pub fn arbitrary_rex_gpr(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Self>",
+            |f| {
+                let max = insts.len() - 1;
+                fmtln!(f, "Ok(match u.int_in_range(0..={max})? {{");
+                f.indent(|f| {
+                    for (index, inst) in insts.iter().enumerate() {
+                        let variant_name = inst.name();
+                        fmtln!(f, "{index} => Self::{variant_name}(u.arbitrary()?),");
+                    }
+                    fmtln!(f, "_ => unreachable!(),");
+                });
+                fmtln!(f, "}})");
+            },
+        );
+    });
+}
@@ -40,10 +40,36 @@ enum ModRmStyle {
 enum ModRmReg {
     /// A static set of bits is used.
     Digit(u8),
+    /// A typed selector's bits are used.
+    Selector(dsl::Selector),
     /// A runtime-defined register is used with this field name.
     Reg(dsl::Location),
 }
 
+impl ModRmReg {
+    /// Return the statically-known `reg` field contents of `modrm`, if any.
+    fn fixed(modrm: dsl::ModRmKind) -> Option<Self> {
+        match modrm {
+            dsl::ModRmKind::Digit(digit) => Some(Self::Digit(digit)),
+            dsl::ModRmKind::Selector(selector) => Some(Self::Selector(selector)),
+            dsl::ModRmKind::Reg => None,
+        }
+    }
+
+    /// Return the static bits of this field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field holds a runtime-defined register.
+    fn unwrap_bits(&self) -> u8 {
+        match self {
+            Self::Digit(digit) => *digit,
+            Self::Selector(selector) => selector.bits(),
+            Self::Reg(_) => panic!("expected static `reg` bits"),
+        }
+    }
+}
+
 impl dsl::Format {
     /// Re-order the Intel-style operand order to accommodate ATT-style
     /// printing.
@@ -65,6 +91,19 @@ impl dsl::Format {
         ordered_ops.join(", ")
     }
 
+    /// Like [`Format::generate_att_style_operands`] but keeps the operands in
+    /// their natural, Intel-style order (destination first).
+    #[must_use]
+    pub(crate) fn generate_intel_style_operands(&self) -> String {
+        let ordered_ops: Vec<_> = self
+            .operands
+            .iter()
+            .filter(|o| !o.implicit)
+            .map(|o| format!("{{{}}}", o.location))
+            .collect();
+        ordered_ops.join(", ")
+    }
+
     #[must_use]
     pub(crate) fn generate_implicit_operands(&self) -> String {
         let ops: Vec<_> = self
@@ -82,8 +121,13 @@ impl dsl::Format {
 
     pub(crate) fn generate_rex_encoding(&self, f: &mut Formatter, rex: &dsl::Rex) {
         self.generate_prefixes(f, rex);
+        if let Some(acc_opcode) = rex.acc_opcode {
+            self.generate_acc_form(f, rex, acc_opcode);
+        }
         let style = self.generate_rex_prefix(f, rex);
-        rex.generate_opcodes(f, self.locations().next());
+        let first_op = self.locations().next();
+        let reg = first_op.map(|op| self.generate_trace_operand(*op));
+        rex.generate_opcodes(f, first_op, reg);
         self.generate_modrm_byte(f, style);
         self.generate_immediate(f, style);
     }
@@ -102,16 +146,44 @@ impl dsl::Format {
         self.generate_immediate(f, style);
     }
 
+    /// Emit the compact `opcode +r` form, and return early, if either register
+    /// operand is the accumulator; see [`dsl::Rex::or_acc`].
+    fn generate_acc_form(&self, f: &mut Formatter, rex: &dsl::Rex, acc_opcode: u8) {
+        let [a, b] = self.operands.as_slice() else {
+            unreachable!("checked when validating the encoding")
+        };
+        let (a, b) = (a.location, b.location);
+        f.empty_line();
+        f.comment("Emit the compact form if one operand is the accumulator.");
+        fmtln!(f, "let (a, b) = (self.{a}.enc(), self.{b}.enc());");
+        fmtln!(
+            f,
+            "let other = if a == 0 {{ b }} else if b == 0 {{ a }} else {{ 0 }};"
+        );
+        f.add_block("if other != 0", |f| {
+            generate_trace(f, "REX", &[]);
+            fmtln!(
+                f,
+                "RexPrefix::one_op(other, {}, false).encode(buf);",
+                rex.w.as_bool()
+            );
+            generate_trace(f, "opcode", &[]);
+            fmtln!(f, "buf.put1(0x{acc_opcode:x} | (other & 0b111));");
+            fmtln!(f, "return;");
+        });
+    }
+
     /// `buf.put1(...);`
     fn generate_prefixes(&self, f: &mut Formatter, rex: &dsl::Rex) {
-        if !rex.opcodes.prefixes.is_empty() {
+        if !rex.opcodes.prefixes.is_empty() || rex.segment.is_some() || rex.repeat.is_some() {
             f.empty_line();
             f.comment("Emit prefixes.");
+            generate_trace(f, "prefix", &[]);
         }
-        if let Some(group1) = &rex.opcodes.prefixes.group1 {
+        if let Some(group1) = rex.group1() {
             fmtln!(f, "buf.put1({group1});");
         }
-        if let Some(group2) = &rex.opcodes.prefixes.group2 {
+        if let Some(group2) = rex.group2() {
             fmtln!(f, "buf.put1({group2});");
         }
         if let Some(group3) = &rex.opcodes.prefixes.group3 {
@@ -143,7 +215,11 @@ impl dsl::Format {
         let bits = "w_bit, uses_8bit";
 
         let style = match self.operands_by_kind().as_slice() {
-            [FixedReg(dst), FixedReg(_)] | [FixedReg(dst)] | [FixedReg(dst), Imm(_)] => {
+            [FixedReg(dst), FixedReg(_)]
+            | [FixedReg(dst)]
+            | [FixedReg(dst), Imm(_)]
+            | [Imm(_), FixedReg(dst)]
+            | [FixedReg(dst), FixedReg(_), FixedReg(_)] => {
                 // TODO: don't emit REX byte here.
                 assert_eq!(rex.unwrap_digit(), None);
                 fmtln!(f, "let digit = 0;");
@@ -158,15 +234,12 @@ impl dsl::Format {
                 fmtln!(f, "let rex = RexPrefix::one_op(dst, {bits});");
                 ModRmStyle::None
             }
-            [Reg(dst), Imm(_)] => match rex.unwrap_digit() {
-                Some(digit) => {
-                    fmtln!(f, "let digit = 0x{digit:x};");
+            [Reg(dst), Imm(_)] => match rex.modrm.and_then(ModRmReg::fixed) {
+                Some(reg) => {
+                    fmtln!(f, "let digit = {:#x};", reg.unwrap_bits());
                     fmtln!(f, "let dst = self.{dst}.enc();");
                     fmtln!(f, "let rex = RexPrefix::two_op(digit, dst, {bits});");
-                    ModRmStyle::Reg {
-                        reg: ModRmReg::Digit(digit),
-                        rm: *dst,
-                    }
+                    ModRmStyle::Reg { reg, rm: *dst }
                 }
                 None => {
                     assert!(rex.opcode_mod.is_some());
@@ -175,19 +248,14 @@ impl dsl::Format {
                     ModRmStyle::None
                 }
             },
-            [FixedReg(_), RegMem(mem)]
-            | [FixedReg(_), FixedReg(_), RegMem(mem)]
-            | [RegMem(mem), FixedReg(_)]
-            | [Mem(mem), Imm(_)]
-            | [RegMem(mem), Imm(_)]
-            | [RegMem(mem)]
-            | [FixedReg(_), FixedReg(_), FixedReg(_), FixedReg(_), Mem(mem)] => {
-                let digit = rex.unwrap_digit().unwrap();
-                fmtln!(f, "let digit = 0x{digit:x};");
+            kinds if only_memory_is_encoded(kinds) => {
+                let mem = self.uses_memory().unwrap();
+                let reg = rex.modrm.and_then(ModRmReg::fixed).unwrap();
+                fmtln!(f, "let digit = {:#x};", reg.unwrap_bits());
                 fmtln!(f, "let rex = self.{mem}.as_rex_prefix(digit, {bits});");
                 ModRmStyle::RegMem {
-                    reg: ModRmReg::Digit(digit),
-                    rm: *mem,
+                    reg,
+                    rm: mem,
                     evex_scaling: None,
                 }
             }
@@ -216,6 +284,7 @@ impl dsl::Format {
             unknown => unimplemented!("unknown pattern: {unknown:?}"),
         };
 
+        generate_trace(f, "REX", &[]);
         fmtln!(f, "rex.encode(buf);");
         style
     }
@@ -225,7 +294,10 @@ impl dsl::Format {
         f.comment("Emit VEX prefix.");
         fmtln!(f, "let len = {:#03b};", vex.length.vex_bits());
         fmtln!(f, "let pp = {:#04b};", vex.pp.map_or(0b00, |pp| pp.bits()));
-        fmtln!(f, "let mmmmm = {:#07b};", vex.mmmmm.unwrap().bits());
+        let mmmmm = vex
+            .mmmmm
+            .expect("missing opcode map (mmmmm); checked by `inst`");
+        fmtln!(f, "let mmmmm = {:#07b};", mmmmm.bits());
         fmtln!(f, "let w = {};", vex.w.as_bool());
         let bits = "len, pp, mmmmm, w";
 
@@ -237,20 +309,67 @@ impl dsl::Format {
     fn generate_evex_prefix(&self, f: &mut Formatter, evex: &dsl::Evex) -> ModRmStyle {
         f.empty_line();
         f.comment("Emit EVEX prefix.");
-        let ll = evex.length.evex_bits();
+        // With embedded rounding, the `L'L` bits hold the rounding mode and the
+        // `b` bit enables it; there is no memory operand to scale.
+        let ll = match evex.rounding {
+            Some(rounding) => rounding.evex_bits(),
+            None => evex.length.evex_bits(),
+        };
         fmtln!(f, "let ll = {ll:#04b};");
         fmtln!(f, "let pp = {:#04b};", evex.pp.map_or(0b00, |pp| pp.bits()));
-        fmtln!(f, "let mmm = {:#07b};", evex.mmm.unwrap().bits());
+        let mmm = evex
+            .mmm
+            .expect("missing opcode map (mmm); checked by `inst`");
+        fmtln!(f, "let mmm = {:#07b};", mmm.bits());
         fmtln!(f, "let w = {};", evex.w.as_bool());
-        // NB: when bcast is supported in the future the `evex_scaling`
-        // calculation for `Full` and `Half` below need to be updated.
-        let bcast = false;
+        let bcast = evex.rounding.is_some();
         fmtln!(f, "let bcast = {bcast};");
         let bits = format!("ll, pp, mmm, w, bcast");
         let is4 = false;
 
-        let length_bytes = match evex.length {
-            dsl::Length::LZ | dsl::Length::LIG => unimplemented!(),
+        if evex.rounding.is_some() {
+            return self.generate_vex_or_evex_prefix(f, "EvexPrefix", &bits, is4, None, || {
+                evex.unwrap_digit()
+            });
+        }
+
+        let evex_scaling = Some(self.evex_scaling(evex));
+        self.generate_vex_or_evex_prefix(f, "EvexPrefix", &bits, is4, evex_scaling, || {
+            evex.unwrap_digit()
+        })
+    }
+
+    /// Compute the scaling factor `N` of the compressed 8-bit displacement for
+    /// this EVEX instruction's memory operand.
+    ///
+    /// For the `TupleN` types, the memory operand holds `N` elements whose
+    /// width is given by `EVEX.W`:
+    ///
+    /// ```
+    /// # use cranelift_assembler_x64_meta::dsl::{evex, fmt, r, w, Length::*, Location::*, TupleType::*};
+    /// let format = fmt("A", [w(xmm1), r(xmm_m128)]);
+    /// let scaling = |e| format.evex_scaling(&e);
+    /// assert_eq!(scaling(evex(L128, Tuple2).w0()), 8);
+    /// assert_eq!(scaling(evex(L256, Tuple2).w1()), 16);
+    /// assert_eq!(scaling(evex(L256, Tuple4).w0()), 16);
+    /// assert_eq!(scaling(evex(L512, Tuple4).w1()), 32);
+    /// assert_eq!(scaling(evex(L512, Tuple8).w0()), 32);
+    /// ```
+    pub fn evex_scaling(&self, evex: &dsl::Evex) -> i8 {
+        // NB: when bcast is supported in the future the calculation for `Full`
+        // and `Half` below needs to be updated.
+        assert!(evex.rounding.is_none());
+        // `LIG` instructions are scalar: the `L'L` bits are ignored by the
+        // processor (and emitted as `0b00`) so there is no vector length to
+        // scale by. Only tuple types that do not depend on the vector length
+        // are valid here.
+        let length_bytes = || match evex.length {
+            dsl::Length::LZ => panic!(
+                "EVEX has no `LZ` encoding (found in `{self}`); use `LIG` for scalar instructions"
+            ),
+            dsl::Length::LIG => {
+                panic!("`LIG` requires a scalar tuple type (found in `{self}`)")
+            }
             dsl::Length::L128 => 16,
             dsl::Length::L256 => 32,
             dsl::Length::L512 => 64,
@@ -259,39 +378,58 @@ impl dsl::Format {
         // Figure out, according to table 2-34 and 2-35 in the Intel manual,
         // what the scaling factor is for 8-bit displacements to pass through to
         // encoding.
-        let evex_scaling = Some(match evex.tuple_type {
-            dsl::TupleType::Full => {
-                assert!(!bcast);
-                length_bytes
-            }
-            dsl::TupleType::Half => {
-                assert!(!bcast);
-                length_bytes / 2
-            }
-            dsl::TupleType::FullMem => length_bytes,
-            // FIXME: according to table 2-35 this needs to take into account
-            // "InputSize" which isn't accounted for in our `Evex` structure at
-            // this time.
-            dsl::TupleType::Tuple1Scalar => unimplemented!(),
+        match evex.tuple_type {
+            dsl::TupleType::Full => length_bytes(),
+            dsl::TupleType::Half => length_bytes() / 2,
+            dsl::TupleType::FullMem => length_bytes(),
+            // For scalar instructions the "InputSize" of table 2-35 is the
+            // size of the memory operand itself.
+            dsl::TupleType::Tuple1Scalar => self.scalar_input_bytes(),
             dsl::TupleType::Tuple1Fixed => unimplemented!(),
-            dsl::TupleType::Tuple2 => unimplemented!(),
-            dsl::TupleType::Tuple4 => unimplemented!(),
-            dsl::TupleType::Tuple8 => 32,
-            dsl::TupleType::HalfMem => length_bytes / 2,
-            dsl::TupleType::QuarterMem => length_bytes / 4,
-            dsl::TupleType::EigthMem => length_bytes / 8,
+            dsl::TupleType::Tuple2 => self.evex_tuple_bytes(evex, 2, length_bytes()),
+            dsl::TupleType::Tuple4 => self.evex_tuple_bytes(evex, 4, length_bytes()),
+            dsl::TupleType::Tuple8 => self.evex_tuple_bytes(evex, 8, length_bytes()),
+            dsl::TupleType::HalfMem => length_bytes() / 2,
+            dsl::TupleType::QuarterMem => length_bytes() / 4,
+            dsl::TupleType::EigthMem => length_bytes() / 8,
             dsl::TupleType::Mem128 => 16,
             dsl::TupleType::Movddup => match evex.length {
-                dsl::Length::LZ | dsl::Length::LIG => unimplemented!(),
                 dsl::Length::L128 => 8,
-                dsl::Length::L256 => 32,
-                dsl::Length::L512 => 64,
+                _ => length_bytes(),
             },
-        });
+        }
+    }
 
-        self.generate_vex_or_evex_prefix(f, "EvexPrefix", &bits, is4, evex_scaling, || {
-            evex.unwrap_digit()
-        })
+    /// Return the size, in bytes, of the `count` elements of a `TupleN` memory
+    /// operand; elements are 32 bits wide with `EVEX.W0` and 64 bits wide with
+    /// `EVEX.W1`. Per table 2-35, the tuple must be smaller than the vector.
+    fn evex_tuple_bytes(&self, evex: &dsl::Evex, count: i8, length_bytes: i8) -> i8 {
+        assert!(
+            !evex.w.is_ignored(),
+            "`TupleN` requires `W0` or `W1` (found in `{self}`)"
+        );
+        let element_bytes = if evex.w.as_bool() { 8 } else { 4 };
+        let bytes = count * element_bytes;
+        assert!(
+            bytes < length_bytes,
+            "a {bytes}-byte tuple does not fit the vector length of `{self}`"
+        );
+        bytes
+    }
+
+    /// Return the size, in bytes, of the memory operand of a scalar EVEX
+    /// instruction; this is the "InputSize" used for `Tuple1Scalar` scaling.
+    fn scalar_input_bytes(&self) -> i8 {
+        use dsl::OperandKind::{Mem, RegMem};
+        let mem = self
+            .operands_by_kind()
+            .into_iter()
+            .find_map(|kind| match kind {
+                Mem(loc) | RegMem(loc) => Some(loc),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("no memory operand to scale in `{self}`"));
+        i8::try_from(mem.bits() / 8).unwrap()
     }
 
     /// Helper function to generate either a vex or evex prefix, mostly handling
@@ -433,18 +571,16 @@ impl dsl::Format {
             unknown => unimplemented!("unknown pattern: {unknown:?}"),
         };
 
+        let component = prefix_type.strip_suffix("Prefix").unwrap().to_uppercase();
+        generate_trace(f, &component, &[]);
         fmtln!(f, "prefix.encode(buf);");
         style
     }
 
     fn generate_modrm_byte(&self, f: &mut Formatter, modrm_style: ModRmStyle) {
-        let operands = self.operands_by_kind();
-        let bytes_at_end = match operands.as_slice() {
-            [.., dsl::OperandKind::Imm(imm)] => imm.bytes(),
-            _ => match modrm_style {
-                ModRmStyle::RegMemIs4 { .. } => 1,
-                _ => 0,
-            },
+        let bytes_at_end = match modrm_style {
+            ModRmStyle::RegMemIs4 { .. } => 1,
+            _ => self.immediates().map(|imm| imm.bytes()).sum(),
         };
 
         f.empty_line();
@@ -467,9 +603,11 @@ impl dsl::Format {
                 is4: _,
                 evex_scaling,
             } => {
+                generate_trace(f, "modrm", &self.generate_modrm_meaning(reg, rm));
                 match reg {
                     ModRmReg::Reg(reg) => fmtln!(f, "let reg = self.{reg}.enc();"),
                     ModRmReg::Digit(digit) => fmtln!(f, "let reg = {digit:#x};"),
+                    ModRmReg::Selector(selector) => fmtln!(f, "let reg = {:#x};", selector.bits()),
                 }
                 fmtln!(
                     f,
@@ -477,9 +615,11 @@ impl dsl::Format {
                 );
             }
             ModRmStyle::Reg { reg, rm } => {
+                generate_trace(f, "modrm", &self.generate_modrm_meaning(reg, rm));
                 match reg {
                     ModRmReg::Reg(reg) => fmtln!(f, "let reg = self.{reg}.enc();"),
                     ModRmReg::Digit(digit) => fmtln!(f, "let reg = {digit:#x};"),
+                    ModRmReg::Selector(selector) => fmtln!(f, "let reg = {:#x};", selector.bits()),
                 }
                 fmtln!(f, "self.{rm}.encode_modrm(buf, reg);");
             }
@@ -487,31 +627,158 @@ impl dsl::Format {
     }
 
     fn generate_immediate(&self, f: &mut Formatter, modrm_style: ModRmStyle) {
-        use dsl::OperandKind::Imm;
-        match self.operands_by_kind().as_slice() {
-            [prefix @ .., Imm(imm)] => {
-                assert!(!prefix.iter().any(|o| matches!(o, Imm(_))));
-                f.empty_line();
-                f.comment("Emit immediate.");
-                fmtln!(f, "self.{imm}.encode(buf);");
-            }
-            unknown => {
-                if let ModRmStyle::RegMemIs4 { is4, .. } = modrm_style {
-                    fmtln!(f, "buf.put1(self.{is4}.enc() << 4);");
+        let imms: Vec<_> = self.immediates().collect();
+        if let ModRmStyle::RegMemIs4 { is4, .. } = modrm_style {
+            // The `is4` register shares its byte with any immediate, which
+            // supplies the low four bits.
+            let mut meaning = vec![("is4", self.generate_trace_operand(is4))];
+            meaning.extend(
+                imms.iter()
+                    .map(|imm| ("imm", self.generate_trace_operand(*imm))),
+            );
+            generate_trace(f, "is4", &meaning);
+            match imms.as_slice() {
+                [] => fmtln!(f, "buf.put1(self.{is4}.enc() << 4);"),
+                [imm] => {
+                    assert_eq!(imm.bits(), 8, "an `is4` immediate must be `imm8`");
+                    fmtln!(
+                        f,
+                        "buf.put1((self.{is4}.enc() << 4) | (self.{imm}.value() & 0xf));"
+                    );
                 }
+                _ => panic!("an `is4` byte leaves room for only one immediate"),
+            }
+            return;
+        }
 
-                // Do nothing: no immediates expected.
-                assert!(!unknown.iter().any(|o| matches!(o, Imm(_))));
+        // Emit immediates in the order the DSL lists them, wherever they
+        // appear among the other operands.
+        if !imms.is_empty() {
+            f.empty_line();
+            f.comment("Emit immediate.");
+        }
+        let symbolic = self.symbolic_immediate();
+        for (i, imm) in imms.iter().enumerate() {
+            if symbolic == Some(*imm) {
+                // Relocations are relative to the end of the field; account
+                // for any immediates which follow it.
+                let after: u16 = imms[i + 1..].iter().map(|imm| imm.bytes()).sum();
+                let addend = -4 - i32::from(after);
+                f.add_block("if let Some(symbol) = self.symbol", |f| {
+                    fmtln!(f, "buf.add_reloc(RelocKind::CallPcRel4, symbol, {addend});");
+                });
             }
+            let value = self.generate_trace_operand(*imm);
+            generate_trace(f, &imm.to_string(), &[("", value)]);
+            fmtln!(f, "self.{imm}.encode(buf);");
         }
     }
+
+    /// Return the `Patch` list of a template for this format, with each
+    /// offset relative to the `end` of the encoding, or `None` if the format
+    /// has no immediates to patch. Immediates are emitted last, so their
+    /// offsets from the end do not depend on the other operands; only
+    /// REX-encoded formats qualify, since VEX and EVEX may pack an `is4`
+    /// register into the immediate byte.
+    pub(crate) fn generate_template_patches(&self, encoding: &dsl::Encoding) -> Option<String> {
+        if !matches!(encoding, dsl::Encoding::Rex(_)) {
+            return None;
+        }
+        let imms: Vec<_> = self.immediates().collect();
+        if imms.is_empty() {
+            return None;
+        }
+        let patches = imms.iter().enumerate().map(|(i, imm)| {
+            let from_end: u16 = imms[i..].iter().map(|imm| imm.bytes()).sum();
+            let size = imm.bytes();
+            format!("Patch {{ offset: end - {from_end}, size: {size} }}")
+        });
+        Some(patches.collect::<Vec<_>>().join(", "))
+    }
+
+    /// Return the expression printing the operand at `location`, for traces.
+    fn generate_trace_operand(&self, location: dsl::Location) -> String {
+        let op = self
+            .operands
+            .iter()
+            .find(|o| o.location == location)
+            .unwrap();
+        location.generate_to_string(op.extension)
+    }
+
+    /// Return the `reg=... rm=...` trace of a ModR/M byte.
+    fn generate_modrm_meaning(&self, reg: ModRmReg, rm: dsl::Location) -> Vec<(&str, String)> {
+        let reg = match reg {
+            ModRmReg::Reg(reg) => self.generate_trace_operand(reg),
+            ModRmReg::Digit(_) | ModRmReg::Selector(_) => {
+                format!("\"/{}\"", reg.unwrap_bits())
+            }
+        };
+        vec![("reg", reg), ("rm", self.generate_trace_operand(rm))]
+    }
+
+    /// Return the immediate operands, in DSL order.
+    fn immediates(&self) -> impl Iterator<Item = dsl::Location> + '_ {
+        self.operands_by_kind().into_iter().filter_map(|k| match k {
+            dsl::OperandKind::Imm(imm) => Some(imm),
+            _ => None,
+        })
+    }
+}
+
+/// `buf.trace(...);`: with the `trace` feature, mark the start of an encoding
+/// `component`, described by `meaning`'s `name=<expression>` pairs (or a
+/// bare `<expression>`, for an empty name); see
+/// `CodeSink::trace`.
+fn generate_trace(f: &mut Formatter, component: &str, meaning: &[(&str, String)]) {
+    fmtln!(f, "#[cfg(feature = \"trace\")]");
+    if meaning.is_empty() {
+        fmtln!(f, "buf.trace(\"{component}\", &String::new);");
+    } else {
+        let template: Vec<_> = meaning
+            .iter()
+            .map(|(name, _)| match *name {
+                "" => "{}".to_string(),
+                name => format!("{name}={{}}"),
+            })
+            .collect();
+        let args: Vec<_> = meaning.iter().map(|(_, expr)| expr.as_str()).collect();
+        fmtln!(
+            f,
+            "buf.trace(\"{component}\", &|| format!(\"{}\", {}));",
+            template.join(" "),
+            args.join(", ")
+        );
+    }
+}
+
+/// Check if an instruction's operands only need a memory operand (plus an
+/// optional immediate) encoded; the ModR/M `reg` field then holds a `/digit`.
+/// Fixed registers are implicit in the opcode and are never encoded, so any
+/// number of them may participate (e.g., `CMPXCHG16B` reads and writes four).
+fn only_memory_is_encoded(kinds: &[dsl::OperandKind]) -> bool {
+    use dsl::OperandKind::{FixedReg, Imm, Mem, RegMem};
+    let mut encoded = kinds.iter().filter(|k| !matches!(k, FixedReg(_)));
+    matches!(
+        (encoded.next(), encoded.next(), encoded.next()),
+        (Some(Mem(_) | RegMem(_)), None | Some(Imm(_)), None)
+    )
 }
 
 impl dsl::Rex {
     // `buf.put1(...);`
-    fn generate_opcodes(&self, f: &mut Formatter, first_op: Option<&dsl::Location>) {
+    fn generate_opcodes(
+        &self,
+        f: &mut Formatter,
+        first_op: Option<&dsl::Location>,
+        first_op_trace: Option<String>,
+    ) {
         f.empty_line();
         f.comment("Emit opcode(s).");
+        match (self.opcode_mod, first_op_trace) {
+            (Some(_), Some(reg)) => generate_trace(f, "opcode", &[("reg", reg)]),
+            _ => generate_trace(f, "opcode", &[]),
+        }
         if self.opcodes.escape {
             fmtln!(f, "buf.put1(0x0f);");
         }
@@ -534,6 +801,7 @@ impl dsl::Vex {
     fn generate_opcode(&self, f: &mut Formatter) {
         f.empty_line();
         f.comment("Emit opcode.");
+        generate_trace(f, "opcode", &[]);
         fmtln!(f, "buf.put1(0x{:x});", self.opcode);
     }
 }
@@ -543,6 +811,7 @@ impl dsl::Evex {
     fn generate_opcode(&self, f: &mut Formatter) {
         f.empty_line();
         f.comment("Emit opcode.");
+        generate_trace(f, "opcode", &[]);
         fmtln!(f, "buf.put1(0x{:x});", self.opcode);
     }
 }
@@ -26,6 +26,9 @@ impl dsl::Inst {
             if self.has_trap {
                 fmtln!(f, "pub trap: TrapCode,");
             }
+            if self.format.symbolic_immediate().is_some() {
+                fmtln!(f, "pub symbol: Option<Symbol>,");
+            }
         });
     }
 
@@ -63,11 +66,29 @@ impl dsl::Inst {
             f.empty_line();
             self.generate_encode_function(f);
             f.empty_line();
+            self.generate_encode_into_function(f);
+            f.empty_line();
+            self.generate_encode_aligned_function(f);
+            f.empty_line();
+            if let Some(patches) = self.template_patches() {
+                self.generate_template_function(f, &patches);
+                f.empty_line();
+            }
             self.generate_visit_function(f);
             f.empty_line();
             self.generate_is_available_function(f);
             f.empty_line();
             self.generate_features_function(f);
+            f.empty_line();
+            self.generate_macro_fusible_function(f);
+            f.empty_line();
+            self.generate_operand_strings_function(f);
+            if let dsl::Encoding::Evex(evex) = &self.encoding {
+                if let Some(mem) = self.format.uses_memory() {
+                    f.empty_line();
+                    self.generate_chooses_disp8_function(f, evex, mem);
+                }
+            }
         });
     }
 
@@ -94,6 +115,9 @@ impl dsl::Inst {
                 if self.has_trap {
                     fmtln!(f, "trap: trap.into(),");
                 }
+                if self.format.symbolic_immediate().is_some() {
+                    fmtln!(f, "symbol: None,");
+                }
             });
         });
     }
@@ -137,6 +161,171 @@ impl dsl::Inst {
         );
     }
 
+    /// `fn encode_into(&self, buf: &mut [u8]) -> Result<usize, EncodeError> { ... }`
+    fn generate_encode_into_function(&self, f: &mut Formatter) {
+        fmtln!(
+            f,
+            "/// Encode this instruction into the fixed-size `buf`, returning"
+        );
+        fmtln!(
+            f,
+            "/// the encoded length or an error if `buf` is too small or the"
+        );
+        fmtln!(f, "/// instruction refers to a label, constant or symbol.");
+        f.add_block(
+            "pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize, EncodeError>",
+            |f| {
+                fmtln!(f, "let mut sink = SliceSink::new(buf);");
+                fmtln!(f, "self.encode(&mut sink);");
+                fmtln!(f, "sink.finish()");
+            },
+        );
+    }
+
+    /// `fn encode_aligned(&self, ...) { ... }`
+    fn generate_encode_aligned_function(&self, f: &mut Formatter) {
+        fmtln!(
+            f,
+            "/// Encode this instruction after padding `offset`, the current offset"
+        );
+        fmtln!(
+            f,
+            "/// into `buf`, with multi-byte `nop`s up to a multiple of `align`."
+        );
+        f.add_block(
+            "pub fn encode_aligned(&self, buf: &mut impl CodeSink, offset: usize, align: usize)",
+            |f| {
+                fmtln!(f, "crate::encode_alignment_nops(buf, offset, align);");
+                fmtln!(f, "self.encode(buf);");
+            },
+        );
+    }
+
+    /// `fn template(&self) -> Template { ... }`
+    fn generate_template_function(&self, f: &mut Formatter, patches: &str) {
+        fmtln!(
+            f,
+            "/// Encode this instruction into a [`Template`] whose immediates can be"
+        );
+        fmtln!(
+            f,
+            "/// patched later; all other operands are fixed by this encoding."
+        );
+        f.add_block("pub fn template(&self) -> Template", |f| {
+            fmtln!(f, "let mut bytes = Vec::new();");
+            fmtln!(f, "self.encode(&mut bytes);");
+            fmtln!(f, "let end = bytes.len();");
+            fmtln!(f, "Template::new(bytes, vec![{patches}])");
+        });
+    }
+
+    /// Return the patches of this instruction's template, if it has one;
+    /// custom encodings may not place immediates last, so they have none.
+    fn template_patches(&self) -> Option<String> {
+        if self.custom.contains(dsl::Customization::Encode) {
+            return None;
+        }
+        self.format.generate_template_patches(&self.encoding)
+    }
+
+    /// Find the form of this instruction taking a sign-extended 8-bit
+    /// immediate in place of its 16- or 32-bit one, e.g., `addl_mi_sxb` for
+    /// `addl_mi` and `addl_i`. Forms which differ in anything other than the
+    /// immediate width (and the accumulator being encoded in ModR/M) do not
+    /// match.
+    pub(crate) fn short_immediate_form<'a>(&self, all: &'a [dsl::Inst]) -> Option<&'a dsl::Inst> {
+        use dsl::{Customization::Encode, Location::*, OperandKind::*};
+
+        let dsl::Encoding::Rex(_) = &self.encoding else {
+            return None;
+        };
+        if self.has_trap || self.custom.contains(Encode) {
+            return None;
+        }
+        if self.format.symbolic_immediate().is_some() {
+            return None;
+        }
+        if !self
+            .format
+            .operands
+            .iter()
+            .any(|o| matches!(o.location, imm16 | imm32))
+        {
+            return None;
+        }
+        let format = self
+            .format
+            .name
+            .strip_suffix("_SXL")
+            .unwrap_or(&self.format.name);
+        let format = match format {
+            "I" => "MI_SXB".to_string(),
+            _ => format!("{format}_SXB"),
+        };
+        let short = all
+            .iter()
+            .find(|i| i.mnemonic == self.mnemonic && i.format.name == format)?;
+        let operands_match = short.format.operands.len() == self.format.operands.len()
+            && self
+                .format
+                .operands
+                .iter()
+                .zip(&short.format.operands)
+                .all(|(a, b)| {
+                    a.mutability == b.mutability
+                        && match (a.location.kind(), b.location.kind()) {
+                            (Imm(_), Imm(imm8)) => b.extension.is_sign_extended(),
+                            (FixedReg(acc), RegMem(rm)) => acc.bits() == rm.bits(),
+                            _ => a.location == b.location,
+                        }
+                });
+        let matches = operands_match
+            && short.features == self.features
+            && !short.has_trap
+            && !short.custom.contains(Encode);
+        matches.then_some(short)
+    }
+
+    /// `impl <inst> { fn encode_short(&self, ...) { ... } }`
+    pub fn generate_encode_short_impl(&self, f: &mut Formatter, short: &dsl::Inst) {
+        use dsl::OperandKind::*;
+
+        let impl_block = self.generate_impl_block_start();
+        let struct_name = self.struct_name_with_generic();
+        let short_name = short.name();
+        let short_path = short.struct_name_with_generic().replace('<', "::<");
+        f.add_block(&format!("{impl_block} {struct_name}"), |f| {
+            fmtln!(
+                f,
+                "/// Encode this instruction as `{short_name}` if its immediate fits in"
+            );
+            fmtln!(f, "/// a sign-extended byte, otherwise as with `encode`.");
+            f.add_block("pub fn encode_short(&self, buf: &mut impl CodeSink)", |f| {
+                let mut imm = String::new();
+                let args = comma_join(self.format.operands.iter().map(|o| {
+                    let loc = o.location;
+                    match loc.kind() {
+                        Imm(_) => {
+                            let bits = loc.bits();
+                            imm = if o.extension.is_sign_extended() {
+                                format!("self.{loc}.value()")
+                            } else {
+                                format!("self.{loc}.value() as i{bits}")
+                            };
+                            "imm8".to_string()
+                        }
+                        FixedReg(_) => format!("GprMem::Gpr(self.{loc}.0)"),
+                        _ => format!("self.{loc}"),
+                    }
+                }));
+                f.add_block(&format!("match i8::try_from({imm})"), |f| {
+                    fmtln!(f, "Ok(imm8) => {short_path}::new({args}).encode(buf),");
+                    fmtln!(f, "Err(_) => self.encode(buf),");
+                });
+            });
+        });
+    }
+
     // `buf.add_trap(...)`
     fn generate_possible_trap(&self, f: &mut Formatter) {
         if self.has_trap {
@@ -244,6 +433,81 @@ impl dsl::Inst {
         });
     }
 
+    /// `fn macro_fusible(&self) -> bool { ... }`
+    fn generate_macro_fusible_function(&self, f: &mut Formatter) {
+        fmtln!(
+            f,
+            "/// Whether this instruction can fuse with a following `jcc`."
+        );
+        fmtln!(f, "#[must_use]");
+        fmtln!(f, "#[inline]");
+        f.add_block("pub const fn macro_fusible(&self) -> bool", |f| {
+            fmtln!(f, "{}", self.is_macro_fusible());
+        });
+    }
+
+    /// `fn operand_strings(&self) -> OperandStrings { ... }`
+    fn generate_operand_strings_function(&self, f: &mut Formatter) {
+        fmtln!(f, "#[must_use]");
+        f.add_block("pub fn operand_strings(&self) -> OperandStrings", |f| {
+            for op in self.format.operands.iter().filter(|o| !o.implicit) {
+                let location = op.location;
+                let to_string = location.generate_to_string(op.extension);
+                fmtln!(f, "let {location} = {to_string};");
+            }
+            if let dsl::Encoding::Rex(dsl::Rex {
+                segment: Some(segment),
+                ..
+            }) = &self.encoding
+            {
+                let mem = self.format.uses_memory().unwrap();
+                fmtln!(f, "let {mem} = format!(\"%{segment}:{{{mem}}}\");");
+            }
+            let mut att = self.format.generate_att_style_operands();
+            let mut intel = self.format.generate_intel_style_operands();
+            if let dsl::Encoding::Evex(dsl::Evex {
+                rounding: Some(rounding),
+                ..
+            }) = &self.encoding
+            {
+                att.insert_str(0, &format!("{{{rounding}}}, "));
+                intel.push_str(&format!(", {{{rounding}}}"));
+            }
+            let as_string = |ops: &str| {
+                if ops.is_empty() {
+                    "String::new()".to_string()
+                } else {
+                    format!("format!(\"{ops}\")")
+                }
+            };
+            let att = as_string(&att);
+            let intel = as_string(&intel);
+            fmtln!(f, "OperandStrings {{ att: {att}, intel: {intel} }}");
+        });
+    }
+
+    /// `fn chooses_disp8(&self) -> bool { ... }`
+    fn generate_chooses_disp8_function(
+        &self,
+        f: &mut Formatter,
+        evex: &dsl::Evex,
+        mem: dsl::Location,
+    ) {
+        let scaling = self.format.evex_scaling(evex);
+        fmtln!(
+            f,
+            "/// Return `true` if the memory operand's displacement is encoded as a"
+        );
+        fmtln!(
+            f,
+            "/// compressed 8-bit displacement (scaled by {scaling})."
+        );
+        fmtln!(f, "#[must_use]");
+        f.add_block("pub fn chooses_disp8(&self) -> bool", |f| {
+            fmtln!(f, "self.{mem}.chooses_disp8(Some({scaling}))");
+        });
+    }
+
     /// `impl Display for <inst> { ... }`
     pub fn generate_display_impl(&self, f: &mut Formatter) {
         use crate::dsl::Customization::*;
@@ -270,7 +534,22 @@ impl dsl::Inst {
                             let to_string = location.generate_to_string(op.extension);
                             fmtln!(f, "let {location} = {to_string};");
                         }
-                        let ordered_ops = self.format.generate_att_style_operands();
+                        if let dsl::Encoding::Rex(dsl::Rex {
+                            segment: Some(segment),
+                            ..
+                        }) = &self.encoding
+                        {
+                            let mem = self.format.uses_memory().unwrap();
+                            fmtln!(f, "let {mem} = format!(\"%{segment}:{{{mem}}}\");");
+                        }
+                        let mut ordered_ops = self.format.generate_att_style_operands();
+                        if let dsl::Encoding::Evex(dsl::Evex {
+                            rounding: Some(rounding),
+                            ..
+                        }) = &self.encoding
+                        {
+                            ordered_ops.insert_str(0, &format!("{{{rounding}}}, "));
+                        }
                         let mut implicit_ops = self.format.generate_implicit_operands();
                         if self.has_trap {
                             fmtln!(f, "let trap = self.trap;");
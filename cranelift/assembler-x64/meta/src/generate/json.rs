@@ -0,0 +1,152 @@
+//! Describe the DSL-defined instructions as JSON, for use by external tools.
+//!
+//! This avoids a dependency on a serialization crate: all values printed here
+//! come from the DSL itself so only minimal string escaping is necessary.
+
+use crate::dsl;
+use std::fmt::Write;
+
+/// `[{ "name": ..., "mnemonic": ..., ... }, ...]`
+pub fn json(insts: &[dsl::Inst]) -> String {
+    let mut out = String::from("[\n");
+    for (i, inst) in insts.iter().enumerate() {
+        let comma = if i + 1 < insts.len() { "," } else { "" };
+        writeln!(out, "  {}{comma}", inst.generate_json()).unwrap();
+    }
+    out.push_str("]\n");
+    out
+}
+
+impl dsl::Inst {
+    fn generate_json(&self) -> String {
+        let operands = self.format.operands.iter().map(dsl::Operand::generate_json);
+        object([
+            ("name", string(self.name())),
+            ("mnemonic", string(&self.mnemonic)),
+            ("format", string(&self.format.name)),
+            ("operands", array(operands)),
+            ("encoding", self.encoding.generate_json()),
+            ("features", string(&self.features)),
+            ("macro_fusible", self.is_macro_fusible().to_string()),
+        ])
+    }
+}
+
+impl dsl::Operand {
+    fn generate_json(&self) -> String {
+        let kind = match self.location.kind() {
+            dsl::OperandKind::FixedReg(_) => "fixed_reg",
+            dsl::OperandKind::Imm(_) => "imm",
+            dsl::OperandKind::Reg(_) => "reg",
+            dsl::OperandKind::RegMem(_) => "reg_mem",
+            dsl::OperandKind::Mem(_) => "mem",
+        };
+        let mutability = match self.mutability {
+            dsl::Mutability::Read => "r",
+            dsl::Mutability::ReadWrite => "rw",
+            dsl::Mutability::Write => "w",
+        };
+        object([
+            ("location", string(self.location)),
+            ("kind", string(kind)),
+            ("bits", self.location.bits().to_string()),
+            ("mutability", string(mutability)),
+            ("implicit", self.implicit.to_string()),
+            ("symbolic", self.symbolic.to_string()),
+        ])
+    }
+}
+
+impl dsl::Encoding {
+    fn generate_json(&self) -> String {
+        match self {
+            dsl::Encoding::Rex(rex) => {
+                let prefixes = [
+                    rex.group1().map(string),
+                    rex.group2().map(string),
+                    rex.opcodes.prefixes.group3.as_ref().map(string),
+                    rex.opcodes.prefixes.group4.as_ref().map(string),
+                ];
+                let opcodes = [
+                    rex.opcodes.escape.then(|| byte(0x0F)),
+                    Some(byte(rex.opcodes.primary)),
+                    rex.opcodes.secondary.map(byte),
+                ];
+                object([
+                    ("kind", string("rex")),
+                    ("description", string(self)),
+                    ("prefixes", array(prefixes.into_iter().flatten())),
+                    ("opcodes", array(opcodes.into_iter().flatten())),
+                    ("w", string(&rex.w)),
+                    ("modrm", optional(rex.modrm)),
+                    ("opcode_mod", optional(rex.opcode_mod)),
+                    ("imm", imm(&rex.imm)),
+                    (
+                        "acc_opcode",
+                        optional(rex.acc_opcode.map(|b| format!("{b:#04X}"))),
+                    ),
+                ])
+            }
+            dsl::Encoding::Vex(vex) => object([
+                ("kind", string("vex")),
+                ("description", string(self)),
+                ("length", string(&vex.length)),
+                ("pp", optional(vex.pp)),
+                ("map", optional(vex.mmmmm)),
+                ("w", string(&vex.w)),
+                ("opcode", byte(vex.opcode)),
+                ("modrm", optional(vex.modrm)),
+                ("imm", imm(&vex.imm)),
+                ("is4", vex.is4.to_string()),
+            ]),
+            dsl::Encoding::Evex(evex) => object([
+                ("kind", string("evex")),
+                ("description", string(self)),
+                ("length", string(&evex.length)),
+                ("pp", optional(evex.pp)),
+                ("map", optional(evex.mmm)),
+                ("w", string(&evex.w)),
+                ("opcode", byte(evex.opcode)),
+                ("modrm", optional(evex.modrm)),
+                ("imm", imm(&evex.imm)),
+                ("tuple_type", string(format!("{:?}", evex.tuple_type))),
+                ("rounding", optional(evex.rounding)),
+            ]),
+        }
+    }
+}
+
+fn object<const N: usize>(fields: [(&str, String); N]) -> String {
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|(key, value)| format!("\"{key}\": {value}"))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+fn array(values: impl Iterator<Item = String>) -> String {
+    format!("[{}]", values.collect::<Vec<_>>().join(", "))
+}
+
+fn string(value: impl ToString) -> String {
+    let value = value.to_string();
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn optional(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "null".to_string(), string)
+}
+
+fn byte(value: u8) -> String {
+    string(format!("{value:#04X}"))
+}
+
+/// Immediates print as `ib`, `iw`, etc., or as nothing when absent.
+fn imm(imm: impl ToString) -> String {
+    let imm = imm.to_string();
+    if imm.is_empty() {
+        "null".to_string()
+    } else {
+        string(imm)
+    }
+}
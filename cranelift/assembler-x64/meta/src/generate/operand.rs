@@ -14,12 +14,14 @@ impl dsl::Operand {
                     format!("Imm{bits}")
                 }
             }
-            al | ax | eax | rax | rbx | cl | rcx | dx | edx | rdx => {
+            al | ax | eax | rax | rbx | cl | rcx | dx | edx | rdx | rsi | rdi => {
                 let enc = match self.location {
                     al | ax | eax | rax => "{ gpr::enc::RAX }",
                     rbx => "{ gpr::enc::RBX }",
                     cl | rcx => "{ gpr::enc::RCX }",
                     dx | edx | rdx => "{ gpr::enc::RDX }",
+                    rsi => "{ gpr::enc::RSI }",
+                    rdi => "{ gpr::enc::RDI }",
                     _ => unreachable!(),
                 };
                 format!("Fixed<R::{mut_}Gpr, {enc}>")
@@ -58,7 +60,7 @@ impl dsl::Location {
                     None => unreachable!(),
                 }
             }
-            al | ax | eax | rax | rbx | cl | rcx | dx | edx | rdx | xmm0 => {
+            al | ax | eax | rax | rbx | cl | rcx | dx | edx | rdx | rsi | rdi | xmm0 => {
                 match self.generate_size() {
                     Some(size) => format!("self.{self}.to_string(Some({size}))"),
                     None => format!("self.{self}.to_string(None)"),
@@ -80,7 +82,7 @@ impl dsl::Location {
             al | cl | r8 | rm8 => Some("Size::Byte"),
             ax | dx | r16 | rm16 => Some("Size::Word"),
             eax | edx | r32 | r32a | r32b | rm32 => Some("Size::Doubleword"),
-            rax | rbx | rcx | rdx | r64 | r64a | r64b | rm64 => Some("Size::Quadword"),
+            rax | rbx | rcx | rdx | rsi | rdi | r64 | r64a | r64b | rm64 => Some("Size::Quadword"),
             m8 | m16 | m32 | m64 | m128 => {
                 panic!("no need to generate a size for memory-only access")
             }
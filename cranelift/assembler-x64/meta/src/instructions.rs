@@ -30,11 +30,12 @@ mod setcc;
 mod shift;
 mod sqrt;
 mod stack;
+mod string;
 mod sub;
 mod unpack;
 mod xor;
 
-use crate::dsl::{Feature, Inst, Mutability, OperandKind};
+use crate::dsl::{Customization, Encoding, Feature, Inst, Mutability, OperandKind};
 use std::collections::HashMap;
 
 #[must_use]
@@ -70,15 +71,120 @@ pub fn list() -> Vec<Inst> {
     all.extend(shift::list());
     all.extend(sqrt::list());
     all.extend(stack::list());
+    all.extend(string::list());
     all.extend(sub::list());
     all.extend(unpack::list());
     all.extend(xor::list());
 
     check_avx_alternates(&mut all);
+    check_digit_operands(&all);
+    check_unique_encodings(&all);
 
     all
 }
 
+/// Checks that instructions with a `/digit` opcode extension have no register
+/// operand competing with the digit for the ModR/M `reg` field.
+///
+/// The digit leaves only the ModR/M `rm` field--and, for VEX and EVEX, the
+/// `vvvv` field--to encode explicit register and memory operands. Any
+/// additional operand would be encoded in the `reg` field in place of the
+/// digit, silently producing a different instruction.
+///
+/// ```should_panic
+/// # use cranelift_assembler_x64_meta::dsl::{fmt, inst, r, rex, rw, Feature::*, Location::*};
+/// # use cranelift_assembler_x64_meta::instructions::check_digit_operands;
+/// let shl = inst("shlx", fmt("MR", [rw(rm32), r(r32)]), rex(0xD3).digit(4), _64b | compat);
+/// check_digit_operands(&[shl]); // panics: `r32` has nowhere to go.
+/// ```
+///
+/// # Panics
+///
+/// Panics if an instruction has too many operands for its digit, naming the
+/// instruction.
+pub fn check_digit_operands(all: &[Inst]) {
+    for inst in all {
+        let (modrm, available) = match &inst.encoding {
+            Encoding::Rex(rex) => (rex.modrm, 1),
+            Encoding::Vex(vex) => (vex.modrm, 2),
+            Encoding::Evex(evex) => (evex.modrm, 2),
+        };
+        let Some(modrm) = modrm.filter(|m| m.fixed_reg_bits().is_some()) else {
+            continue;
+        };
+        let encoded = inst
+            .format
+            .operands_by_kind()
+            .into_iter()
+            .filter(|k| {
+                matches!(
+                    k,
+                    OperandKind::Reg(_) | OperandKind::RegMem(_) | OperandKind::Mem(_)
+                )
+            })
+            .count();
+        assert!(
+            encoded <= available,
+            "`{}` uses the ModR/M `reg` field for opcode extension `{modrm}` but has \
+             {encoded} register or memory operands to encode (at most {available}):\n{inst}",
+            inst.name()
+        );
+    }
+}
+
+/// Checks that no two instructions share an encoding for the same operands.
+///
+/// Two definitions collide if they emit the same prefixes, opcode bytes, and
+/// ModR/M `reg` field (see [`Encoding`]'s `Display`) and their ModR/M `rm`
+/// operands can both be registers or can both be memory; e.g., `movss_a_r` and
+/// `movss_a_m` share an encoding but are distinguished by the ModR/M `mod`
+/// bits. Instructions with custom encoding functions (e.g., multi-byte `nop`s)
+/// are not checked.
+///
+/// ```should_panic
+/// # use cranelift_assembler_x64_meta::dsl::{fmt, inst, r, rex, rw, Feature::*, Location::*};
+/// # use cranelift_assembler_x64_meta::instructions::{check_unique_encodings, list};
+/// let mut all = list();
+/// all.push(inst("addx", fmt("MI", [rw(rm32), r(imm32)]), rex(0x81).digit(0).id(), _64b | compat));
+/// check_unique_encodings(&all); // panics: `addx_mi` duplicates `addl_mi`.
+/// ```
+///
+/// # Panics
+///
+/// Panics if two instructions collide, naming both.
+pub fn check_unique_encodings(all: &[Inst]) {
+    let mut seen: HashMap<String, Vec<&Inst>> = HashMap::new();
+    for inst in all
+        .iter()
+        .filter(|i| !i.custom.contains(Customization::Encode))
+    {
+        let key = inst.encoding.to_string();
+        let others = seen.entry(key).or_default();
+        for other in others.iter() {
+            let (a, b) = (rm_domain(inst), rm_domain(other));
+            if (a.0 && b.0) || (a.1 && b.1) {
+                panic!(
+                    "duplicate encoding `{}` for `{}` and `{}`:\n{other}\n{inst}",
+                    inst.encoding,
+                    other.name(),
+                    inst.name()
+                );
+            }
+        }
+        others.push(inst);
+    }
+}
+
+/// Return whether the ModR/M `rm` operand of `inst` can be a register and
+/// whether it can be memory, in that order.
+fn rm_domain(inst: &Inst) -> (bool, bool) {
+    match inst.format.uses_memory().map(|l| l.kind()) {
+        Some(OperandKind::Mem(_)) => (false, true),
+        Some(_) => (true, true),
+        None => (true, false),
+    }
+}
+
 /// Checks that assigned AVX alternates are correctly applied to SSE
 /// instructions.
 ///
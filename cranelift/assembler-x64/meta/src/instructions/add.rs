@@ -1,27 +1,18 @@
-use crate::dsl::{Customization::*, Feature::*, Inst, Length::*, Location::*, TupleType::*};
-use crate::dsl::{align, evex, fmt, inst, r, rex, rw, sxl, sxq, vex, w};
+use crate::dsl::{
+    Customization::*, Feature::*, Inst, Length::*, Location::*, Rounding::*, TupleType::*,
+};
+use crate::dsl::{align, bwlq, evex, fmt, inst, r, rex, rw, sxl, sxq, vex, w};
 
 #[rustfmt::skip] // Keeps instructions on a single line.
 pub fn list() -> Vec<Inst> {
-    vec![
-        inst("addb", fmt("I", [rw(al), r(imm8)]), rex(0x4).ib(), _64b | compat),
-        inst("addw", fmt("I", [rw(ax), r(imm16)]), rex([0x66, 0x5]).iw(), _64b | compat),
-        inst("addl", fmt("I", [rw(eax), r(imm32)]), rex(0x5).id(), _64b | compat),
-        inst("addq", fmt("I_SXL", [rw(rax), sxq(imm32)]), rex(0x5).w().id(), _64b),
-        inst("addb", fmt("MI", [rw(rm8), r(imm8)]), rex(0x80).digit(0).ib(), _64b | compat),
-        inst("addw", fmt("MI", [rw(rm16), r(imm16)]), rex([0x66, 0x81]).digit(0).iw(), _64b | compat),
-        inst("addl", fmt("MI", [rw(rm32), r(imm32)]), rex(0x81).digit(0).id(), _64b | compat),
-        inst("addq", fmt("MI_SXL", [rw(rm64), sxq(imm32)]), rex(0x81).w().digit(0).id(), _64b),
+    let mut insts = vec![];
+    insts.extend(bwlq("add", "I", |w| [rw(w.acc()), r(w.imm())], || rex(0x4).ib()));
+    insts.extend(bwlq("add", "MI", |w| [rw(w.rm()), r(w.imm())], || rex(0x80).digit(0).ib()));
+    insts.extend(bwlq("add", "MR", |w| [rw(w.rm()), r(w.r())], || rex(0x0).r()));
+    insts.extend(bwlq("add", "RM", |w| [rw(w.r()), r(w.rm())], || rex(0x2).r()));
+    insts.extend([
         inst("addl", fmt("MI_SXB", [rw(rm32), sxl(imm8)]), rex(0x83).digit(0).ib(), _64b | compat),
         inst("addq", fmt("MI_SXB", [rw(rm64), sxq(imm8)]), rex(0x83).w().digit(0).ib(), _64b),
-        inst("addb", fmt("MR", [rw(rm8), r(r8)]), rex(0x0).r(), _64b | compat),
-        inst("addw", fmt("MR", [rw(rm16), r(r16)]), rex([0x66, 0x1]).r(), _64b | compat),
-        inst("addl", fmt("MR", [rw(rm32), r(r32)]), rex(0x1).r(), _64b | compat),
-        inst("addq", fmt("MR", [rw(rm64), r(r64)]), rex(0x1).w().r(), _64b),
-        inst("addb", fmt("RM", [rw(r8), r(rm8)]), rex(0x2).r(), _64b | compat),
-        inst("addw", fmt("RM", [rw(r16), r(rm16)]), rex([0x66, 0x3]).r(), _64b | compat),
-        inst("addl", fmt("RM", [rw(r32), r(rm32)]), rex(0x3).r(), _64b | compat),
-        inst("addq", fmt("RM", [rw(r64), r(rm64)]), rex(0x3).w().r(), _64b),
         // Add with carry.
         inst("adcb", fmt("I", [rw(al), r(imm8)]), rex(0x14).ib(), _64b | compat),
         inst("adcw", fmt("I", [rw(ax), r(imm16)]), rex([0x66, 0x15]).iw(), _64b | compat),
@@ -70,7 +61,7 @@ pub fn list() -> Vec<Inst> {
         // Vector instructions.
         inst("addss", fmt("A", [rw(xmm1), r(xmm_m32)]), rex([0xF3, 0x0F, 0x58]).r(), (_64b | compat) & sse).alt(avx, "vaddss_b"),
         inst("addsd", fmt("A", [rw(xmm1), r(xmm_m64)]), rex([0xF2, 0x0F, 0x58]).r(), (_64b | compat) & sse2).alt(avx, "vaddsd_b"),
-        inst("addps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x58]).r(), (_64b | compat) & sse).alt(avx, "vaddps_b"),
+        inst("addps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x58]).np().r(), (_64b | compat) & sse).alt(avx, "vaddps_b"),
         inst("addpd", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0x58]).r(), (_64b | compat) & sse2).alt(avx, "vaddpd_b"),
         inst("paddb", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0xFC]).r(), (_64b | compat) & sse2).alt(avx, "vpaddb_b"),
         inst("paddw", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0xFD]).r(), (_64b | compat) & sse2).alt(avx, "vpaddw_b"),
@@ -97,5 +88,9 @@ pub fn list() -> Vec<Inst> {
         inst("vphaddw", fmt("B", [w(xmm1), r(xmm2), r(xmm_m128)]), vex(L128)._66()._0f38().op(0x01).r(), (_64b | compat) & avx),
         inst("vphaddd", fmt("B", [w(xmm1), r(xmm2), r(xmm_m128)]), vex(L128)._66()._0f38().op(0x02).r(), (_64b | compat) & avx),
         inst("vaddpd", fmt("C", [w(xmm1), r(xmm2), r(xmm_m128)]), evex(L128, Full)._66()._0f().w1().op(0x58).r(), (_64b | compat) & avx512vl),
-    ]
+        inst("vaddss", fmt("C", [w(xmm1), r(xmm2), r(xmm_m32)]), evex(LIG, Tuple1Scalar)._f3()._0f().w0().op(0x58).r(), (_64b | compat) & avx512f),
+        inst("vaddsd", fmt("C", [w(xmm1), r(xmm2), r(xmm_m64)]), evex(LIG, Tuple1Scalar)._f2()._0f().w1().op(0x58).r(), (_64b | compat) & avx512f),
+        inst("vaddsd", fmt("C_RU_SAE", [w(xmm1), r(xmm2), r(xmm3)]), evex(LIG, Tuple1Scalar)._f2()._0f().w1().op(0x58).r().er(RuSae), (_64b | compat) & avx512f),
+    ]);
+    insts
 }
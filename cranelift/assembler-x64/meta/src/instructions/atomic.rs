@@ -10,8 +10,9 @@ pub fn list() -> Vec<Inst> {
     vec![
         // Note that for xchg the "MR" variants are omitted from the Intel
         // manual as they have the exact same encoding as the "RM" variant.
-        // Additionally the "O" variants are omitted as they're just exchanging
-        // registers which isn't needed by Cranelift at this time.
+        // The "O" variants, which exchange a register with the accumulator,
+        // are folded into the register-only forms below, which use them when
+        // either register is the accumulator.
         //
         // Also note that these have a custom display implementation to swap the
         // order of the operands to match what Capstone prints.
@@ -19,6 +20,8 @@ pub fn list() -> Vec<Inst> {
         inst("xchgw", fmt("RM", [rw(r16), rw(m16)]), rex([0x66, 0x87]).r(), _64b | compat).custom(Display),
         inst("xchgl", fmt("RM", [rw(r32), rw(m32)]), rex(0x87).r(), _64b | compat).custom(Display),
         inst("xchgq", fmt("RM", [rw(r64), rw(m64)]), rex(0x87).w().r(), _64b).custom(Display),
+        inst("xchgl", fmt("RR", [rw(r32a), rw(r32b)]), rex(0x87).r().or_acc(0x90), _64b | compat).custom(Display),
+        inst("xchgq", fmt("RR", [rw(r64a), rw(r64b)]), rex(0x87).w().r().or_acc(0x90), _64b).custom(Display),
 
         inst("cmpxchg16b", cmpxchg16b_m.clone(), rex([0x0f, 0xc7]).digit(1).w(), _64b & cmpxchg16b),
         inst("lock_cmpxchg16b", cmpxchg16b_m.clone(), rex([0xf0, 0x0f, 0xc7]).digit(1).w(), _64b & cmpxchg16b).custom(Mnemonic),
@@ -5,7 +5,7 @@ use crate::dsl::{align, evex, fmt, inst, r, rex, rw, vex, w};
 pub fn list() -> Vec<Inst> {
     vec![
         // From 32-bit floating point.
-        inst("cvtps2pd", fmt("A", [w(xmm1), r(xmm_m64)]), rex([0x0F, 0x5A]).r(), (_64b | compat) & sse2),
+        inst("cvtps2pd", fmt("A", [w(xmm1), r(xmm_m64)]), rex([0x0F, 0x5A]).np().r(), (_64b | compat) & sse2),
         inst("cvttps2dq", fmt("A", [w(xmm1), r(align(xmm_m128))]), rex([0xF3, 0x0F, 0x5B]).r(), (_64b | compat) & sse2),
         inst("cvtss2sd", fmt("A", [rw(xmm1), r(xmm_m32)]), rex([0xF3, 0x0F, 0x5A]).r(), (_64b | compat) & sse2),
         inst("cvtss2si", fmt("A", [w(r32), r(xmm_m32)]), rex([0xF3, 0x0F, 0x2D]).r(), (_64b | compat) & sse),
@@ -39,7 +39,7 @@ pub fn list() -> Vec<Inst> {
         inst("vcvttsd2si", fmt("AQ", [w(r64), r(xmm_m64)]), vex(LIG)._f2()._0f().w1().op(0x2C).r(), _64b & avx),
 
         // From signed 32-bit integer.
-        inst("cvtdq2ps", fmt("A", [w(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x5B]).r(), (_64b | compat) & sse2),
+        inst("cvtdq2ps", fmt("A", [w(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x5B]).np().r(), (_64b | compat) & sse2),
         inst("cvtdq2pd", fmt("A", [w(xmm1), r(xmm_m64)]), rex([0xF3, 0x0F, 0xE6]).r(), (_64b | compat) & sse2),
         inst("cvtsi2ssl", fmt("A", [rw(xmm1), r(rm32)]), rex([0xF3, 0x0F, 0x2A]).r(), (_64b | compat) & sse),
         inst("cvtsi2ssq", fmt("A", [rw(xmm1), r(rm64)]), rex([0xF3, 0x0F, 0x2A]).w().r(), _64b & sse),
@@ -1,5 +1,5 @@
 use crate::dsl::{Customization::*, Feature::*, Inst, Location::*};
-use crate::dsl::{fmt, inst, r, rex, sxq};
+use crate::dsl::{fmt, inst, r, rex, sxq, symbolic};
 
 #[rustfmt::skip] // Keeps instructions on a single line.
 pub fn list() -> Vec<Inst> {
@@ -7,7 +7,7 @@ pub fn list() -> Vec<Inst> {
         inst("jmpq", fmt("M", [r(rm64)]), rex([0xFF]).digit(4), _64b).custom(Display),
 
         inst("jmp", fmt("D8", [r(sxq(imm8))]), rex([0xEB]).ib(), _64b | compat).custom(Display),
-        inst("jmp", fmt("D32", [r(sxq(imm32))]), rex([0xE9]).id(), _64b | compat).custom(Display),
+        inst("jmp", fmt("D32", [r(symbolic(sxq(imm32)))]), rex([0xE9]).id(), _64b | compat).custom(Display),
 
         // Note that the Intel manual lists many mnemonics for this family of
         // instructions which are duplicates of other mnemonics. The order here
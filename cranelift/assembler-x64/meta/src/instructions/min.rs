@@ -8,7 +8,7 @@ pub fn list() -> Vec<Inst> {
         // behavior; see `max.rs`.
         inst("minss", fmt("A", [rw(xmm1), r(xmm_m32)]), rex([0xF3, 0x0F, 0x5D]).r(), (_64b | compat) & sse).alt(avx, "vminss_b"),
         inst("minsd", fmt("A", [rw(xmm1), r(xmm_m64)]), rex([0xF2, 0x0F, 0x5D]).r(), (_64b | compat) & sse2).alt(avx, "vminsd_b"),
-        inst("minps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x5D]).r(), (_64b | compat) & sse).alt(avx, "vminps_b"),
+        inst("minps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x5D]).np().r(), (_64b | compat) & sse).alt(avx, "vminps_b"),
         inst("minpd", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0x5D]).r(), (_64b | compat) & sse2).alt(avx, "vminpd_b"),
         inst("vminss", fmt("B", [w(xmm1), r(xmm2), r(xmm_m32)]), vex(LIG)._f3()._0f().op(0x5D).r(), (_64b | compat) & avx),
         inst("vminsd", fmt("B", [w(xmm1), r(xmm2), r(xmm_m64)]), vex(LIG)._f2()._0f().op(0x5D).r(), (_64b | compat) & avx),
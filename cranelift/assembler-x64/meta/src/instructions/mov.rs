@@ -27,6 +27,11 @@ pub fn list() -> Vec<Inst> {
         inst("movl", fmt("MI", [w(rm32), r(imm32)]), rex(0xC7).digit(0).id(), _64b | compat),
         inst("movq", fmt("MI_SXL", [w(rm64), sxq(imm32)]), rex(0xC7).w().digit(0).id(), _64b),
 
+        // Load integers relative to the FS or GS segment base, e.g., to access
+        // thread-local storage.
+        inst("movq", fmt("RM_FS", [w(r64), r(m64)]), rex(0x8B).w().r().fs(), _64b),
+        inst("movq", fmt("RM_GS", [w(r64), r(m64)]), rex(0x8B).w().r().gs(), _64b),
+
         // Move integers with sign extension. These are defined as `movsx` in
         // the x64 reference manual but Capstone (and likely other tools)
         // disassemble this as `movs{from}{to}`.
@@ -93,8 +98,8 @@ pub fn list() -> Vec<Inst> {
         // Move aligned 128-bit values to and from XMM locations.
         inst("movapd", fmt("A", [w(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0x28]).r(), compat | _64b | sse2).alt(avx, "vmovapd_a"),
         inst("movapd", fmt("B", [w(align(xmm_m128)), r(xmm1)]), rex([0x66, 0x0F, 0x29]).r(), compat | _64b | sse2).alt(avx, "vmovapd_b"),
-        inst("movaps", fmt("A", [w(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x28]).r(), compat | _64b | sse).alt(avx, "vmovaps_a"),
-        inst("movaps", fmt("B", [w(align(xmm_m128)), r(xmm1)]), rex([0x0F, 0x29]).r(), compat | _64b | sse).alt(avx, "vmovaps_b"),
+        inst("movaps", fmt("A", [w(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x28]).np().r(), compat | _64b | sse).alt(avx, "vmovaps_a"),
+        inst("movaps", fmt("B", [w(align(xmm_m128)), r(xmm1)]), rex([0x0F, 0x29]).np().r(), compat | _64b | sse).alt(avx, "vmovaps_b"),
         inst("movdqa", fmt("A", [w(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0x6F]).r(), compat | _64b | sse2).alt(avx, "vmovdqa_a"),
         inst("movdqa", fmt("B", [w(align(xmm_m128)), r(xmm1)]), rex([0x66, 0x0F, 0x7F]).r(), compat | _64b | sse2).alt(avx, "vmovdqa_b"),
         inst("vmovapd", fmt("A", [w(xmm1), r(align(xmm_m128))]), vex(L128)._66()._0f().op(0x28).r(), compat | _64b | avx),
@@ -107,8 +112,8 @@ pub fn list() -> Vec<Inst> {
         // Move unaligned 128-bit values to and from XMM locations.
         inst("movupd", fmt("A", [w(xmm1), r(xmm_m128)]), rex([0x66, 0x0F, 0x10]).r(), compat | _64b | sse2).alt(avx, "vmovupd_a"),
         inst("movupd", fmt("B", [w(xmm_m128), r(xmm1)]), rex([0x66, 0x0F, 0x11]).r(), compat | _64b | sse2).alt(avx, "vmovupd_b"),
-        inst("movups", fmt("A", [w(xmm1), r(xmm_m128)]), rex([0x0F, 0x10]).r(), compat | _64b | sse).alt(avx, "vmovups_a"),
-        inst("movups", fmt("B", [w(xmm_m128), r(xmm1)]), rex([0x0F, 0x11]).r(), compat | _64b | sse).alt(avx, "vmovups_b"),
+        inst("movups", fmt("A", [w(xmm1), r(xmm_m128)]), rex([0x0F, 0x10]).np().r(), compat | _64b | sse).alt(avx, "vmovups_a"),
+        inst("movups", fmt("B", [w(xmm_m128), r(xmm1)]), rex([0x0F, 0x11]).np().r(), compat | _64b | sse).alt(avx, "vmovups_b"),
         inst("movdqu", fmt("A", [w(xmm1), r(xmm_m128)]), rex([0xF3, 0x0F, 0x6F]).r(), compat | _64b | sse2).alt(avx, "vmovdqu_a"),
         inst("movdqu", fmt("B", [w(xmm_m128), r(xmm1)]), rex([0xF3, 0x0F, 0x7F]).r(), compat | _64b | sse2).alt(avx, "vmovdqu_b"),
         inst("vmovupd", fmt("A", [w(xmm1), r(xmm_m128)]), vex(L128)._66()._0f().op(0x10).r(), compat | _64b | avx),
@@ -7,66 +7,66 @@ pub fn list() -> Vec<Inst> {
         // Scalar arithmetic right shifts.
         inst("sarb", fmt("MC", [rw(rm8), r(cl)]), rex([0xD2]).digit(7), _64b | compat),
         inst("sarb", fmt("MI", [rw(rm8), r(imm8)]), rex([0xC0]).digit(7).ib(), _64b | compat),
-        inst("sarb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(7).ib(), _64b | compat).custom(Display),
+        inst("sarb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(7).implicit_imm(1), _64b | compat).custom(Display),
         inst("sarw", fmt("MC", [rw(rm16), r(cl)]), rex([0x66, 0xD3]).digit(7), _64b | compat),
         inst("sarw", fmt("MI", [rw(rm16), r(imm8)]), rex([0x66, 0xC1]).digit(7).ib(), _64b | compat),
-        inst("sarw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(7).ib(), _64b | compat).custom(Display),
+        inst("sarw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(7).implicit_imm(1), _64b | compat).custom(Display),
         inst("sarl", fmt("MC", [rw(rm32), r(cl)]), rex([0xD3]).digit(7), _64b | compat),
         inst("sarl", fmt("MI", [rw(rm32), r(imm8)]), rex([0xC1]).digit(7).ib(), _64b | compat),
-        inst("sarl", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(7).ib(), _64b | compat).custom(Display),
+        inst("sarl", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(7).implicit_imm(1), _64b | compat).custom(Display),
         inst("sarq", fmt("MC", [rw(rm64), r(cl)]), rex([0xD3]).digit(7).w(), _64b),
         inst("sarq", fmt("MI", [rw(rm64), r(imm8)]), rex([0xC1]).digit(7).ib().w(), _64b),
-        inst("sarq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(7).ib().w(), _64b).custom(Display),
+        inst("sarq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(7).implicit_imm(1).w(), _64b).custom(Display),
         // Scalar logical left shifts.
         inst("shlb", fmt("MC", [rw(rm8), r(cl)]), rex([0xD2]).digit(4), _64b | compat),
         inst("shlb", fmt("MI", [rw(rm8), r(imm8)]), rex([0xC0]).digit(4).ib(), _64b | compat),
-        inst("shlb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(4).ib(), _64b | compat).custom(Display),
+        inst("shlb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(4).implicit_imm(1), _64b | compat).custom(Display),
         inst("shlw", fmt("MC", [rw(rm16), r(cl)]), rex([0x66, 0xD3]).digit(4), _64b | compat),
         inst("shlw", fmt("MI", [rw(rm16), r(imm8)]), rex([0x66, 0xC1]).digit(4).ib(), _64b | compat),
-        inst("shlw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(4).ib(), _64b | compat).custom(Display),
+        inst("shlw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(4).implicit_imm(1), _64b | compat).custom(Display),
         inst("shll", fmt("MC", [rw(rm32), r(cl)]), rex([0xD3]).digit(4), _64b | compat),
         inst("shll", fmt("MI", [rw(rm32), r(imm8)]), rex([0xC1]).digit(4).ib(), _64b | compat),
-        inst("shll", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(4).ib(), _64b | compat).custom(Display),
+        inst("shll", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(4).implicit_imm(1), _64b | compat).custom(Display),
         inst("shlq", fmt("MC", [rw(rm64), r(cl)]), rex([0xD3]).digit(4).w(), _64b),
         inst("shlq", fmt("MI", [rw(rm64), r(imm8)]), rex([0xC1]).digit(4).ib().w(), _64b),
-        inst("shlq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(4).ib().w(), _64b).custom(Display),
+        inst("shlq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(4).implicit_imm(1).w(), _64b).custom(Display),
         // Scalar logical right shifts.
         inst("shrb", fmt("MC", [rw(rm8), r(cl)]), rex([0xD2]).digit(5), _64b | compat),
         inst("shrb", fmt("MI", [rw(rm8), r(imm8)]), rex([0xC0]).digit(5).ib(), _64b | compat),
-        inst("shrb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(5).ib(), _64b | compat).custom(Display),
+        inst("shrb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(5).implicit_imm(1), _64b | compat).custom(Display),
         inst("shrw", fmt("MC", [rw(rm16), r(cl)]), rex([0x66, 0xD3]).digit(5), _64b | compat),
         inst("shrw", fmt("MI", [rw(rm16), r(imm8)]), rex([0x66, 0xC1]).digit(5).ib(), _64b | compat),
-        inst("shrw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(5).ib(), _64b | compat).custom(Display),
+        inst("shrw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(5).implicit_imm(1), _64b | compat).custom(Display),
         inst("shrl", fmt("MC", [rw(rm32), r(cl)]), rex([0xD3]).digit(5), _64b | compat),
         inst("shrl", fmt("MI", [rw(rm32), r(imm8)]), rex([0xC1]).digit(5).ib(), _64b | compat),
-        inst("shrl", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(5).ib(), _64b | compat).custom(Display),
+        inst("shrl", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(5).implicit_imm(1), _64b | compat).custom(Display),
         inst("shrq", fmt("MC", [rw(rm64), r(cl)]), rex([0xD3]).digit(5).w(), _64b),
         inst("shrq", fmt("MI", [rw(rm64), r(imm8)]), rex([0xC1]).digit(5).ib().w(), _64b),
-        inst("shrq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(5).ib().w(), _64b).custom(Display),
+        inst("shrq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(5).implicit_imm(1).w(), _64b).custom(Display),
         inst("rolb", fmt("MC", [rw(rm8), r(cl)]), rex([0xD2]).digit(0), _64b | compat),
         inst("rolb", fmt("MI", [rw(rm8), r(imm8)]), rex([0xC0]).digit(0).ib(), _64b | compat),
-        inst("rolb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(0).ib(), _64b | compat).custom(Display),
+        inst("rolb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(0).implicit_imm(1), _64b | compat).custom(Display),
         inst("rolw", fmt("MC", [rw(rm16), r(cl)]), rex([0x66, 0xD3]).digit(0), _64b | compat),
         inst("rolw", fmt("MI", [rw(rm16), r(imm8)]), rex([0x66, 0xC1]).digit(0).ib(), _64b | compat),
-        inst("rolw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(0).ib(), _64b | compat).custom(Display),
+        inst("rolw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(0).implicit_imm(1), _64b | compat).custom(Display),
         inst("roll", fmt("MC", [rw(rm32), r(cl)]), rex([0xD3]).digit(0), _64b | compat),
         inst("roll", fmt("MI", [rw(rm32), r(imm8)]), rex([0xC1]).digit(0).ib(), _64b | compat),
-        inst("roll", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(0).ib(), _64b | compat).custom(Display),
+        inst("roll", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(0).implicit_imm(1), _64b | compat).custom(Display),
         inst("rolq", fmt("MC", [rw(rm64), r(cl)]), rex([0xD3]).digit(0).w(), _64b),
         inst("rolq", fmt("MI", [rw(rm64), r(imm8)]), rex([0xC1]).digit(0).ib().w(), _64b),
-        inst("rolq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(0).ib().w(), _64b).custom(Display),
+        inst("rolq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(0).implicit_imm(1).w(), _64b).custom(Display),
         inst("rorb", fmt("MC", [rw(rm8), r(cl)]), rex([0xD2]).digit(1), _64b | compat),
         inst("rorb", fmt("MI", [rw(rm8), r(imm8)]), rex([0xC0]).digit(1).ib(), _64b | compat),
-        inst("rorb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(1).ib(), _64b | compat).custom(Display),
+        inst("rorb", fmt("M1", [rw(rm8)]), rex([0xD0]).digit(1).implicit_imm(1), _64b | compat).custom(Display),
         inst("rorw", fmt("MC", [rw(rm16), r(cl)]), rex([0x66, 0xD3]).digit(1), _64b | compat),
         inst("rorw", fmt("MI", [rw(rm16), r(imm8)]), rex([0x66, 0xC1]).digit(1).ib(), _64b | compat),
-        inst("rorw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(1).ib(), _64b | compat).custom(Display),
+        inst("rorw", fmt("M1", [rw(rm16)]), rex([0x66, 0xD1]).digit(1).implicit_imm(1), _64b | compat).custom(Display),
         inst("rorl", fmt("MC", [rw(rm32), r(cl)]), rex([0xD3]).digit(1), _64b | compat),
         inst("rorl", fmt("MI", [rw(rm32), r(imm8)]), rex([0xC1]).digit(1).ib(), _64b | compat),
-        inst("rorl", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(1).ib(), _64b | compat).custom(Display),
+        inst("rorl", fmt("M1", [rw(rm32)]), rex([0xD1]).digit(1).implicit_imm(1), _64b | compat).custom(Display),
         inst("rorq", fmt("MC", [rw(rm64), r(cl)]), rex([0xD3]).digit(1).w(), _64b),
         inst("rorq", fmt("MI", [rw(rm64), r(imm8)]), rex([0xC1]).digit(1).ib().w(), _64b),
-        inst("rorq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(1).ib().w(), _64b).custom(Display),
+        inst("rorq", fmt("M1", [rw(rm64)]), rex([0xD1]).digit(1).implicit_imm(1).w(), _64b).custom(Display),
 
         inst("shldw", fmt("MRI", [rw(rm16), r(r16), r(imm8)]), rex([0x66, 0x0F, 0xA4]).ib(), _64b | compat),
         inst("shldw", fmt("MRC", [rw(rm16), r(r16), r(cl)]), rex([0x66, 0x0F, 0xA5]).ib(), _64b | compat),
@@ -0,0 +1,12 @@
+use crate::dsl::{Customization::*, Feature::*, Inst, Location::*};
+use crate::dsl::{fmt, implicit, inst, r, rex, rw};
+
+#[rustfmt::skip] // Keeps instructions on a single line.
+pub fn list() -> Vec<Inst> {
+    vec![
+        inst("movsb", fmt("ZO", [rw(implicit(rdi)), rw(implicit(rsi)), rw(implicit(rcx))]), rex(0xA4).rep(), _64b | compat).custom(Display),
+        inst("movsq", fmt("ZO", [rw(implicit(rdi)), rw(implicit(rsi)), rw(implicit(rcx))]), rex(0xA5).w().rep(), _64b).custom(Display),
+        inst("stosb", fmt("ZO", [rw(implicit(rdi)), rw(implicit(rcx)), r(implicit(al))]), rex(0xAA).rep(), _64b | compat).custom(Display),
+        inst("stosq", fmt("ZO", [rw(implicit(rdi)), rw(implicit(rcx)), r(implicit(rax))]), rex(0xAB).w().rep(), _64b).custom(Display),
+    ]
+}
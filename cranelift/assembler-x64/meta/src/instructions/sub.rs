@@ -65,7 +65,7 @@ pub fn list() -> Vec<Inst> {
         // Vector instructions.
         inst("subss", fmt("A", [rw(xmm1), r(xmm_m32)]), rex([0xF3, 0x0F, 0x5C]).r(), (_64b | compat) & sse).alt(avx, "vsubss_b"),
         inst("subsd", fmt("A", [rw(xmm1), r(xmm_m64)]), rex([0xF2, 0x0F, 0x5C]).r(), (_64b | compat) & sse2).alt(avx, "vsubsd_b"),
-        inst("subps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x5C]).r(), (_64b | compat) & sse).alt(avx, "vsubps_b"),
+        inst("subps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x5C]).np().r(), (_64b | compat) & sse).alt(avx, "vsubps_b"),
         inst("subpd", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0x5C]).r(), (_64b | compat) & sse2).alt(avx, "vsubpd_b"),
         inst("psubb", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0xF8]).r(), (_64b | compat) & sse2).alt(avx, "vpsubb_b"),
         inst("psubw", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0xF9]).r(), (_64b | compat) & sse2).alt(avx, "vpsubw_b"),
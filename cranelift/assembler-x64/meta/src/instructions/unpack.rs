@@ -5,9 +5,9 @@ use crate::dsl::{align, fmt, inst, r, rex, rw, vex, w};
 pub fn list() -> Vec<Inst> {
     vec![
         // Unpack floating-point.
-        inst("unpcklps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x14]).r(), (_64b | compat) & sse).alt(avx, "vunpcklps_b"),
+        inst("unpcklps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x14]).np().r(), (_64b | compat) & sse).alt(avx, "vunpcklps_b"),
         inst("unpcklpd", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x66, 0x0F, 0x14]).r(), (_64b | compat) & sse2).alt(avx, "vunpcklpd_b"),
-        inst("unpckhps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x15]).r(), (_64b | compat) & sse).alt(avx, "vunpckhps_b"),
+        inst("unpckhps", fmt("A", [rw(xmm1), r(align(xmm_m128))]), rex([0x0F, 0x15]).np().r(), (_64b | compat) & sse).alt(avx, "vunpckhps_b"),
         inst("vunpcklps", fmt("B", [w(xmm1), r(xmm2), r(xmm_m128)]), vex(L128)._0f().op(0x14).r(), (_64b | compat) & avx),
         inst("vunpcklpd", fmt("B", [w(xmm1), r(xmm2), r(xmm_m128)]), vex(L128)._66()._0f().op(0x14).r(), (_64b | compat) & avx),
         inst("vunpckhps", fmt("B", [w(xmm1), r(xmm2), r(xmm_m128)]), vex(L128)._0f().op(0x15).r(), (_64b | compat) & avx),
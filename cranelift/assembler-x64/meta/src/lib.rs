@@ -22,3 +22,36 @@ pub fn generate_rust_assembler<P: AsRef<Path>>(dir: P, file: &str) -> PathBuf {
     fmt.write(file, dir.as_ref()).unwrap();
     out
 }
+
+/// Describe each of the DSL-defined `insts` as JSON: mnemonic, operands, and
+/// encoding parameters (e.g., prefixes, opcode bytes, ModR/M kind). This is
+/// meant for external tools (assemblers, analyzers) that want to reuse the
+/// instruction definitions.
+///
+/// ```
+/// # use cranelift_assembler_x64_meta::{instructions, json_metadata};
+/// let insts = instructions::list();
+/// let vaddps = insts.iter().find(|i| i.name() == "vaddps_b").unwrap();
+/// let json = json_metadata(std::slice::from_ref(vaddps));
+/// assert!(json.contains(r#""mnemonic": "vaddps""#));
+/// assert!(json.contains(r#""opcode": "0x58""#));
+/// assert!(json.contains(r#"{"location": "xmm1", "kind": "reg", "bits": 128, "mutability": "w", "implicit": false, "symbolic": false}"#));
+/// assert!(json.contains(r#"{"location": "xmm_m128", "kind": "reg_mem", "bits": 128, "mutability": "r", "implicit": false, "symbolic": false}"#));
+/// ```
+#[must_use]
+pub fn json_metadata(insts: &[dsl::Inst]) -> String {
+    generate::json(insts)
+}
+
+/// Generate the JSON `file` describing each DSL-defined instruction; see
+/// [`json_metadata`].
+///
+/// # Panics
+///
+/// This function panics if we cannot write the file.
+pub fn generate_json_metadata<P: AsRef<Path>>(dir: P, file: &str) -> PathBuf {
+    let out = dir.as_ref().join(file);
+    eprintln!("Generating {}", out.display());
+    std::fs::write(&out, json_metadata(&instructions::list())).unwrap();
+    out
+}
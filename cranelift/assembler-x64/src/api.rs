@@ -20,6 +20,13 @@ pub trait CodeSink {
     /// Add 8 bytes to the code section.
     fn put8(&mut self, _: u64);
 
+    /// Add `data` to the code section.
+    fn put_data(&mut self, data: &[u8]) {
+        for byte in data {
+            self.put1(*byte);
+        }
+    }
+
     /// Inform the code buffer of a possible trap at the current location;
     /// required for assembling memory accesses.
     fn add_trap(&mut self, code: TrapCode);
@@ -33,6 +40,28 @@ pub trait CodeSink {
 
     /// Resolves a `KnownOffset` value to the actual signed offset.
     fn known_offset(&self, offset: KnownOffset) -> i32;
+
+    /// Inform the code buffer that the 4 bytes about to be placed at the
+    /// current offset refer to `symbol` and must be patched by a relocation of
+    /// the given `kind` and `addend`.
+    ///
+    /// This is only called when encoding an instruction with a symbolic
+    /// operand; the default implementation panics, for sinks which are never
+    /// handed one.
+    fn add_reloc(&mut self, kind: RelocKind, symbol: Symbol, addend: i32) {
+        let _ = addend;
+        panic!("unsupported {kind:?} relocation against {symbol:?}")
+    }
+
+    /// Mark the start of an encoding `component` (e.g., `REX`, `opcode`,
+    /// `modrm`); the bytes put until the next mark belong to it. `meaning`
+    /// describes the component in terms of the instruction's operands.
+    ///
+    /// Generated encoders only call this when the `trace` feature is enabled;
+    /// by default, it is ignored. See [`TraceSink`].
+    fn trace(&mut self, component: &'static str, meaning: &dyn Fn() -> String) {
+        let _ = (component, meaning);
+    }
 }
 
 /// Provide a convenient implementation for testing.
@@ -53,6 +82,10 @@ impl CodeSink for Vec<u8> {
         self.extend_from_slice(&v.to_le_bytes());
     }
 
+    fn put_data(&mut self, data: &[u8]) {
+        self.extend_from_slice(data);
+    }
+
     fn add_trap(&mut self, _: TrapCode) {}
 
     fn use_target(&mut self, _: DeferredTarget) {}
@@ -60,8 +93,253 @@ impl CodeSink for Vec<u8> {
     fn known_offset(&self, offset: KnownOffset) -> i32 {
         panic!("unknown offset {offset:?}")
     }
+
+    fn add_reloc(&mut self, _: RelocKind, _: Symbol, _: i32) {}
+}
+
+/// A [`CodeSink`] writing into a fixed-size slice, e.g., to patch code in
+/// place; see `encode_into`.
+///
+/// Bytes which do not fit are dropped but still counted, so that
+/// [`SliceSink::finish`] can report how many bytes were needed. A slice has
+/// nowhere to record label uses or relocations, so instructions needing them
+/// fail to encode.
+pub struct SliceSink<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    unresolved: Option<EncodeError>,
+}
+
+impl<'a> SliceSink<'a> {
+    /// Create a sink writing from the start of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            buf,
+            len: 0,
+            unresolved: None,
+        }
+    }
+
+    /// Return the number of bytes written or, if the instruction refers to a
+    /// target or symbol, an [`EncodeError::UnresolvedTarget`] or
+    /// [`EncodeError::UnresolvedRelocation`] or, if the bytes did not all fit,
+    /// an [`EncodeError::Overflow`].
+    pub fn finish(self) -> Result<usize, EncodeError> {
+        if let Some(err) = self.unresolved {
+            Err(err)
+        } else if self.len <= self.buf.len() {
+            Ok(self.len)
+        } else {
+            Err(EncodeError::Overflow {
+                needed: self.len,
+                available: self.buf.len(),
+            })
+        }
+    }
+
+    fn put(&mut self, bytes: &[u8]) {
+        let start = self.len;
+        self.len += bytes.len();
+        if let Some(dst) = self.buf.get_mut(start..self.len) {
+            dst.copy_from_slice(bytes);
+        }
+    }
+}
+
+impl CodeSink for SliceSink<'_> {
+    fn put1(&mut self, v: u8) {
+        self.put(&[v]);
+    }
+
+    fn put2(&mut self, v: u16) {
+        self.put(&v.to_le_bytes());
+    }
+
+    fn put4(&mut self, v: u32) {
+        self.put(&v.to_le_bytes());
+    }
+
+    fn put8(&mut self, v: u64) {
+        self.put(&v.to_le_bytes());
+    }
+
+    fn put_data(&mut self, data: &[u8]) {
+        self.put(data);
+    }
+
+    fn add_trap(&mut self, _: TrapCode) {}
+
+    fn use_target(&mut self, target: DeferredTarget) {
+        if !matches!(target, DeferredTarget::None) {
+            let offset = self.len;
+            self.unresolved
+                .get_or_insert(EncodeError::UnresolvedTarget { offset });
+        }
+    }
+
+    fn known_offset(&self, offset: KnownOffset) -> i32 {
+        panic!("unknown offset {offset:?}")
+    }
+
+    fn add_reloc(&mut self, _: RelocKind, _: Symbol, _: i32) {
+        let offset = self.len;
+        self.unresolved
+            .get_or_insert(EncodeError::UnresolvedRelocation { offset });
+    }
+}
+
+/// A [`CodeSink`] recording which encoding component each byte belongs to,
+/// for debugging encodings; see [`CodeSink::trace`].
+///
+/// Its `Display` implementation lists each component with its bytes and
+/// meaning; e.g., `addq %rax, %rcx` prints as
+/// `REX=0x48 opcode=0x01 modrm=0xc1 (reg=%rax rm=%rcx)`. Immediates print as
+/// their value instead, e.g., `imm32=0x10`. Components are only marked when
+/// the `trace` feature is enabled.
+#[derive(Default)]
+pub struct TraceSink {
+    /// The encoded bytes.
+    pub buf: Vec<u8>,
+    /// The marked components, in encoding order.
+    pub components: Vec<TracedComponent>,
+}
+
+/// A component of an encoding, recorded by [`TraceSink`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TracedComponent {
+    /// The component name, e.g., `modrm`.
+    pub name: &'static str,
+    /// The offset of the component's first byte.
+    pub start: usize,
+    /// The component's meaning, e.g., `reg=%rax rm=%rcx`; possibly empty.
+    pub meaning: String,
+}
+
+impl TraceSink {
+    /// Return the bytes of each component; components which emitted no
+    /// bytes (e.g., an unneeded REX prefix) are skipped.
+    pub fn component_bytes(&self) -> impl Iterator<Item = (&TracedComponent, &[u8])> {
+        let ends = self
+            .components
+            .iter()
+            .skip(1)
+            .map(|c| c.start)
+            .chain(Some(self.buf.len()));
+        self.components
+            .iter()
+            .zip(ends)
+            .map(|(c, end)| (c, &self.buf[c.start..end]))
+            .filter(|(_, bytes)| !bytes.is_empty())
+    }
 }
 
+impl CodeSink for TraceSink {
+    fn put1(&mut self, v: u8) {
+        self.buf.put1(v);
+    }
+
+    fn put2(&mut self, v: u16) {
+        self.buf.put2(v);
+    }
+
+    fn put4(&mut self, v: u32) {
+        self.buf.put4(v);
+    }
+
+    fn put8(&mut self, v: u64) {
+        self.buf.put8(v);
+    }
+
+    fn put_data(&mut self, data: &[u8]) {
+        self.buf.put_data(data);
+    }
+
+    fn add_trap(&mut self, _: TrapCode) {}
+
+    fn use_target(&mut self, _: DeferredTarget) {}
+
+    fn known_offset(&self, offset: KnownOffset) -> i32 {
+        panic!("unknown offset {offset:?}")
+    }
+
+    fn add_reloc(&mut self, _: RelocKind, _: Symbol, _: i32) {}
+
+    fn trace(&mut self, component: &'static str, meaning: &dyn Fn() -> String) {
+        self.components.push(TracedComponent {
+            name: component,
+            start: self.buf.len(),
+            meaning: meaning(),
+        });
+    }
+}
+
+impl fmt::Display for TraceSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (component, bytes)) in self.component_bytes().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            if component.name.starts_with("imm") {
+                // Immediates are encoded little-endian.
+                let value = bytes.iter().rev().fold(0, |v, &b| v << 8 | u64::from(b));
+                write!(f, "{}={value:#x}", component.name)?;
+                continue;
+            }
+            write!(f, "{}=0x", component.name)?;
+            for byte in bytes {
+                write!(f, "{byte:02x}")?;
+            }
+            if !component.meaning.is_empty() {
+                write!(f, " ({})", component.meaning)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error encoding an instruction into a fixed-size buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The instruction needs `needed` bytes but only `available` fit.
+    Overflow {
+        /// The encoded length of the instruction.
+        needed: usize,
+        /// The length of the buffer.
+        available: usize,
+    },
+    /// The instruction refers to a label or constant, whose offset a buffer
+    /// cannot resolve.
+    UnresolvedTarget {
+        /// The offset of the bytes referring to the target.
+        offset: usize,
+    },
+    /// The instruction refers to a symbol, which needs a relocation a buffer
+    /// cannot record.
+    UnresolvedRelocation {
+        /// The offset of the bytes to relocate.
+        offset: usize,
+    },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow { needed, available } => write!(
+                f,
+                "instruction needs {needed} bytes but the buffer holds {available}"
+            ),
+            Self::UnresolvedTarget { offset } => {
+                write!(f, "unresolved target at offset {offset}")
+            }
+            Self::UnresolvedRelocation { offset } => {
+                write!(f, "unresolved relocation at offset {offset}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
 /// Wrap [`CodeSink`]-specific labels.
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
@@ -72,6 +350,23 @@ pub struct Label(pub u32);
 #[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
 pub struct Constant(pub u32);
 
+/// Wrap [`CodeSink`]-specific symbols, e.g., external functions or data.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
+pub struct Symbol(pub u32);
+
+/// The kinds of relocation reported by [`CodeSink::add_reloc`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RelocKind {
+    /// A 32-bit offset from the end of the field to the symbol, as for a
+    /// RIP-relative memory operand.
+    PcRel4,
+    /// A 32-bit offset from the end of the field to the symbol, as for the
+    /// displacement of a `call` or `jmp`; linkers may redirect this through a
+    /// PLT entry.
+    CallPcRel4,
+}
+
 /// Wrap [`CodeSink`]-specific trap codes.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(any(test, feature = "fuzz"), derive(arbitrary::Arbitrary))]
@@ -241,3 +536,137 @@ pub trait RegisterVisitor<R: Registers> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Inst;
+    use crate::fuzz::{FuzzReg, FuzzRegs, TestCodeSink, assemble};
+
+    #[test]
+    fn encode_into() {
+        use crate::EncodeError;
+        use crate::gpr::enc::RAX;
+        use crate::{Fixed, Imm32};
+
+        // `addl $0x1000, %eax` takes five bytes.
+        let inst: Inst<FuzzRegs> =
+            crate::inst::addl_i::new(Fixed(FuzzReg::new(RAX)), Imm32::new(0x1000)).into();
+        let expected = assemble(&inst);
+        assert_eq!(expected.len(), 5);
+
+        let mut buf = [0; 8];
+        assert_eq!(inst.encode_into(&mut buf), Ok(5));
+        assert_eq!(&buf[..5], expected.as_slice());
+        assert_eq!(buf[5..], [0; 3]);
+
+        let mut buf = [0; 4];
+        assert_eq!(
+            inst.encode_into(&mut buf),
+            Err(EncodeError::Overflow {
+                needed: 5,
+                available: 4
+            })
+        );
+    }
+
+    #[test]
+    fn encode_into_unresolved() {
+        use crate::EncodeError;
+        use crate::gpr::enc::RAX;
+        use crate::{Amode, Gpr, GprMem};
+
+        // `movq <target>(%rip), %rax`: the displacement follows REX, opcode,
+        // and ModR/M bytes.
+        let movq = |target| -> Inst<FuzzRegs> {
+            let mem = GprMem::Mem(Amode::RipRelative { target });
+            crate::inst::movq_rm::new(Gpr::new(FuzzReg::new(RAX)), mem).into()
+        };
+        let mut buf = [0; 16];
+        assert_eq!(
+            movq(DeferredTarget::Label(Label(0))).encode_into(&mut buf),
+            Err(EncodeError::UnresolvedTarget { offset: 3 })
+        );
+        assert_eq!(
+            movq(DeferredTarget::Constant(Constant(0))).encode_into(&mut buf),
+            Err(EncodeError::UnresolvedTarget { offset: 3 })
+        );
+        assert_eq!(
+            movq(DeferredTarget::Symbol(Symbol(7))).encode_into(&mut buf),
+            Err(EncodeError::UnresolvedRelocation { offset: 3 })
+        );
+
+        // `callq sym`: the displacement immediate follows the opcode.
+        let mut call = crate::inst::callq_d::new(0);
+        assert_eq!(Inst::<FuzzRegs>::from(call).encode_into(&mut buf), Ok(5));
+        call.symbol = Some(Symbol(7));
+        assert_eq!(
+            Inst::<FuzzRegs>::from(call).encode_into(&mut buf),
+            Err(EncodeError::UnresolvedRelocation { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn symbolic_relocations() {
+        use crate::gpr::enc::RAX;
+        use crate::{Amode, Gpr, GprMem, Imm32};
+
+        let encode = |inst: Inst<FuzzRegs>| {
+            let mut sink = TestCodeSink::default();
+            inst.encode(&mut sink);
+            (sink.buf, sink.relocs)
+        };
+        let symbol = Symbol(7);
+        let riprel = || Amode::RipRelative {
+            target: DeferredTarget::Symbol(symbol),
+        };
+
+        // `movq sym(%rip), %rax`: the displacement follows REX, opcode, and
+        // ModR/M bytes.
+        let inst = crate::inst::movq_rm::new(Gpr::new(FuzzReg::new(RAX)), GprMem::Mem(riprel()));
+        let (buf, relocs) = encode(inst.into());
+        assert_eq!(buf, [0x48, 0x8b, 0x05, 0, 0, 0, 0]);
+        assert_eq!(relocs, [(3, RelocKind::PcRel4, symbol, -4)]);
+
+        // `cmpl $1, sym(%rip)`: the immediate follows the displacement.
+        let inst = crate::inst::cmpl_mi::new(GprMem::Mem(riprel()), Imm32::new(1));
+        let (buf, relocs) = encode(inst.into());
+        assert_eq!(buf, [0x81, 0x3d, 0, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(relocs, [(2, RelocKind::PcRel4, symbol, -8)]);
+
+        // `callq sym`: the displacement immediate follows the opcode.
+        let mut inst = crate::inst::callq_d::new(0);
+        assert!(encode(inst.into()).1.is_empty());
+        inst.symbol = Some(symbol);
+        let (buf, relocs) = encode(inst.into());
+        assert_eq!(buf, [0xe8, 0, 0, 0, 0]);
+        assert_eq!(relocs, [(1, RelocKind::CallPcRel4, symbol, -4)]);
+    }
+
+    #[test]
+    #[cfg(feature = "trace")]
+    fn trace() {
+        use crate::gpr::enc::{R9, RAX, RCX};
+        use crate::{Gpr, GprMem, Imm32, TraceSink};
+
+        let trace = |inst: Inst<FuzzRegs>| {
+            let mut sink = TraceSink::default();
+            inst.encode(&mut sink);
+            assert_eq!(sink.buf, assemble(&inst));
+            sink.to_string()
+        };
+        let gpr = |enc| Gpr::new(FuzzReg::new(enc));
+
+        let add = crate::inst::addq_mr::new(GprMem::Gpr(FuzzReg::new(RCX)), gpr(RAX));
+        assert_eq!(
+            trace(add.into()),
+            "REX=0x48 opcode=0x01 modrm=0xc1 (reg=%rax rm=%rcx)"
+        );
+
+        let mov = crate::inst::movl_oi::new(gpr(R9), Imm32::new(0x10));
+        assert_eq!(
+            trace(mov.into()),
+            "REX=0x41 opcode=0xb9 (reg=%r9d) imm32=0x10"
+        );
+    }
+}
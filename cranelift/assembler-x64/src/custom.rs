@@ -194,7 +194,7 @@ pub mod display {
     use std::fmt;
 
     pub fn callq_d(f: &mut fmt::Formatter, inst: &inst::callq_d) -> fmt::Result {
-        let inst::callq_d { imm32 } = inst;
+        let inst::callq_d { imm32, .. } = inst;
         display_displacement(f, "callq", i64::from(imm32.value()) + 5)
     }
 
@@ -462,6 +462,47 @@ pub mod display {
         write!(f, "xchg{suffix} {reg}, {mem}")
     }
 
+    pub fn xchgl_rr<R: Registers>(
+        f: &mut fmt::Formatter<'_>,
+        inst: &inst::xchgl_rr<R>,
+    ) -> fmt::Result {
+        let inst::xchgl_rr { r32a, r32b } = inst;
+        xchg_rr::<R>(f, r32a, r32b, Size::Doubleword)
+    }
+
+    pub fn xchgq_rr<R: Registers>(
+        f: &mut fmt::Formatter<'_>,
+        inst: &inst::xchgq_rr<R>,
+    ) -> fmt::Result {
+        let inst::xchgq_rr { r64a, r64b } = inst;
+        xchg_rr::<R>(f, r64a, r64b, Size::Quadword)
+    }
+
+    /// Print the operands in the order Capstone does, which depends on the
+    /// encoding: the accumulator first for the compact `0x90 +r` form,
+    /// otherwise the ModR/M `reg` operand first.
+    fn xchg_rr<R: Registers>(
+        f: &mut fmt::Formatter<'_>,
+        a: &Gpr<R::ReadWriteGpr>,
+        b: &Gpr<R::ReadWriteGpr>,
+        size: Size,
+    ) -> fmt::Result {
+        let (first, second) = if b.enc() == 0 && a.enc() != 0 {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let first = first.to_string(size);
+        let second = second.to_string(size);
+        let suffix = match size {
+            Size::Byte => "b",
+            Size::Word => "w",
+            Size::Doubleword => "l",
+            Size::Quadword => "q",
+        };
+        write!(f, "xchg{suffix} {first}, {second}")
+    }
+
     pub fn sarb_m1<R: Registers>(f: &mut fmt::Formatter, inst: &inst::sarb_m1<R>) -> fmt::Result {
         let inst::sarb_m1 { rm8 } = inst;
         shift_m1::<R>(f, "sarb", rm8, Size::Byte)
@@ -587,7 +628,7 @@ pub mod display {
     }
 
     pub fn jmp_d32(f: &mut fmt::Formatter<'_>, jmp: &inst::jmp_d32) -> fmt::Result {
-        let inst::jmp_d32 { imm32 } = jmp;
+        let inst::jmp_d32 { imm32, .. } = jmp;
         display_displacement(f, "jmp", i64::from(imm32.value()) + 5)
     }
 
@@ -624,6 +665,26 @@ pub mod display {
         "js" = js_d8 / js_d32;
     }
 
+    /// The string instructions only use implicit operands but, like other
+    /// disassemblers, print the memory they access through `rsi` and `rdi`.
+    macro_rules! string {
+        ($($name:ident = $display:literal;)*) => ($(
+            pub fn $name<R: Registers>(
+                f: &mut fmt::Formatter<'_>,
+                _: &inst::$name<R>,
+            ) -> fmt::Result {
+                f.write_str($display)
+            }
+        )*)
+    }
+
+    string! {
+        movsb_zo = "rep movsb (%rsi), (%rdi)";
+        movsq_zo = "rep movsq (%rsi), (%rdi)";
+        stosb_zo = "rep stosb %al, (%rdi)";
+        stosq_zo = "rep stosq %rax, (%rdi)";
+    }
+
     fn display_displacement(
         f: &mut fmt::Formatter<'_>,
         mnemonic: &str,
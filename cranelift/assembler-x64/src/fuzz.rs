@@ -1,4 +1,5 @@
-//! A fuzz testing oracle for roundtrip assembly-disassembly.
+//! Fuzz testing oracles for roundtrip assembly-disassembly and for
+//! differential comparison against a reference assembler.
 //!
 //! This contains manual implementations of the `Arbitrary` trait for types
 //! throughout this crate to avoid depending on the `arbitrary` crate
@@ -6,7 +7,7 @@
 
 use crate::{
     AmodeOffset, AmodeOffsetPlusKnownOffset, AsReg, CodeSink, DeferredTarget, Fixed, Gpr, Inst,
-    KnownOffset, NonRspGpr, Registers, TrapCode, Xmm,
+    KnownOffset, NonRspGpr, Registers, RelocKind, Symbol, TrapCode, Xmm,
 };
 use arbitrary::{Arbitrary, Result, Unstructured};
 use capstone::{Capstone, arch::BuildsCapstone, arch::BuildsCapstoneSyntax, arch::x86};
@@ -38,11 +39,149 @@ pub fn roundtrip(inst: &Inst<FuzzRegs>) {
     }
 }
 
+/// Take a random assembly instruction and check its encoding against the
+/// `iced-x86` reference assembler.
+///
+/// `iced-x86` decodes our machine code to recover the instruction it
+/// represents, which must print the same as our own instruction: the same
+/// mnemonic and operands. It then re-encodes that same instruction. Multiple
+/// byte sequences may encode the same instruction (e.g., legacy prefixes can
+/// be reordered) so, when the bytes differ, both byte sequences are decoded
+/// again and must describe the same instruction.
+///
+/// # Panics
+///
+/// This function panics to express failure as expected by the `arbitrary`
+/// fuzzer infrastructure. It may fail during assembly, if `iced-x86` cannot
+/// decode or re-encode our machine code, or when comparing the decoded
+/// instructions.
+pub fn differential(inst: &Inst<FuzzRegs>) {
+    let assembled = assemble(inst);
+    let ours = iced_decode(&assembled, inst);
+
+    // Check that the decoded instruction is the one we meant to encode.
+    let expected = iced_format(&ours);
+    let mut actual = remove_after_semicolon(&inst.to_string()).to_owned();
+    if is_shift_by_one(&ours) {
+        // We print the implicit count of shifts by one inconsistently; drop it.
+        actual = actual.replacen("$1, ", "", 1);
+    }
+    if expected != actual {
+        println!("> {inst}");
+        println!("  debug: {inst:x?}");
+        println!("  assembled: {}", pretty_print_hexadecimal(&assembled));
+        println!("  expected (iced): {expected}");
+        println!("  actual (to_string): {actual}");
+        assert_eq!(expected, actual);
+    }
+
+    let mut encoder = iced_x86::Encoder::new(64);
+    if let Err(e) = encoder.encode(&ours, 0) {
+        println!("> {inst}");
+        println!("  debug: {inst:x?}");
+        println!("  assembled: {}", pretty_print_hexadecimal(&assembled));
+        panic!("iced-x86 failed to encode: {e}");
+    }
+    let reference = encoder.take_buffer();
+
+    if assembled != reference {
+        let theirs = iced_decode(&reference, inst);
+        if ours != theirs {
+            println!("> {inst}");
+            println!("  debug: {inst:x?}");
+            println!("  assembled: {}", pretty_print_hexadecimal(&assembled));
+            println!("  iced-assembled: {}", pretty_print_hexadecimal(&reference));
+            println!("  decoded: {ours}");
+            println!("  iced-decoded: {theirs}");
+            assert_eq!(
+                pretty_print_hexadecimal(&assembled),
+                pretty_print_hexadecimal(&reference)
+            );
+        }
+    }
+}
+
+/// Decode a single instruction with `iced-x86`, checking that it consumes all
+/// of the `assembled` bytes.
+fn iced_decode(assembled: &[u8], original: &Inst<FuzzRegs>) -> iced_x86::Instruction {
+    let mut decoder = iced_x86::Decoder::with_ip(64, assembled, 0, iced_x86::DecoderOptions::NONE);
+    let inst = decoder.decode();
+    if inst.is_invalid() || inst.len() != assembled.len() {
+        println!("> {original}");
+        println!("  debug: {original:x?}");
+        println!("  assembled: {}", pretty_print_hexadecimal(assembled));
+        println!("  decoded length: {}", inst.len());
+        panic!("iced-x86 did not decode a single, valid instruction");
+    }
+    inst
+}
+
+/// Print an instruction decoded by `iced-x86` in AT&T syntax, following the
+/// same conventions as our own `Display` output.
+fn iced_format(inst: &iced_x86::Instruction) -> String {
+    use iced_x86::Formatter;
+    let mut formatter = iced_x86::GasFormatter::new();
+    let options = formatter.options_mut();
+    options.set_gas_show_mnemonic_size_suffix(true);
+    options.set_space_after_operand_separator(true);
+    options.set_gas_space_after_memory_operand_comma(true);
+    options.set_hex_prefix("0x");
+    options.set_hex_suffix("");
+    options.set_uppercase_hex(false);
+    options.set_small_hex_numbers_in_decimal(false);
+    options.set_rip_relative_addresses(true);
+    options.set_branch_leading_zeros(false);
+    let mut output = String::new();
+    formatter.format(inst, &mut output);
+
+    // Account for where our conventions differ from `iced-x86`: we print the
+    // two-byte NOP as `nop`, `SETcc` without a size suffix, shifts by one
+    // without their implicit count, both operands of a two-operand `imul`,
+    // same-size `movsx`/`movzx` with a repeated size suffix, and small
+    // displacements and branch targets in decimal.
+    use iced_x86::{Mnemonic, OpKind};
+    let mnemonic = inst.mnemonic();
+    if output == "xchgw %ax, %ax" {
+        return "nop".to_owned();
+    }
+    if format!("{mnemonic:?}").starts_with("Set") {
+        output = output.replacen("b ", " ", 1);
+    }
+    if is_shift_by_one(inst) {
+        output = output.replacen("$0x1, ", "", 1);
+    }
+    if mnemonic == Mnemonic::Imul
+        && inst.op_count() == 3
+        && inst.op1_kind() == OpKind::Register
+        && inst.op0_register() == inst.op1_register()
+    {
+        let (_, dst) = output.rsplit_once(", ").unwrap();
+        output = format!("{output}, {dst}");
+    }
+    for (from, to) in [("movsxw ", "movsww "), ("movzxw ", "movzww ")] {
+        if let Some(rest) = output.strip_prefix(from) {
+            output = format!("{to}{rest}");
+        }
+    }
+    for digit in 0..10 {
+        output = output.replace(&format!("0x{digit}("), &format!("{digit}("));
+        if let Some(rest) = output.strip_suffix(&format!(" 0x{digit}")) {
+            output = format!("{rest} {digit}");
+        }
+    }
+    output
+}
+
+/// Check if `inst` is a shift or rotate by an implicit count of one.
+fn is_shift_by_one(inst: &iced_x86::Instruction) -> bool {
+    format!("{:?}", inst.code()).ends_with("_1")
+}
+
 /// Use this assembler to emit machine code into a byte buffer.
 ///
 /// This will skip any traps or label registrations, but this is fine for the
 /// single-instruction disassembly we're doing here.
-fn assemble(inst: &Inst<FuzzRegs>) -> Vec<u8> {
+pub(crate) fn assemble(inst: &Inst<FuzzRegs>) -> Vec<u8> {
     let mut sink = TestCodeSink::default();
     inst.encode(&mut sink);
     sink.patch_labels_as_if_they_referred_to_end();
@@ -50,9 +189,10 @@ fn assemble(inst: &Inst<FuzzRegs>) -> Vec<u8> {
 }
 
 #[derive(Default)]
-struct TestCodeSink {
-    buf: Vec<u8>,
+pub(crate) struct TestCodeSink {
+    pub(crate) buf: Vec<u8>,
     offsets_using_label: Vec<usize>,
+    pub(crate) relocs: Vec<(usize, RelocKind, Symbol, i32)>,
 }
 
 impl TestCodeSink {
@@ -108,6 +248,11 @@ impl CodeSink for TestCodeSink {
     fn known_offset(&self, target: KnownOffset) -> i32 {
         panic!("unsupported known target {target:?}")
     }
+
+    fn add_reloc(&mut self, kind: RelocKind, symbol: Symbol, addend: i32) {
+        let offset = self.buf.len();
+        self.relocs.push((offset, kind, symbol, addend));
+    }
 }
 
 /// Building a new `Capstone` each time is suboptimal (TODO).
@@ -279,6 +424,19 @@ impl Registers for FuzzRegs {
     type WriteXmm = FuzzReg;
 }
 
+/// The memory operand `simm32(%base)`, for tests.
+#[cfg(test)]
+pub(crate) fn imm_reg(base: u8, simm32: i32) -> crate::Amode<FuzzReg> {
+    crate::Amode::ImmReg {
+        base: FuzzReg::new(base),
+        simm32: AmodeOffsetPlusKnownOffset {
+            simm32: AmodeOffset::new(simm32),
+            offset: None,
+        },
+        trap: None,
+    }
+}
+
 /// A simple `u8` register type for fuzzing only.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct FuzzReg(u8);
@@ -400,6 +558,16 @@ mod test {
         // test a single input, append `.seed(0x<failing seed>)`.
     }
 
+    #[test]
+    fn differential_smoke() {
+        arbtest(|u| {
+            let inst: Inst<FuzzRegs> = Inst::arbitrary_rex_gpr(u)?;
+            differential(&inst);
+            Ok(())
+        })
+        .budget_ms(1_000);
+    }
+
     #[test]
     fn callq() {
         for i in -500..500 {
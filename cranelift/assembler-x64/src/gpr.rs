@@ -1,6 +1,6 @@
 //! Pure register operands; see [`Gpr`].
 
-use crate::AsReg;
+use crate::{AsReg, CodeSink, rex::encode_modrm};
 
 /// A general purpose x64 register (e.g., `%rax`).
 ///
@@ -32,6 +32,11 @@ impl<R: AsReg> Gpr<R> {
     pub fn to_string(&self, size: Size) -> String {
         self.0.to_string(Some(size))
     }
+
+    /// Emit this register as the `r/m` field of a ModR/M byte.
+    pub(crate) fn encode_modrm(&self, sink: &mut impl CodeSink, enc_reg: u8) {
+        sink.put1(encode_modrm(0b11, enc_reg & 0b111, self.enc() & 0b111));
+    }
 }
 
 impl<R: AsReg> AsRef<R> for Gpr<R> {
@@ -4,16 +4,34 @@
 //! See also: [`Inst`], an `enum` containing all these instructions.
 
 use crate::Fixed;
-use crate::api::{AsReg, CodeSink, RegisterVisitor, Registers, TrapCode};
+use crate::api::{
+    AsReg, CodeSink, EncodeError, RegisterVisitor, Registers, RelocKind, SliceSink, Symbol,
+    TrapCode,
+};
 use crate::evex::EvexPrefix;
 use crate::features::{AvailableFeatures, Feature, Features};
 use crate::gpr::{self, Gpr, Size};
 use crate::imm::{Extension, Imm8, Imm16, Imm32, Imm64, Simm8, Simm32};
 use crate::mem::{Amode, GprMem, XmmMem};
 use crate::rex::RexPrefix;
+use crate::template::{Patch, Template};
 use crate::vex::VexPrefix;
 use crate::xmm::{self, Xmm};
 
+/// The explicit operands of an instruction, printed in both operand orders.
+///
+/// The assembler's [`Display`](std::fmt::Display) output uses AT&T ordering
+/// (sources first, destination last); Intel ordering is the natural order of
+/// the reference manual (destination first). Both are available here so that
+/// tests can check either form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperandStrings {
+    /// The operands in AT&T order, e.g., `$0x1, %eax` for `addl`.
+    pub att: String,
+    /// The operands in Intel order, e.g., `%eax, $0x1` for `addl`.
+    pub intel: String,
+}
+
 // Include code generated by the `meta` crate.
 include!(concat!(env!("OUT_DIR"), "/assembler.rs"));
 
@@ -58,3 +76,341 @@ include!(concat!(env!("OUT_DIR"), "/assembler.rs"));
 /// ```
 #[doc(inline)]
 pub use for_each_feature;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AsReg;
+    use crate::fuzz::{FuzzReg, FuzzRegs, TestCodeSink, assemble, imm_reg, roundtrip};
+
+    #[test]
+    fn operand_order() {
+        use crate::gpr::enc::RAX;
+
+        // `addl $1, %eax` in AT&T order is `add eax, 1` in Intel order.
+        let inst: Inst<FuzzRegs> =
+            addl_mi::new(GprMem::Gpr(FuzzReg::new(RAX)), Imm32::new(1)).into();
+        let ops = inst.operand_strings();
+        assert_eq!(ops.att, "$0x1, %eax");
+        assert_eq!(ops.intel, "%eax, $0x1");
+
+        // The destination comes last in AT&T order, first in Intel order.
+        let inst: Inst<FuzzRegs> = vaddsd_b::new(
+            Xmm::new(FuzzReg::new(1)),
+            Xmm::new(FuzzReg::new(2)),
+            XmmMem::Xmm(FuzzReg::new(3)),
+        )
+        .into();
+        let ops = inst.operand_strings();
+        assert_eq!(ops.att, "%xmm3, %xmm2, %xmm1");
+        assert_eq!(ops.intel, "%xmm1, %xmm2, %xmm3");
+    }
+
+    #[test]
+    fn operand_widths() {
+        use crate::gpr::enc::RCX;
+
+        // A single `add` definition generates all four widths: the word form
+        // has a `0x66` prefix and the quadword form sets REX.W.
+        let rcx = || GprMem::Gpr(FuzzReg::new(RCX));
+        let insts: [Inst<FuzzRegs>; 4] = [
+            addb_mi::new(rcx(), Imm8::new(1)).into(),
+            addw_mi::new(rcx(), Imm16::new(1)).into(),
+            addl_mi::new(rcx(), Imm32::new(1)).into(),
+            addq_mi_sxl::new(rcx(), Simm32::new(1)).into(),
+        ];
+        let expected: [&[u8]; 4] = [
+            &[0x80, 0xc1, 0x01],
+            &[0x66, 0x81, 0xc1, 0x01, 0x00],
+            &[0x81, 0xc1, 0x01, 0x00, 0x00, 0x00],
+            &[0x48, 0x81, 0xc1, 0x01, 0x00, 0x00, 0x00],
+        ];
+        for (inst, expected) in insts.iter().zip(expected) {
+            assert_eq!(assemble(inst), expected);
+            roundtrip(inst);
+        }
+    }
+
+    #[test]
+    fn operand_size_override() {
+        use crate::gpr::enc::{RAX, RCX};
+
+        // `addw %cx, %ax`: the 16-bit form gains `0x66` from its operands...
+        let inst = addw_rm::new(Gpr::new(FuzzReg::new(RAX)), GprMem::Gpr(FuzzReg::new(RCX))).into();
+        assert_eq!(assemble(&inst), [0x66, 0x03, 0xc1]);
+        roundtrip(&inst);
+
+        // ...while `paddw %xmm2, %xmm1` emits its mandatory `0x66` only once.
+        let inst = paddw_a::new(Xmm::new(FuzzReg::new(1)), XmmMem::Xmm(FuzzReg::new(2))).into();
+        assert_eq!(assemble(&inst), [0x66, 0x0f, 0xfd, 0xca]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn condition_selector() {
+        use crate::gpr::enc::RBX;
+
+        // Each `setcc` repeats its condition code from the opcode's low nibble
+        // in the ModR/M `reg` field, with bit 3 in REX.R.
+        let rbx = || GprMem::Gpr(FuzzReg::new(RBX));
+        let insts: [Inst<FuzzRegs>; 16] = [
+            seto_m::new(rbx()).into(),
+            setno_m::new(rbx()).into(),
+            setb_m::new(rbx()).into(),
+            setae_m::new(rbx()).into(),
+            sete_m::new(rbx()).into(),
+            setne_m::new(rbx()).into(),
+            setbe_m::new(rbx()).into(),
+            seta_m::new(rbx()).into(),
+            sets_m::new(rbx()).into(),
+            setns_m::new(rbx()).into(),
+            setp_m::new(rbx()).into(),
+            setnp_m::new(rbx()).into(),
+            setl_m::new(rbx()).into(),
+            setge_m::new(rbx()).into(),
+            setle_m::new(rbx()).into(),
+            setg_m::new(rbx()).into(),
+        ];
+        for (cc, inst) in (0..16).zip(insts) {
+            let bytes = assemble(&inst);
+            let [.., opcode, modrm] = bytes[..] else {
+                unreachable!()
+            };
+            let rex_r = if bytes.len() == 4 {
+                (bytes[0] >> 2) & 1
+            } else {
+                0
+            };
+            assert_eq!(opcode, 0x90 | cc, "{inst}");
+            assert_eq!(rex_r << 3 | (modrm >> 3) & 0b111, cc, "{inst}");
+            assert_eq!(modrm & 0b111, RBX, "{inst}");
+            roundtrip(&inst);
+        }
+    }
+
+    #[test]
+    fn implicit_immediate() {
+        use crate::gpr::enc::RAX;
+
+        // `shll $1, %eax` has its own opcode and no immediate byte, unlike
+        // `shll $2, %eax`.
+        let rax = || GprMem::Gpr(FuzzReg::new(RAX));
+        let inst = shll_m1::new(rax()).into();
+        assert_eq!(assemble(&inst), [0xd1, 0xe0]);
+        roundtrip(&inst);
+        let inst = shll_mi::new(rax(), Imm8::new(2)).into();
+        assert_eq!(assemble(&inst), [0xc1, 0xe0, 0x02]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn segment_override() {
+        use crate::gpr::enc::{RAX, RCX};
+
+        // `movq %gs:0x10(%rax), %rcx`: the `0x65` prefix leaves the REX,
+        // opcode, and ModR/M bytes untouched.
+        let inst = movq_rm_gs::new(Gpr::new(FuzzReg::new(RCX)), imm_reg(RAX, 0x10)).into();
+        assert_eq!(assemble(&inst), [0x65, 0x48, 0x8b, 0x48, 0x10]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn implicit_registers_with_memory() {
+        use crate::gpr::enc::{R12, RAX, RBX, RCX, RDX};
+
+        // `cmpxchg16b` reads and writes four fixed registers but only encodes
+        // its memory operand, using `/1` in the ModR/M byte.
+        let regs = || {
+            (
+                FuzzReg::new(RAX),
+                FuzzReg::new(RDX),
+                FuzzReg::new(RBX),
+                FuzzReg::new(RCX),
+            )
+        };
+
+        let (rax, rdx, rbx, rcx) = regs();
+        let inst = cmpxchg16b_m::new(rax, rdx, rbx, rcx, imm_reg(RAX, 0)).into();
+        assert_eq!(assemble(&inst), [0x48, 0x0f, 0xc7, 0x08]);
+        roundtrip(&inst);
+
+        let (rax, rdx, rbx, rcx) = regs();
+        let inst = lock_cmpxchg16b_m::new(rax, rdx, rbx, rcx, imm_reg(R12, 0x10)).into();
+        assert_eq!(assemble(&inst), [0xf0, 0x49, 0x0f, 0xc7, 0x4c, 0x24, 0x10]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn repeat_prefix() {
+        // `rep movsq`: the `0xF3` repeat prefix precedes the REX prefix.
+        use crate::gpr::enc::{RAX, RCX, RDI, RSI};
+        let inst = movsq_zo::new(FuzzReg::new(RDI), FuzzReg::new(RSI), FuzzReg::new(RCX)).into();
+        assert_eq!(assemble(&inst), [0xf3, 0x48, 0xa5]);
+        roundtrip(&inst);
+
+        // `rep stosb`: no REX prefix is needed for the implicit `%al`.
+        let inst = stosb_zo::new(FuzzReg::new(RDI), FuzzReg::new(RCX), FuzzReg::new(RAX)).into();
+        assert_eq!(assemble(&inst), [0xf3, 0xaa]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn compressed_displacement() {
+        use crate::gpr::enc::RAX;
+
+        // `vaddpd` has a `Full` tuple type at 128 bits, so 8-bit displacements
+        // are scaled by 16.
+        let vaddpd = |simm32: i32| -> Inst<FuzzRegs> {
+            vaddpd_c::new(
+                Xmm::new(FuzzReg::new(1)),
+                Xmm::new(FuzzReg::new(2)),
+                XmmMem::Mem(imm_reg(RAX, simm32)),
+            )
+            .into()
+        };
+        for (simm32, disp8) in [
+            (16, true),
+            (127 * 16, true),
+            (128 * 16, false),
+            (-128 * 16, true),
+            (-129 * 16, false),
+            (8, false),
+        ] {
+            let inst = vaddpd(simm32);
+            let Inst::vaddpd_c(i) = &inst else {
+                unreachable!()
+            };
+            assert_eq!(i.chooses_disp8(), disp8, "displacement: {simm32}");
+            // EVEX prefix (4 bytes), opcode, ModR/M, and the displacement.
+            let disp_len = if disp8 { 1 } else { 4 };
+            assert_eq!(assemble(&inst).len(), 6 + disp_len);
+            roundtrip(&inst);
+        }
+    }
+
+    #[test]
+    fn embedded_rounding() {
+        // `vaddsd {ru-sae}, %xmm3, %xmm2, %xmm1`: `EVEX.L'L` holds the rounding
+        // mode and `EVEX.b` is set.
+        let inst = vaddsd_c_ru_sae::new(
+            Xmm::new(FuzzReg::new(1)),
+            Xmm::new(FuzzReg::new(2)),
+            Xmm::new(FuzzReg::new(3)),
+        )
+        .into();
+        assert_eq!(assemble(&inst), [0x62, 0xf1, 0xef, 0x58, 0x58, 0xcb]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn length_ignored() {
+        use crate::gpr::enc::RAX;
+
+        // `vaddsd 0x40(%rax), %xmm2, %xmm1`: `LIG` emits `EVEX.L'L = 0b00` and
+        // the 8-bit displacement is scaled by the 8-byte scalar operand.
+        let inst = vaddsd_c::new(
+            Xmm::new(FuzzReg::new(1)),
+            Xmm::new(FuzzReg::new(2)),
+            XmmMem::Mem(imm_reg(RAX, 0x40)),
+        )
+        .into();
+        assert_eq!(assemble(&inst), [0x62, 0xf1, 0xef, 0x08, 0x58, 0x48, 0x08]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn tuple_displacement() {
+        use crate::gpr::enc::RAX;
+
+        // `vbroadcasti32x2 0x40(%rax), %xmm1`: a `Tuple2` operand of 32-bit
+        // elements scales the 8-bit displacement by 8.
+        let inst =
+            vbroadcasti32x2_b::new(Xmm::new(FuzzReg::new(1)), XmmMem::Mem(imm_reg(RAX, 0x40)))
+                .into();
+        assert_eq!(assemble(&inst), [0x62, 0xf2, 0x7d, 0x08, 0x59, 0x48, 0x08]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn leading_immediate() {
+        use crate::gpr::enc::RAX;
+
+        // `outb %al, $0x80`: the DSL lists the immediate port first, so the
+        // encoder must not assume immediates are the final operand.
+        let inst = outb_i::new(Imm8::new(0x80), Fixed(FuzzReg::new(RAX))).into();
+        assert_eq!(assemble(&inst), [0xe6, 0x80]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn short_immediate() {
+        use crate::gpr::enc::RAX;
+
+        let assemble_short = |inst: &Inst<FuzzRegs>| {
+            let mut sink = TestCodeSink::default();
+            inst.encode_short(&mut sink);
+            sink.buf
+        };
+
+        // `addl $5, %eax` fits in a sign-extended byte, as does `$-1`...
+        let eax = || Fixed(FuzzReg::new(RAX));
+        let inst = addl_i::new(eax(), Imm32::new(5)).into();
+        assert_eq!(assemble(&inst).len(), 5);
+        assert_eq!(assemble_short(&inst), [0x83, 0xc0, 0x05]);
+        let inst = addl_mi::new(GprMem::Gpr(FuzzReg::new(RAX)), Imm32::new(u32::MAX));
+        assert_eq!(assemble_short(&inst.into()), [0x83, 0xc0, 0xff]);
+
+        // ...but `addl $0x1000, %eax` needs all four bytes.
+        let inst = addl_i::new(eax(), Imm32::new(0x1000)).into();
+        assert_eq!(assemble_short(&inst), assemble(&inst));
+        assert_eq!(assemble_short(&inst).len(), 5);
+
+        // Instructions without a shorter form are encoded as usual.
+        let inst = addb_i::new(Fixed(FuzzReg::new(RAX)), Imm8::new(5)).into();
+        assert_eq!(assemble_short(&inst), assemble(&inst));
+    }
+
+    #[test]
+    fn xchg_accumulator() {
+        use crate::gpr::enc::{RAX, RCX, RDX};
+
+        let xchgl = |a, b| -> Inst<FuzzRegs> {
+            xchgl_rr::new(Gpr::new(FuzzReg::new(a)), Gpr::new(FuzzReg::new(b))).into()
+        };
+
+        // `xchg %eax, %ecx` uses the one-byte `0x90 +rd` form, with the
+        // accumulator in either position...
+        assert_eq!(assemble(&xchgl(RAX, RCX)), [0x91]);
+        assert_eq!(assemble(&xchgl(RCX, RAX)), [0x91]);
+        roundtrip(&xchgl(RAX, RCX));
+        roundtrip(&xchgl(RCX, RAX));
+
+        // ...while `xchg %ecx, %edx` needs a ModR/M byte.
+        assert_eq!(assemble(&xchgl(RCX, RDX)), [0x87, 0xca]);
+        roundtrip(&xchgl(RCX, RDX));
+
+        // `xchg %eax, %eax` is not `nop`: it clears the upper bits of `%rax`.
+        assert_eq!(assemble(&xchgl(RAX, RAX)), [0x87, 0xc0]);
+        roundtrip(&xchgl(RAX, RAX));
+
+        let inst: Inst<FuzzRegs> =
+            xchgq_rr::new(Gpr::new(FuzzReg::new(RAX)), Gpr::new(FuzzReg::new(13))).into();
+        assert_eq!(assemble(&inst), [0x49, 0x95]);
+        roundtrip(&inst);
+    }
+
+    #[test]
+    fn macro_fusible() {
+        use crate::gpr::enc::{RAX, RCX};
+
+        let rax = || Gpr::new(FuzzReg::new(RAX));
+        let rcx = || GprMem::Gpr(FuzzReg::new(RCX));
+
+        let cmp = cmpq_rm::<FuzzRegs>::new(rax(), rcx());
+        assert!(cmp.macro_fusible());
+        let test: Inst<FuzzRegs> = testq_mr::new(rcx(), rax()).into();
+        assert!(test.macro_fusible());
+        let add: Inst<FuzzRegs> = addq_rm::new(rax(), rcx()).into();
+        assert!(!add.macro_fusible());
+    }
+}
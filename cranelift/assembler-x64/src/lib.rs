@@ -51,7 +51,9 @@ pub mod gpr;
 mod imm;
 pub mod inst;
 mod mem;
+mod nop;
 mod rex;
+mod template;
 mod vex;
 pub mod xmm;
 
@@ -68,7 +70,8 @@ pub mod fuzz;
 pub use inst::Inst;
 
 pub use api::{
-    AsReg, CodeSink, Constant, KnownOffset, Label, RegisterVisitor, Registers, TrapCode,
+    AsReg, CodeSink, Constant, EncodeError, KnownOffset, Label, RegisterVisitor, Registers,
+    RelocKind, SliceSink, Symbol, TraceSink, TracedComponent, TrapCode,
 };
 pub use features::{AvailableFeatures, Feature, Features};
 pub use fixed::Fixed;
@@ -77,5 +80,7 @@ pub use imm::{Extension, Imm8, Imm16, Imm32, Imm64, Simm8, Simm16, Simm32};
 pub use mem::{
     Amode, AmodeOffset, AmodeOffsetPlusKnownOffset, DeferredTarget, GprMem, Scale, XmmMem,
 };
+pub use nop::{encode_alignment_nops, encode_nops};
 pub use rex::RexPrefix;
+pub use template::{Patch, Template};
 pub use xmm::Xmm;
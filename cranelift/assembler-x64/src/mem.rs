@@ -1,6 +1,6 @@
 //! Memory operands to instructions.
 
-use crate::api::{AsReg, CodeSink, Constant, KnownOffset, Label, TrapCode};
+use crate::api::{AsReg, CodeSink, Constant, KnownOffset, Label, RelocKind, Symbol, TrapCode};
 use crate::gpr::{self, NonRspGpr, Size};
 use crate::rex::{Disp, RexPrefix, encode_modrm, encode_sib};
 
@@ -60,6 +60,29 @@ impl<R: AsReg> Amode<R> {
         emit_modrm_sib_disp(sink, enc_reg, self, bytes_at_end, evex_scaling);
     }
 
+    /// Return `true` if this address is encoded with an 8-bit displacement,
+    /// compressed by `evex_scaling` for EVEX instructions.
+    ///
+    /// [`KnownOffset`]s are only resolved during emission so an address using
+    /// one is conservatively reported as not using an 8-bit displacement.
+    pub fn chooses_disp8(&self, evex_scaling: Option<i8>) -> bool {
+        let (base, simm32) = match self {
+            Amode::ImmReg {
+                base,
+                simm32:
+                    AmodeOffsetPlusKnownOffset {
+                        simm32,
+                        offset: None,
+                    },
+                ..
+            }
+            | Amode::ImmRegRegShift { base, simm32, .. } => (base, simm32),
+            Amode::ImmReg { .. } | Amode::RipRelative { .. } => return false,
+        };
+        let disp = address_disp(base.enc(), simm32.value(), evex_scaling);
+        matches!(disp, Disp::Imm8(_))
+    }
+
     /// Return the registers for encoding the `b` and `x` bits (e.g., in a VEX
     /// prefix).
     ///
@@ -169,6 +192,9 @@ impl std::fmt::LowerHex for AmodeOffsetPlusKnownOffset {
 pub enum DeferredTarget {
     Label(Label),
     Constant(Constant),
+    /// A symbol which the [`CodeSink`] is told to relocate against, via
+    /// [`CodeSink::add_reloc`], rather than through `use_target`.
+    Symbol(Symbol),
     None,
 }
 
@@ -367,6 +393,15 @@ impl<R: AsReg, M: AsReg> XmmMem<R, M> {
         }
     }
 
+    /// Return `true` if this is a memory operand encoded with an 8-bit
+    /// displacement; see [`Amode::chooses_disp8`].
+    pub fn chooses_disp8(&self, evex_scaling: Option<i8>) -> bool {
+        match self {
+            XmmMem::Xmm(_) => false,
+            XmmMem::Mem(amode) => amode.chooses_disp8(evex_scaling),
+        }
+    }
+
     /// Return the registers for encoding the `b` and `x` bits (e.g., in a VEX
     /// prefix).
     ///
@@ -393,6 +428,21 @@ impl<R: AsReg, M: AsReg> From<Amode<M>> for XmmMem<R, M> {
     }
 }
 
+/// Classify the displacement of an address with base register `enc_base`.
+///
+/// If the offset is zero then there is no immediate. Note, though, that if the
+/// base register's lower three bits are `101` (rbp/r13) then an offset must be
+/// present: with a ModRM byte alone this would otherwise encode RIP-relative
+/// addressing and with a SIB byte it would mean "no base register". In that
+/// case a 1-byte zero offset is forced.
+fn address_disp(enc_base: u8, val: i32, evex_scaling: Option<i8>) -> Disp {
+    let mut imm = Disp::new(val, evex_scaling);
+    if enc_base & 7 == gpr::enc::RBP {
+        imm.force_immediate();
+    }
+    imm
+}
+
 /// Emit the ModRM/SIB/displacement sequence for a memory operand.
 pub fn emit_modrm_sib_disp<R: AsReg>(
     sink: &mut impl CodeSink,
@@ -404,7 +454,7 @@ pub fn emit_modrm_sib_disp<R: AsReg>(
     match *mem_e {
         Amode::ImmReg { simm32, base, .. } => {
             let enc_e = base.enc();
-            let mut imm = Disp::new(simm32.value(sink), evex_scaling);
+            let imm = address_disp(enc_e, simm32.value(sink), evex_scaling);
 
             // Most base registers allow for a single ModRM byte plus an
             // optional immediate. If rsp is the base register, however, then a
@@ -420,12 +470,6 @@ pub fn emit_modrm_sib_disp<R: AsReg>(
                 sink.put1(0b00_100_100);
                 imm.emit(sink);
             } else {
-                // If the base register is rbp and there's no offset then force
-                // a 1-byte zero offset since otherwise the encoding would be
-                // invalid.
-                if enc_e_low3 == gpr::enc::RBP {
-                    imm.force_immediate();
-                }
                 sink.put1(encode_modrm(imm.m0d(), enc_g & 7, enc_e & 7));
                 imm.emit(sink);
             }
@@ -447,14 +491,7 @@ pub fn emit_modrm_sib_disp<R: AsReg>(
             // REX bytes so only rsp is disallowed.
             assert!(enc_index != gpr::enc::RSP);
 
-            // If the offset is zero then there is no immediate. Note, though,
-            // that if the base register's lower three bits are `101` then an
-            // offset must be present. This is a special case in the encoding of
-            // the SIB byte and requires an explicit displacement with rbp/r13.
-            let mut imm = Disp::new(simm32.value(), evex_scaling);
-            if enc_base & 7 == gpr::enc::RBP {
-                imm.force_immediate();
-            }
+            let imm = address_disp(enc_base, simm32.value(), evex_scaling);
 
             // With the above determined encode the ModRM byte, then the SIB
             // byte, then any immediate as necessary.
@@ -463,6 +500,20 @@ pub fn emit_modrm_sib_disp<R: AsReg>(
             imm.emit(sink);
         }
 
+        Amode::RipRelative {
+            target: DeferredTarget::Symbol(symbol),
+        } => {
+            // RIP-relative is mod=00, rm=101.
+            sink.put1(encode_modrm(0b00, enc_g & 7, 0b101));
+
+            // The relocation is relative to the end of the u32 field, but the
+            // addressed location is relative to the end of the instruction,
+            // so the addend accounts for any bytes following the field.
+            let addend = -4 - i32::from(bytes_at_end);
+            sink.add_reloc(RelocKind::PcRel4, symbol, addend);
+            sink.put4(0);
+        }
+
         Amode::RipRelative { target } => {
             // RIP-relative is mod=00, rm=101.
             sink.put1(encode_modrm(0b00, enc_g & 7, 0b101));
@@ -0,0 +1,68 @@
+//! Pad code with the recommended multi-byte `NOP` sequences.
+
+use crate::api::CodeSink;
+use crate::inst;
+
+/// Emit `len` bytes of `NOP`s, using the longest recommended sequences (up to
+/// 9 bytes each) rather than repeated single-byte `0x90`s.
+pub fn encode_nops(buf: &mut impl CodeSink, mut len: usize) {
+    while len > 0 {
+        let n = len.min(9);
+        match n {
+            1 => inst::nop_1b::new().encode(buf),
+            2 => inst::nop_2b::new().encode(buf),
+            3 => inst::nop_3b::new().encode(buf),
+            4 => inst::nop_4b::new().encode(buf),
+            5 => inst::nop_5b::new().encode(buf),
+            6 => inst::nop_6b::new().encode(buf),
+            7 => inst::nop_7b::new().encode(buf),
+            8 => inst::nop_8b::new().encode(buf),
+            _ => inst::nop_9b::new().encode(buf),
+        }
+        len -= n;
+    }
+}
+
+/// Emit the `NOP`s needed to advance `offset`, the current offset into `buf`,
+/// to a multiple of `align`.
+///
+/// # Panics
+///
+/// Panics if `align` is not a power of two.
+pub fn encode_alignment_nops(buf: &mut impl CodeSink, offset: usize, align: usize) {
+    assert!(align.is_power_of_two(), "alignment must be a power of two");
+    encode_nops(buf, offset.wrapping_neg() & (align - 1));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fuzz::{FuzzReg, FuzzRegs, assemble};
+    use crate::{AsReg, Inst};
+
+    #[test]
+    fn encode_aligned() {
+        use crate::gpr::enc::RAX;
+        use crate::{Fixed, Imm32};
+
+        let inst: Inst<FuzzRegs> =
+            crate::inst::addl_i::new(Fixed(FuzzReg::new(RAX)), Imm32::new(0x1000)).into();
+        let mut buf = vec![0xcc; 3];
+        inst.encode_aligned(&mut buf, 3, 16);
+
+        // Thirteen bytes of padding use a 9-byte and a 4-byte `nop`...
+        assert_eq!(buf.len(), 16 + 5);
+        let mut nops = vec![];
+        crate::inst::nop_9b::new().encode(&mut nops);
+        crate::inst::nop_4b::new().encode(&mut nops);
+        assert_eq!(buf[3..16], nops);
+        assert!(!buf[3..16].contains(&0x90));
+
+        // ...and the instruction starts on the 16-byte boundary.
+        assert_eq!(buf[16..], assemble(&inst));
+
+        // Already-aligned offsets need no padding.
+        let mut buf = vec![];
+        inst.encode_aligned(&mut buf, 32, 16);
+        assert_eq!(buf, assemble(&inst));
+    }
+}
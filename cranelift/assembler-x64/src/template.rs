@@ -0,0 +1,123 @@
+//! Pre-encoded instructions whose immediates are patched in at emission time.
+
+use crate::api::CodeSink;
+use std::vec::Vec;
+
+/// The encoding of an instruction with its registers and memory operands
+/// fixed, but with slots left for its immediates.
+///
+/// Building a template encodes the instruction once; emitting it afterwards
+/// copies the encoded bytes into the sink, writing a new value into each
+/// [`Patch`] in place of the original immediate. This produces the same bytes
+/// as encoding the instruction with those immediates.
+///
+/// Only immediates can be patched: changing a register or a memory
+/// displacement may change the length of the encoding (e.g., by adding a REX
+/// prefix or widening an 8-bit displacement), so these are fixed when the
+/// template is built. Templates are built at runtime, not in `const` contexts,
+/// and only for REX-encoded instructions taking an immediate, with their
+/// `template` method, e.g.,
+/// [`movl_oi::template`](crate::inst::movl_oi::template).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    bytes: Vec<u8>,
+    patches: Vec<Patch>,
+}
+
+/// A slot in a [`Template`] to overwrite with an immediate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Patch {
+    /// The offset of the slot from the start of the instruction.
+    pub offset: usize,
+    /// The width of the slot, in bytes: 1, 2, 4, or 8.
+    pub size: usize,
+}
+
+impl Template {
+    /// Create a template from encoded `bytes` and the `patches` to apply to
+    /// them, one per immediate in operand order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a patch has an unsupported width, does not fit within
+    /// `bytes`, or overlaps or precedes the patch before it.
+    #[must_use]
+    pub fn new(bytes: Vec<u8>, patches: Vec<Patch>) -> Self {
+        let mut end = 0;
+        for patch in &patches {
+            assert!(
+                matches!(patch.size, 1 | 2 | 4 | 8),
+                "unsupported patch width"
+            );
+            assert!(patch.offset >= end, "patches must be ordered");
+            end = patch.offset + patch.size;
+            assert!(end <= bytes.len(), "patch out of bounds");
+        }
+        Self { bytes, patches }
+    }
+
+    /// The encoded bytes, holding the immediates the template was built with.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The slots to overwrite, in operand order.
+    #[must_use]
+    pub fn patches(&self) -> &[Patch] {
+        &self.patches
+    }
+
+    /// Emit the template into `buf`, writing each of `values` (truncated to
+    /// the patch width) into its slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one value per patch.
+    pub fn encode(&self, buf: &mut impl CodeSink, values: &[u64]) {
+        assert_eq!(values.len(), self.patches.len(), "one value per patch");
+        let mut start = 0;
+        for (patch, &value) in self.patches.iter().zip(values) {
+            buf.put_data(&self.bytes[start..patch.offset]);
+            match patch.size {
+                1 => buf.put1(value as u8),
+                2 => buf.put2(value as u16),
+                4 => buf.put4(value as u32),
+                8 => buf.put8(value),
+                _ => unreachable!(),
+            }
+            start = patch.offset + patch.size;
+        }
+        buf.put_data(&self.bytes[start..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AsReg;
+    use crate::fuzz::{FuzzReg, FuzzRegs, assemble};
+
+    #[test]
+    fn template() {
+        use crate::gpr::enc::{R9, RAX};
+        use crate::{Gpr, Imm32, Patch};
+
+        let mov = |reg, imm| {
+            crate::inst::movl_oi::<FuzzRegs>::new(Gpr::new(FuzzReg::new(reg)), Imm32::new(imm))
+        };
+
+        for reg in [RAX, R9] {
+            // The `imm32` follows the opcode and any REX prefix...
+            let template = mov(reg, 0).template();
+            let offset = template.bytes().len() - 4;
+            assert_eq!(template.patches(), [Patch { offset, size: 4 }]);
+
+            // ...and patching it matches encoding the immediate directly.
+            for imm in [0, 1, 0x1234_5678, u32::MAX] {
+                let mut buf = vec![];
+                template.encode(&mut buf, &[imm.into()]);
+                assert_eq!(buf, assemble(&mov(reg, imm).into()));
+            }
+        }
+    }
+}
@@ -30,6 +30,114 @@ pub(crate) fn define() -> SettingGroup {
         false,
     );
 
+    settings.add_bool(
+        "regalloc_validate_ssa",
+        "Validate that VCode is in SSA form before register allocation.",
+        r#"
+            Debug builds of Cranelift always run regalloc2's SSA validation on its input. This
+            forces that validation on in release builds as well, trading some compile time for
+            an extra check that lowering produced well-formed input for the register allocator.
+        "#,
+        false,
+    );
+
+    settings.add_bool(
+        "regalloc_checks_disabled",
+        "Disable all register allocation checks, overriding the other settings.",
+        r#"
+            This turns off both the symbolic checker (`regalloc_checker`) and regalloc2's SSA
+            validation (`regalloc_validate_ssa`), including the validation that debug builds of
+            Cranelift always run. It is a single switch for trading safety for compile speed, e.g.
+            when repeatedly compiling very large modules during local development; it should not
+            be used to compile untrusted input.
+        "#,
+        false,
+    );
+
+    settings.add_num(
+        "regalloc_reject_moves_log2_minus_one",
+        "The log2 of the move count above which register allocation is rejected, plus one",
+        r#"
+            After register allocation, if it inserted more than two raised to the power of this
+            value minus one spills, reloads and moves into a function, compilation fails with
+            `CodegenError::RegallocBudgetExceeded` instead of producing the resulting code.
+            Embedders compiling untrusted input can use this to reject functions that are
+            pathological for the register allocator.
+
+            This is a post-hoc rejection: the allocator still runs to completion first, so this
+            does not bound the time spent on a function. Use
+            `regalloc_size_budget_log2_minus_one` for that.
+
+            The default is 0, which places no limit on the number of moves.
+        "#,
+        0,
+    );
+
+    settings.add_num(
+        "regalloc_size_budget_log2_minus_one",
+        "The log2 of the maximum size of a function given to register allocation, plus one",
+        r#"
+            If a lowered function has more than two raised to the power of this value minus one
+            instructions or virtual registers, compilation fails with
+            `CodegenError::RegallocBudgetExceeded` before register allocation starts. The
+            allocator cannot be interrupted, so this bounds the time spent on a single function
+            where `regalloc_reject_moves_log2_minus_one` only rejects its result.
+
+            The default is 0, which places no limit on the size of functions.
+        "#,
+        0,
+    );
+
+    settings.add_bool(
+        "compile_phase_timing",
+        "Record how long each backend compilation phase takes.",
+        r#"
+            When enabled, the backend measures the wall-clock time spent in lowering,
+            proof-carrying-code validation, register allocation and the register allocation
+            checker for every function, and reports it alongside the compilation result. This is
+            disabled by default to avoid reading the clock when nobody consumes the timings.
+        "#,
+        false,
+    );
+
+    settings.add_bool(
+        "regalloc_block_stats",
+        "Count the moves register allocation inserts into each block.",
+        r#"
+            When enabled, the backend reports the spills, reloads and moves that register
+            allocation inserted into each lowered block of every function alongside the
+            compilation result, e.g. to find the hot loops where lowering rules cost the most
+            moves.
+        "#,
+        false,
+    );
+
+    settings.add_bool(
+        "compile_determinism_check",
+        "Check that compiling each function twice produces identical results.",
+        r#"
+            When enabled, the backend compiles every function two extra times with a fresh
+            control plane and fails with `CodegenError::Nondeterministic`, showing where they
+            differ, if the lowered VCode or the register allocation results are not identical.
+            This catches nondeterminism such as iteration over randomly-ordered hash maps, which
+            would break reproducible builds. It triples backend compile time and is disabled by
+            default.
+        "#,
+        false,
+    );
+
+    settings.add_bool(
+        "dump_vcode",
+        "Write each function's VCode and register allocation result to a file.",
+        r#"
+            When enabled, the backend writes the lowered VCode and the regalloc2 output of every
+            function it compiles to `<function name>.vcode` in the directory named by the
+            `CRANELIFT_VCODE_DUMP_DIR` environment variable, or the system temporary directory if
+            that is unset. This is a debugging aid for miscompiles and is disabled by default.
+        "#,
+        false,
+    );
+
     settings.add_enum(
         "regalloc_algorithm",
         "Algorithm to use in register allocator.",
@@ -91,6 +199,31 @@ pub(crate) fn define() -> SettingGroup {
         false,
     );
 
+    settings.add_bool(
+        "pcc_report",
+        "Report which memory accesses proof-carrying code proved in bounds.",
+        r#"
+            When `enable_pcc` is also enabled, compilation returns a report listing each
+            memory access that PCC validation inspected and whether it was proven in bounds or
+            trusted without a proof (e.g. unchecked accesses and stack-slot accesses), for
+            auditing how much of the compiled code is actually verified.
+        "#,
+        false,
+    );
+
+    settings.add_bool(
+        "require_pcc",
+        "Reject untrusted memory accesses unless proof-carrying code is enabled.",
+        r#"
+            When this is enabled but `enable_pcc` is not, compilation fails if the function
+            contains a memory access that may trap or is marked "checked", rather than
+            compiling it without a proof. Accesses marked "notrap" and not "checked" are
+            trusted. This guards embedders whose sandboxing relies on PCC against forgetting
+            to enable it.
+        "#,
+        false,
+    );
+
     // Note that Cranelift doesn't currently need an is_pie flag, because PIE is
     // just PIC where symbols can't be pre-empted, which can be expressed with the
     // `colocated` flag on external functions and global values.
@@ -18,7 +18,7 @@ use crate::ir::Function;
 use crate::isa::TargetIsa;
 use crate::legalizer::simple_legalize;
 use crate::loop_analysis::LoopAnalysis;
-use crate::machinst::{CompiledCode, CompiledCodeStencil};
+use crate::machinst::{CompiledCode, CompiledCodeStencil, LoweredVCode};
 use crate::nan_canonicalization::do_nan_canonicalization;
 use crate::remove_constant_phis::do_remove_constant_phis;
 use crate::result::{CodegenResult, CompileResult};
@@ -27,6 +27,7 @@ use crate::trace;
 use crate::unreachable_code::eliminate_unreachable_code;
 use crate::verifier::{VerifierErrors, VerifierResult, verify_context};
 use crate::{CompileError, timing};
+use alloc::boxed::Box;
 #[cfg(feature = "souper-harvest")]
 use alloc::string::String;
 use alloc::vec::Vec;
@@ -129,6 +130,15 @@ impl Context {
         &mut self,
         isa: &dyn TargetIsa,
         ctrl_plane: &mut ControlPlane,
+    ) -> CodegenResult<CompiledCodeStencil> {
+        self.compile_stencil_with_pre_regalloc_hook(isa, ctrl_plane, &mut |_| {})
+    }
+
+    fn compile_stencil_with_pre_regalloc_hook(
+        &mut self,
+        isa: &dyn TargetIsa,
+        ctrl_plane: &mut ControlPlane,
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
     ) -> CodegenResult<CompiledCodeStencil> {
         let result;
         trace!("****** START compiling {}", self.func.display_spec());
@@ -137,12 +147,30 @@ impl Context {
 
             self.verify_if(isa)?;
             self.optimize(isa, ctrl_plane)?;
-            result = isa.compile_function(&self.func, &self.domtree, self.want_disasm, ctrl_plane);
+            result = isa.compile_function_with_pre_regalloc_hook(
+                &self.func,
+                &self.domtree,
+                self.want_disasm,
+                ctrl_plane,
+                pre_regalloc,
+            );
         }
         trace!("****** DONE compiling {}\n", self.func.display_spec());
         result
     }
 
+    /// Lower the function to VCode without allocating registers, performing
+    /// all compilation steps up to but not including register allocation.
+    pub fn lower_only(
+        &mut self,
+        isa: &dyn TargetIsa,
+        ctrl_plane: &mut ControlPlane,
+    ) -> CodegenResult<Box<dyn LoweredVCode>> {
+        self.verify_if(isa)?;
+        self.optimize(isa, ctrl_plane)?;
+        isa.lower_function(&self.func, &self.domtree, ctrl_plane)
+    }
+
     /// Optimize the function, performing all compilation steps up to
     /// but not including machine-code lowering and register
     /// allocation.
@@ -212,9 +240,23 @@ impl Context {
         &mut self,
         isa: &dyn TargetIsa,
         ctrl_plane: &mut ControlPlane,
+    ) -> CompileResult<'_, &CompiledCode> {
+        self.compile_with_pre_regalloc_hook(isa, ctrl_plane, &mut |_| {})
+    }
+
+    /// Like [`Context::compile`], but invokes `pre_regalloc` with the lowered
+    /// VCode right before register allocation.
+    ///
+    /// This lets tooling inspect instruction selection, e.g. to build register
+    /// pressure heatmaps or run custom verifiers, without forking the pipeline.
+    pub fn compile_with_pre_regalloc_hook(
+        &mut self,
+        isa: &dyn TargetIsa,
+        ctrl_plane: &mut ControlPlane,
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
     ) -> CompileResult<'_, &CompiledCode> {
         let stencil = self
-            .compile_stencil(isa, ctrl_plane)
+            .compile_stencil_with_pre_regalloc_hook(isa, ctrl_plane, pre_regalloc)
             .map_err(|error| CompileError {
                 inner: error,
                 func: &self.func,
@@ -75,8 +75,10 @@
 use crate::ir;
 use crate::ir::types::*;
 use crate::isa::TargetIsa;
-use crate::machinst::{BlockIndex, LowerBackend, VCode};
+use crate::machinst::{BlockIndex, InsnIndex, LowerBackend, VCode};
 use crate::trace;
+use alloc::vec::Vec;
+use core::cell::RefCell;
 use regalloc2::Function as _;
 use std::fmt;
 
@@ -750,12 +752,38 @@ pub enum InequalityKind {
     Loose,
 }
 
+/// How proof-carrying-code validation treated a memory access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessVerdict {
+    /// The address was proven to be in bounds of its memory type.
+    Proven,
+    /// The access was accepted without a proof, e.g. because it is not
+    /// marked `checked` or because it is to a stack slot or constant that
+    /// the compiler itself generated.
+    Trusted,
+}
+
+/// The memory accesses seen by successful proof-carrying-code validation,
+/// for auditing which of them were actually proven in bounds.
+///
+/// Only accesses that the backend inspects are listed; in particular, most
+/// x64 instructions are not yet checked at all.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PccReport {
+    /// The VCode instruction performing each access and its verdict, in
+    /// program order.
+    pub accesses: Vec<(InsnIndex, AccessVerdict)>,
+}
+
 /// A "context" in which we can evaluate and derive facts. This
 /// context carries environment/global properties, such as the machine
 /// pointer width.
 pub struct FactContext<'a> {
     function: &'a ir::Function,
     pointer_width: u16,
+    /// Verdicts recorded for the instruction being checked, if a
+    /// [`PccReport`] was requested.
+    verdicts: Option<RefCell<Vec<AccessVerdict>>>,
 }
 
 impl<'a> FactContext<'a> {
@@ -764,6 +792,14 @@ impl<'a> FactContext<'a> {
         FactContext {
             function,
             pointer_width,
+            verdicts: None,
+        }
+    }
+
+    /// Record how a memory access was validated, for a [`PccReport`].
+    pub fn record_access(&self, verdict: AccessVerdict) {
+        if let Some(verdicts) = &self.verdicts {
+            verdicts.borrow_mut().push(verdict);
         }
     }
 
@@ -1638,15 +1674,28 @@ fn max_value_for_width(bits: u16) -> u64 {
 
 /// Top-level entry point after compilation: this checks the facts in
 /// VCode.
+///
+/// If `report` is provided, the verdict for each memory access is appended to
+/// it.
 pub fn check_vcode_facts<B: LowerBackend + TargetIsa>(
     f: &ir::Function,
     vcode: &mut VCode<B::MInst>,
     backend: &B,
+    mut report: Option<&mut PccReport>,
 ) -> PccResult<()> {
-    let ctx = FactContext::new(f, backend.triple().pointer_width().unwrap().bits().into());
+    let mut ctx = FactContext::new(f, backend.triple().pointer_width().unwrap().bits().into());
+    if report.is_some() {
+        ctx.verdicts = Some(RefCell::new(Vec::new()));
+    }
 
     // Check that individual instructions are valid according to input
     // facts, and support the stated output facts.
+    //
+    // Note that blocks must be checked one at a time, in order: checking an
+    // instruction may record a propagated fact on its output vreg (see
+    // `check_output`), and instructions in later blocks consume those facts.
+    // Checking blocks in parallel would therefore make both the facts seen and
+    // the first error reported depend on scheduling.
     for block in 0..vcode.num_blocks() {
         let block = BlockIndex::new(block);
         let mut flow_state = B::FactFlowState::default();
@@ -1656,6 +1705,11 @@ pub fn check_vcode_facts<B: LowerBackend + TargetIsa>(
                 log::info!("Error checking instruction: {:?}", vcode[inst]);
                 return Err(e);
             }
+            if let (Some(report), Some(verdicts)) = (report.as_deref_mut(), &ctx.verdicts) {
+                report
+                    .accesses
+                    .extend(verdicts.borrow_mut().drain(..).map(|v| (inst, v)));
+            }
 
             // If this is a branch, check that all block arguments subsume
             // the assumed facts on the blockparams of successors.
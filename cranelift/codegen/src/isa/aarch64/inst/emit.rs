@@ -711,6 +711,7 @@ impl EmitState {
 }
 
 /// Constant state used during function compilation.
+#[derive(Clone)]
 pub struct EmitInfo(settings::Flags);
 
 impl EmitInfo {
@@ -7,8 +7,8 @@ use crate::isa::aarch64::settings as aarch64_settings;
 use crate::isa::unwind::systemv;
 use crate::isa::{Builder as IsaBuilder, FunctionAlignment, IsaFlagsHashKey, TargetIsa};
 use crate::machinst::{
-    CompiledCode, CompiledCodeStencil, MachInst, MachTextSectionBuilder, Reg, SigSet,
-    TextSectionBuilder, VCode, compile,
+    CompileOptions, CompileOutput, CompiledCode, CompiledCodeStencil, LoweredVCode, MachInst,
+    MachTextSectionBuilder, Reg, SigSet, TextSectionBuilder, compile,
 };
 use crate::result::CodegenResult;
 use crate::settings as shared_settings;
@@ -55,11 +55,18 @@ impl AArch64Backend {
         func: &Function,
         domtree: &DominatorTree,
         ctrl_plane: &mut ControlPlane,
-    ) -> CodegenResult<(VCode<inst::Inst>, regalloc2::Output)> {
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompileOutput<inst::Inst>> {
         let emit_info = EmitInfo::new(self.flags.clone());
         let sigs = SigSet::new::<abi::AArch64MachineDeps>(func, &self.flags)?;
         let abi = abi::AArch64Callee::new(func, self, &self.isa_flags, &sigs)?;
-        compile::compile::<AArch64Backend>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)
+        let options = CompileOptions {
+            pre_regalloc: Some(&mut |vcode| pre_regalloc(vcode)),
+            ..Default::default()
+        };
+        compile::compile_with_options::<AArch64Backend>(
+            func, domtree, self, abi, emit_info, sigs, ctrl_plane, options,
+        )
     }
 }
 
@@ -71,7 +78,28 @@ impl TargetIsa for AArch64Backend {
         want_disasm: bool,
         ctrl_plane: &mut ControlPlane,
     ) -> CodegenResult<CompiledCodeStencil> {
-        let (vcode, regalloc_result) = self.compile_vcode(func, domtree, ctrl_plane)?;
+        self.compile_function_with_pre_regalloc_hook(
+            func,
+            domtree,
+            want_disasm,
+            ctrl_plane,
+            &mut |_| {},
+        )
+    }
+
+    fn compile_function_with_pre_regalloc_hook(
+        &self,
+        func: &Function,
+        domtree: &DominatorTree,
+        want_disasm: bool,
+        ctrl_plane: &mut ControlPlane,
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompiledCodeStencil> {
+        let CompileOutput {
+            vcode,
+            regalloc_result,
+            report,
+        } = self.compile_vcode(func, domtree, ctrl_plane, pre_regalloc)?;
 
         let emit_result = vcode.emit(&regalloc_result, want_disasm, &self.flags, ctrl_plane);
         let frame_size = emit_result.frame_size;
@@ -93,9 +121,24 @@ impl TargetIsa for AArch64Backend {
             dynamic_stackslot_offsets,
             bb_starts: emit_result.bb_offsets,
             bb_edges: emit_result.bb_edges,
+            report,
         })
     }
 
+    fn lower_function(
+        &self,
+        func: &Function,
+        domtree: &DominatorTree,
+        ctrl_plane: &mut ControlPlane,
+    ) -> CodegenResult<Box<dyn LoweredVCode>> {
+        let emit_info = EmitInfo::new(self.flags.clone());
+        let sigs = SigSet::new::<abi::AArch64MachineDeps>(func, &self.flags)?;
+        let abi = abi::AArch64Callee::new(func, self, &self.isa_flags, &sigs)?;
+        let vcode =
+            compile::lower_only::<Self>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)?;
+        Ok(Box::new(vcode))
+    }
+
     fn name(&self) -> &'static str {
         "aarch64"
     }
@@ -284,3 +327,96 @@ pub fn isa_builder(triple: Triple) -> IsaBuilder {
         },
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cursor::{Cursor, FuncCursor};
+    use crate::flowgraph::ControlFlowGraph;
+    use crate::ir::pcc::{AccessVerdict, Fact};
+    use crate::ir::{
+        AbiParam, ArgumentPurpose, InstBuilder, MemFlags, MemoryTypeData, Signature, UserFuncName,
+        types,
+    };
+    use crate::isa::CallConv;
+    use crate::settings::Configurable;
+
+    /// Build a function that loads a byte from a 4 GiB memory at a 32-bit
+    /// offset, with facts proving the load in bounds.
+    fn bounds_checked_load() -> Function {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params
+            .push(AbiParam::special(types::I64, ArgumentPurpose::VMContext));
+        sig.params.push(AbiParam::new(types::I32));
+        sig.returns.push(AbiParam::new(types::I8));
+        let mut func = Function::with_name_signature(UserFuncName::testcase("load"), sig);
+        let mt = func.create_memory_type(MemoryTypeData::Memory {
+            size: 0x1_0000_0000,
+        });
+        let block0 = func.dfg.make_block();
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        let base = pos.func.dfg.append_block_param(block0, types::I64);
+        let index = pos.func.dfg.append_block_param(block0, types::I32);
+        let index64 = pos.ins().uextend(types::I64, index);
+        let addr = pos.ins().iadd(base, index64);
+        let loaded = pos
+            .ins()
+            .load(types::I8, MemFlags::new().with_checked(), addr, 0);
+        pos.ins().return_(&[loaded]);
+
+        let mem = |max_offset| Fact::Mem {
+            ty: mt,
+            min_offset: 0,
+            max_offset,
+            nullable: false,
+        };
+        let range = |bit_width| Fact::Range {
+            bit_width,
+            min: 0,
+            max: 0xffff_ffff,
+        };
+        func.dfg.facts[base] = Some(mem(0));
+        func.dfg.facts[index] = Some(range(32));
+        func.dfg.facts[index64] = Some(range(64));
+        func.dfg.facts[addr] = Some(mem(0xffff_ffff));
+        func
+    }
+
+    #[test]
+    fn pcc_report_lists_proven_accesses() {
+        let func = bounds_checked_load();
+        let cfg = ControlFlowGraph::with_function(&func);
+        let domtree = DominatorTree::with_function(&func, &cfg);
+
+        let mut flag_builder = shared_settings::builder();
+        flag_builder.set("enable_pcc", "true").unwrap();
+        flag_builder.set("pcc_report", "true").unwrap();
+        let flags = shared_settings::Flags::new(flag_builder);
+        let isa_flags = aarch64_settings::Flags::new(&flags, &aarch64_settings::builder());
+        let backend = AArch64Backend::new_with_flags(
+            "aarch64".parse().unwrap(),
+            flags.clone(),
+            isa_flags.clone(),
+        );
+
+        let output = backend
+            .compile_vcode(&func, &domtree, &mut Default::default(), &mut |_| {})
+            .unwrap();
+        let report = output.report.pcc_report.expect("`pcc_report` is enabled");
+        let proven: Vec<_> = report
+            .accesses
+            .iter()
+            .filter(|(_, verdict)| *verdict == AccessVerdict::Proven)
+            .map(|(inst, _)| *inst)
+            .collect();
+        assert_eq!(proven.len(), 1, "{report:?}");
+        assert!(output.vcode[proven[0]].is_mem_access());
+
+        // Embedders see the same report on the compiled code.
+        let compiled = backend
+            .compile_function(&func, &domtree, false, &mut Default::default())
+            .unwrap();
+        assert_eq!(compiled.report.pcc_report, Some(report));
+    }
+}
@@ -418,6 +418,7 @@ fn check_addr<'a>(
     op: LoadOrStore<'a>,
 ) -> PccResult<()> {
     if !flags.checked() {
+        ctx.record_access(AccessVerdict::Trusted);
         return Ok(());
     }
 
@@ -442,7 +443,9 @@ fn check_addr<'a>(
                 }
             }
             LoadOrStore::Store { stored_fact } => ctx.store(addr, ty, stored_fact),
-        }
+        }?;
+        ctx.record_access(AccessVerdict::Proven);
+        Ok(())
     };
 
     match addr {
@@ -499,6 +502,7 @@ fn check_addr<'a>(
         &AMode::Label { .. } | &AMode::Const { .. } => {
             // Always accept: labels and constants must be within the
             // generated code (else they won't be resolved).
+            ctx.record_access(AccessVerdict::Trusted);
             Ok(())
         }
         &AMode::RegOffset { rn, off, .. } => {
@@ -514,6 +518,7 @@ fn check_addr<'a>(
         | &AMode::SPPreIndexed { .. } => {
             // We trust ABI code (for now!) and no lowering rules
             // lower input value accesses directly to these.
+            ctx.record_access(AccessVerdict::Trusted);
             Ok(())
         }
     }
@@ -547,10 +552,12 @@ fn check_load_addr(
     ty: Type,
 ) -> PccResult<()> {
     if !flags.checked() {
+        ctx.record_access(AccessVerdict::Trusted);
         return Ok(());
     }
     let fact = get_fact_or_default(vcode, reg, 64);
     let _output_fact = ctx.load(&fact, ty)?;
+    ctx.record_access(AccessVerdict::Proven);
     Ok(())
 }
 
@@ -562,9 +569,11 @@ fn check_store_addr(
     ty: Type,
 ) -> PccResult<()> {
     if !flags.checked() {
+        ctx.record_access(AccessVerdict::Trusted);
         return Ok(());
     }
     let fact = get_fact_or_default(vcode, reg, 64);
     let _output_fact = ctx.store(&fact, ty, None)?;
+    ctx.record_access(AccessVerdict::Proven);
     Ok(())
 }
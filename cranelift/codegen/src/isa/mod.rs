@@ -46,14 +46,14 @@
 use crate::dominator_tree::DominatorTree;
 pub use crate::isa::call_conv::CallConv;
 
-use crate::CodegenResult;
 use crate::ir::{self, Function, Type};
 #[cfg(feature = "unwind")]
 use crate::isa::unwind::{UnwindInfoKind, systemv::RegisterMappingError};
-use crate::machinst::{CompiledCode, CompiledCodeStencil, TextSectionBuilder};
+use crate::machinst::{CompiledCode, CompiledCodeStencil, LoweredVCode, TextSectionBuilder};
 use crate::settings;
 use crate::settings::Configurable;
 use crate::settings::SetResult;
+use crate::{CodegenError, CodegenResult};
 use crate::{Reg, flowgraph};
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::fmt;
@@ -314,6 +314,41 @@ pub trait TargetIsa: fmt::Display + Send + Sync {
         ctrl_plane: &mut ControlPlane,
     ) -> CodegenResult<CompiledCodeStencil>;
 
+    /// Lower the given function to VCode without allocating registers, e.g.
+    /// for tooling which only inspects instruction selection.
+    ///
+    /// By default this is unsupported.
+    fn lower_function(
+        &self,
+        _func: &Function,
+        _domtree: &DominatorTree,
+        _ctrl_plane: &mut ControlPlane,
+    ) -> CodegenResult<Box<dyn LoweredVCode>> {
+        Err(CodegenError::Unsupported(format!(
+            "lowering without register allocation for {}",
+            self.name()
+        )))
+    }
+
+    /// Compile the given function, invoking `pre_regalloc` with its lowered
+    /// VCode right before register allocation, e.g. to measure register
+    /// pressure.
+    ///
+    /// By default this is unsupported.
+    fn compile_function_with_pre_regalloc_hook(
+        &self,
+        _func: &Function,
+        _domtree: &DominatorTree,
+        _want_disasm: bool,
+        _ctrl_plane: &mut ControlPlane,
+        _pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompiledCodeStencil> {
+        Err(CodegenError::Unsupported(format!(
+            "pre-regalloc hook for {}",
+            self.name()
+        )))
+    }
+
     #[cfg(feature = "unwind")]
     /// Map a regalloc::Reg to its corresponding DWARF register.
     fn map_regalloc_reg_to_dwarf(
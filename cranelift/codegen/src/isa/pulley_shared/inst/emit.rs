@@ -10,6 +10,7 @@ use cranelift_control::ControlPlane;
 use pulley_interpreter::encode as enc;
 use pulley_interpreter::regs::BinaryOperands;
 
+#[derive(Clone)]
 pub struct EmitInfo {
     call_conv: isa::CallConv,
     shared_flags: settings::Flags,
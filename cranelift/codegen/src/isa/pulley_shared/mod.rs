@@ -12,7 +12,9 @@ use crate::{
     dominator_tree::DominatorTree,
     ir,
     isa::{self, IsaFlagsHashKey, OwnedTargetIsa, TargetIsa},
-    machinst::{self, CompiledCodeStencil, MachInst, SigSet, VCode},
+    machinst::{
+        self, CompileOptions, CompileOutput, CompiledCodeStencil, LoweredVCode, MachInst, SigSet,
+    },
     result::CodegenResult,
     settings::{self as shared_settings, Flags},
 };
@@ -123,7 +125,8 @@ where
         func: &ir::Function,
         domtree: &DominatorTree,
         ctrl_plane: &mut ControlPlane,
-    ) -> CodegenResult<(VCode<inst::InstAndKind<P>>, regalloc2::Output)> {
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompileOutput<inst::InstAndKind<P>>> {
         let emit_info = EmitInfo::new(
             func.signature.call_conv,
             self.flags.clone(),
@@ -131,7 +134,13 @@ where
         );
         let sigs = SigSet::new::<abi::PulleyMachineDeps<P>>(func, &self.flags)?;
         let abi = abi::PulleyCallee::new(func, self, &self.isa_flags, &sigs)?;
-        machinst::compile::<Self>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)
+        let options = CompileOptions {
+            pre_regalloc: Some(&mut |vcode| pre_regalloc(vcode)),
+            ..Default::default()
+        };
+        machinst::compile_with_options::<Self>(
+            func, domtree, self, abi, emit_info, sigs, ctrl_plane, options,
+        )
     }
 }
 
@@ -175,7 +184,28 @@ where
         want_disasm: bool,
         ctrl_plane: &mut cranelift_control::ControlPlane,
     ) -> CodegenResult<CompiledCodeStencil> {
-        let (vcode, regalloc_result) = self.compile_vcode(func, domtree, ctrl_plane)?;
+        self.compile_function_with_pre_regalloc_hook(
+            func,
+            domtree,
+            want_disasm,
+            ctrl_plane,
+            &mut |_| {},
+        )
+    }
+
+    fn compile_function_with_pre_regalloc_hook(
+        &self,
+        func: &ir::Function,
+        domtree: &DominatorTree,
+        want_disasm: bool,
+        ctrl_plane: &mut cranelift_control::ControlPlane,
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompiledCodeStencil> {
+        let CompileOutput {
+            vcode,
+            regalloc_result,
+            report,
+        } = self.compile_vcode(func, domtree, ctrl_plane, pre_regalloc)?;
 
         let want_disasm =
             want_disasm || (cfg!(feature = "trace-log") && log::log_enabled!(log::Level::Debug));
@@ -199,9 +229,28 @@ where
             dynamic_stackslot_offsets,
             bb_starts: emit_result.bb_offsets,
             bb_edges: emit_result.bb_edges,
+            report,
         })
     }
 
+    fn lower_function(
+        &self,
+        func: &ir::Function,
+        domtree: &DominatorTree,
+        ctrl_plane: &mut cranelift_control::ControlPlane,
+    ) -> CodegenResult<Box<dyn LoweredVCode>> {
+        let emit_info = EmitInfo::new(
+            func.signature.call_conv,
+            self.flags.clone(),
+            self.isa_flags.clone(),
+        );
+        let sigs = SigSet::new::<abi::PulleyMachineDeps<P>>(func, &self.flags)?;
+        let abi = abi::PulleyCallee::new(func, self, &self.isa_flags, &sigs)?;
+        let vcode =
+            machinst::lower_only::<Self>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)?;
+        Ok(Box::new(vcode))
+    }
+
     fn emit_unwind_info(
         &self,
         _result: &crate::CompiledCode,
@@ -7,6 +7,7 @@ use crate::isa::riscv64::lower::isle::generated_code::{
 };
 use cranelift_control::ControlPlane;
 
+#[derive(Clone)]
 pub struct EmitInfo {
     #[expect(dead_code, reason = "may want to be used in the future")]
     shared_flag: settings::Flags,
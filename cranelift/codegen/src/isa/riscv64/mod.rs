@@ -7,8 +7,8 @@ use crate::isa::{
     Builder as IsaBuilder, FunctionAlignment, IsaFlagsHashKey, OwnedTargetIsa, TargetIsa,
 };
 use crate::machinst::{
-    CompiledCode, CompiledCodeStencil, MachInst, MachTextSectionBuilder, Reg, SigSet,
-    TextSectionBuilder, VCode, compile,
+    CompileOptions, CompileOutput, CompiledCode, CompiledCodeStencil, LoweredVCode, MachInst,
+    MachTextSectionBuilder, Reg, SigSet, TextSectionBuilder, compile,
 };
 use crate::result::CodegenResult;
 use crate::settings::{self as shared_settings, Flags};
@@ -55,11 +55,18 @@ impl Riscv64Backend {
         func: &Function,
         domtree: &DominatorTree,
         ctrl_plane: &mut ControlPlane,
-    ) -> CodegenResult<(VCode<inst::Inst>, regalloc2::Output)> {
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompileOutput<inst::Inst>> {
         let emit_info = EmitInfo::new(self.flags.clone(), self.isa_flags.clone());
         let sigs = SigSet::new::<abi::Riscv64MachineDeps>(func, &self.flags)?;
         let abi = abi::Riscv64Callee::new(func, self, &self.isa_flags, &sigs)?;
-        compile::compile::<Riscv64Backend>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)
+        let options = CompileOptions {
+            pre_regalloc: Some(&mut |vcode| pre_regalloc(vcode)),
+            ..Default::default()
+        };
+        compile::compile_with_options::<Riscv64Backend>(
+            func, domtree, self, abi, emit_info, sigs, ctrl_plane, options,
+        )
     }
 }
 
@@ -71,7 +78,28 @@ impl TargetIsa for Riscv64Backend {
         want_disasm: bool,
         ctrl_plane: &mut ControlPlane,
     ) -> CodegenResult<CompiledCodeStencil> {
-        let (vcode, regalloc_result) = self.compile_vcode(func, domtree, ctrl_plane)?;
+        self.compile_function_with_pre_regalloc_hook(
+            func,
+            domtree,
+            want_disasm,
+            ctrl_plane,
+            &mut |_| {},
+        )
+    }
+
+    fn compile_function_with_pre_regalloc_hook(
+        &self,
+        func: &Function,
+        domtree: &DominatorTree,
+        want_disasm: bool,
+        ctrl_plane: &mut ControlPlane,
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompiledCodeStencil> {
+        let CompileOutput {
+            vcode,
+            regalloc_result,
+            report,
+        } = self.compile_vcode(func, domtree, ctrl_plane, pre_regalloc)?;
 
         let want_disasm = want_disasm || log::log_enabled!(log::Level::Debug);
         let emit_result = vcode.emit(&regalloc_result, want_disasm, &self.flags, ctrl_plane);
@@ -94,9 +122,24 @@ impl TargetIsa for Riscv64Backend {
             dynamic_stackslot_offsets,
             bb_starts: emit_result.bb_offsets,
             bb_edges: emit_result.bb_edges,
+            report,
         })
     }
 
+    fn lower_function(
+        &self,
+        func: &Function,
+        domtree: &DominatorTree,
+        ctrl_plane: &mut ControlPlane,
+    ) -> CodegenResult<Box<dyn LoweredVCode>> {
+        let emit_info = EmitInfo::new(self.flags.clone(), self.isa_flags.clone());
+        let sigs = SigSet::new::<abi::Riscv64MachineDeps>(func, &self.flags)?;
+        let abi = abi::Riscv64Callee::new(func, self, &self.isa_flags, &sigs)?;
+        let vcode =
+            compile::lower_only::<Self>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)?;
+        Ok(Box::new(vcode))
+    }
+
     fn name(&self) -> &'static str {
         "riscv64"
     }
@@ -1400,6 +1400,7 @@ impl EmitState {
 }
 
 /// Constant state used during function compilation.
+#[derive(Clone)]
 pub struct EmitInfo {
     isa_flags: s390x_settings::Flags,
 }
@@ -7,8 +7,8 @@ use crate::isa::s390x::settings as s390x_settings;
 use crate::isa::unwind::systemv::RegisterMappingError;
 use crate::isa::{Builder as IsaBuilder, FunctionAlignment, IsaFlagsHashKey, TargetIsa};
 use crate::machinst::{
-    CompiledCode, CompiledCodeStencil, MachInst, MachTextSectionBuilder, Reg, SigSet,
-    TextSectionBuilder, VCode, compile,
+    CompileOptions, CompileOutput, CompiledCode, CompiledCodeStencil, LoweredVCode, MachInst,
+    MachTextSectionBuilder, Reg, SigSet, TextSectionBuilder, compile,
 };
 use crate::result::CodegenResult;
 use crate::settings as shared_settings;
@@ -54,11 +54,18 @@ impl S390xBackend {
         func: &Function,
         domtree: &DominatorTree,
         ctrl_plane: &mut ControlPlane,
-    ) -> CodegenResult<(VCode<inst::Inst>, regalloc2::Output)> {
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompileOutput<inst::Inst>> {
         let emit_info = EmitInfo::new(self.isa_flags.clone());
         let sigs = SigSet::new::<abi::S390xMachineDeps>(func, &self.flags)?;
         let abi = abi::S390xCallee::new(func, self, &self.isa_flags, &sigs)?;
-        compile::compile::<S390xBackend>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)
+        let options = CompileOptions {
+            pre_regalloc: Some(&mut |vcode| pre_regalloc(vcode)),
+            ..Default::default()
+        };
+        compile::compile_with_options::<S390xBackend>(
+            func, domtree, self, abi, emit_info, sigs, ctrl_plane, options,
+        )
     }
 }
 
@@ -69,9 +76,30 @@ impl TargetIsa for S390xBackend {
         domtree: &DominatorTree,
         want_disasm: bool,
         ctrl_plane: &mut ControlPlane,
+    ) -> CodegenResult<CompiledCodeStencil> {
+        self.compile_function_with_pre_regalloc_hook(
+            func,
+            domtree,
+            want_disasm,
+            ctrl_plane,
+            &mut |_| {},
+        )
+    }
+
+    fn compile_function_with_pre_regalloc_hook(
+        &self,
+        func: &Function,
+        domtree: &DominatorTree,
+        want_disasm: bool,
+        ctrl_plane: &mut ControlPlane,
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
     ) -> CodegenResult<CompiledCodeStencil> {
         let flags = self.flags();
-        let (vcode, regalloc_result) = self.compile_vcode(func, domtree, ctrl_plane)?;
+        let CompileOutput {
+            vcode,
+            regalloc_result,
+            report,
+        } = self.compile_vcode(func, domtree, ctrl_plane, pre_regalloc)?;
 
         let emit_result = vcode.emit(&regalloc_result, want_disasm, flags, ctrl_plane);
         let frame_size = emit_result.frame_size;
@@ -93,9 +121,24 @@ impl TargetIsa for S390xBackend {
             dynamic_stackslot_offsets,
             bb_starts: emit_result.bb_offsets,
             bb_edges: emit_result.bb_edges,
+            report,
         })
     }
 
+    fn lower_function(
+        &self,
+        func: &Function,
+        domtree: &DominatorTree,
+        ctrl_plane: &mut ControlPlane,
+    ) -> CodegenResult<Box<dyn LoweredVCode>> {
+        let emit_info = EmitInfo::new(self.isa_flags.clone());
+        let sigs = SigSet::new::<abi::S390xMachineDeps>(func, &self.flags)?;
+        let abi = abi::S390xCallee::new(func, self, &self.isa_flags, &sigs)?;
+        let vcode =
+            compile::lower_only::<Self>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)?;
+        Ok(Box::new(vcode))
+    }
+
     fn name(&self) -> &'static str {
         "s390x"
     }
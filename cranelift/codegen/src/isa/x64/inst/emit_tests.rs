@@ -65,7 +65,7 @@ fn test_x64_emit() {
     let _w_r9 = Writable::<Reg>::from_reg(r9);
     let w_r11 = Writable::<Reg>::from_reg(r11);
     let _w_r14 = Writable::<Reg>::from_reg(r14);
-    let _w_r15 = Writable::<Reg>::from_reg(r15);
+    let w_r15 = Writable::<Reg>::from_reg(r15);
 
     let _w_xmm0 = Writable::<Reg>::from_reg(xmm0);
     let _w_xmm1 = Writable::<Reg>::from_reg(xmm1);
@@ -130,6 +130,20 @@ fn test_x64_emit() {
         "call    *321(%r10,%rdx,4)",
     ));
 
+    // ========================================================
+    // Imm: the `+rd id` and `+ro io` forms fold the destination register into
+    // the opcode byte, emit no ModR/M byte, and end with the immediate.
+    insns.push((
+        Inst::imm(OperandSize::Size32, 0x1234_5678, w_rax),
+        "B878563412",
+        "movl $0x12345678, %eax",
+    ));
+    insns.push((
+        Inst::imm(OperandSize::Size64, 0x1122_3344_5566_7788, w_r15),
+        "49BF8877665544332211",
+        "movabsq $0x1122334455667788, %r15",
+    ));
+
     // ========================================================
     // LoadExtName
     // N.B.: test harness below sets is_pic.
@@ -4,7 +4,8 @@ use super::{
     Amode, Gpr, Inst, LabelUse, MachBuffer, MachLabel, OperandVisitor, OperandVisitorImpl,
     SyntheticAmode, VCodeConstant, WritableGpr, WritableXmm, Xmm, args::FromWritableReg,
 };
-use crate::{Reg, Writable, ir::TrapCode};
+use crate::ir::{ExternalName, TrapCode, UserExternalNameRef};
+use crate::{Reg, Writable, binemit::Reloc};
 use cranelift_assembler_x64 as asm;
 use regalloc2::{PReg, RegClass};
 use std::string::String;
@@ -456,6 +457,10 @@ impl asm::CodeSink for AsmCodeSink<'_> {
         self.sink.put8(value)
     }
 
+    fn put_data(&mut self, data: &[u8]) {
+        self.sink.put_data(data)
+    }
+
     fn add_trap(&mut self, code: asm::TrapCode) {
         self.sink.add_trap(code.into());
     }
@@ -472,6 +477,9 @@ impl asm::CodeSink for AsmCodeSink<'_> {
                 self.sink
                     .use_label_at_offset(offset, label, LabelUse::JmpRel32);
             }
+            asm::DeferredTarget::Symbol(symbol) => {
+                unreachable!("{symbol:?} is relocated through `add_reloc`")
+            }
             asm::DeferredTarget::None => {}
         }
     }
@@ -483,6 +491,15 @@ impl asm::CodeSink for AsmCodeSink<'_> {
             other => panic!("unknown \"known\" offset {other}"),
         }
     }
+
+    fn add_reloc(&mut self, kind: asm::RelocKind, symbol: asm::Symbol, addend: i32) {
+        let kind = match kind {
+            asm::RelocKind::PcRel4 => Reloc::X86PCRel4,
+            asm::RelocKind::CallPcRel4 => Reloc::X86CallPCRel4,
+        };
+        let name = ExternalName::from(symbol);
+        self.sink.add_reloc(kind, &name, addend.into());
+    }
 }
 
 impl From<asm::TrapCode> for TrapCode {
@@ -515,6 +532,18 @@ impl From<asm::Constant> for VCodeConstant {
     }
 }
 
+impl From<asm::Symbol> for ExternalName {
+    fn from(value: asm::Symbol) -> Self {
+        Self::User(UserExternalNameRef::from_u32(value.0))
+    }
+}
+
+impl From<UserExternalNameRef> for asm::Symbol {
+    fn from(value: UserExternalNameRef) -> Self {
+        Self(value.as_u32())
+    }
+}
+
 // Include code generated by `cranelift-codegen/meta/src/gen_asm.rs`. This file
 // contains a `isle_assembler_methods!` macro with Rust implementations of all
 // the assembler instructions exposed to ISLE.
@@ -530,6 +559,54 @@ mod tests {
     use crate::{Reg, Writable};
     use regalloc2::{RegClass, VReg};
 
+    #[test]
+    fn symbolic_relocations() {
+        use super::{AsmCodeSink, CraneliftRegisters, MachBuffer, asm};
+        use crate::binemit::Reloc;
+        use crate::ir::{ExternalName, UserExternalNameRef};
+        use crate::machinst::{FinalizedRelocTarget, VCodeConstants};
+        use std::vec::Vec;
+
+        // `callq sym`, then `movq sym(%rip), %rax`.
+        let name = UserExternalNameRef::from_u32(7);
+        let mut call = asm::inst::callq_d::new(0);
+        call.symbol = Some(name.into());
+        let rax = Gpr::unwrap_new(crate::isa::x64::inst::regs::rax());
+        let riprel = asm::Amode::RipRelative {
+            target: asm::DeferredTarget::Symbol(name.into()),
+        };
+        let load: asm::inst::movq_rm<CraneliftRegisters> =
+            asm::inst::movq_rm::new(WritableGpr::from_reg(rax), riprel);
+
+        let mut buffer = MachBuffer::new();
+        let mut sink = AsmCodeSink {
+            sink: &mut buffer,
+            incoming_arg_offset: 0,
+            slot_offset: 0,
+        };
+        call.encode(&mut sink);
+        load.encode(&mut sink);
+        let buffer = buffer.finish(&VCodeConstants::default(), &mut Default::default());
+
+        assert_eq!(
+            buffer.data(),
+            [0xe8, 0, 0, 0, 0, 0x48, 0x8b, 0x05, 0, 0, 0, 0]
+        );
+        let relocs: Vec<_> = buffer
+            .relocs()
+            .iter()
+            .map(|r| (r.offset, r.kind, r.target.clone(), r.addend))
+            .collect();
+        let target = FinalizedRelocTarget::ExternalName(ExternalName::User(name));
+        assert_eq!(
+            relocs,
+            [
+                (1, Reloc::X86CallPCRel4, target.clone(), -4),
+                (8, Reloc::X86PCRel4, target, -4),
+            ]
+        );
+    }
+
     #[test]
     fn pretty_print_registers() {
         // For logging, we need to be able to pretty-print the virtual registers
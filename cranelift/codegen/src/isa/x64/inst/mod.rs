@@ -1460,6 +1460,7 @@ impl MachInst for Inst {
 }
 
 /// Constant state used during emissions of a sequence of instructions.
+#[derive(Clone)]
 pub struct EmitInfo {
     pub(super) flags: settings::Flags,
     isa_flags: x64_settings::Flags,
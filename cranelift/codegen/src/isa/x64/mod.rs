@@ -10,8 +10,8 @@ use crate::isa::unwind::systemv;
 use crate::isa::x64::settings as x64_settings;
 use crate::isa::{Builder as IsaBuilder, FunctionAlignment, IsaFlagsHashKey};
 use crate::machinst::{
-    CompiledCode, CompiledCodeStencil, MachInst, MachTextSectionBuilder, Reg, SigSet,
-    TextSectionBuilder, VCode, compile,
+    CompileOptions, CompileOutput, CompiledCode, CompiledCodeStencil, LoweredVCode, MachInst,
+    MachTextSectionBuilder, Reg, SigSet, TextSectionBuilder, compile,
 };
 use crate::result::CodegenResult;
 use crate::settings::{self as shared_settings, Flags};
@@ -52,13 +52,20 @@ impl X64Backend {
         func: &Function,
         domtree: &DominatorTree,
         ctrl_plane: &mut ControlPlane,
-    ) -> CodegenResult<(VCode<inst::Inst>, regalloc2::Output)> {
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompileOutput<inst::Inst>> {
         // This performs lowering to VCode, register-allocates the code, computes
         // block layout and finalizes branches. The result is ready for binary emission.
         let emit_info = EmitInfo::new(self.flags.clone(), self.x64_flags.clone());
         let sigs = SigSet::new::<abi::X64ABIMachineSpec>(func, &self.flags)?;
         let abi = abi::X64Callee::new(func, self, &self.x64_flags, &sigs)?;
-        compile::compile::<Self>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)
+        let options = CompileOptions {
+            pre_regalloc: Some(&mut |vcode| pre_regalloc(vcode)),
+            ..Default::default()
+        };
+        compile::compile_with_options::<Self>(
+            func, domtree, self, abi, emit_info, sigs, ctrl_plane, options,
+        )
     }
 }
 
@@ -70,7 +77,28 @@ impl TargetIsa for X64Backend {
         want_disasm: bool,
         ctrl_plane: &mut ControlPlane,
     ) -> CodegenResult<CompiledCodeStencil> {
-        let (vcode, regalloc_result) = self.compile_vcode(func, domtree, ctrl_plane)?;
+        self.compile_function_with_pre_regalloc_hook(
+            func,
+            domtree,
+            want_disasm,
+            ctrl_plane,
+            &mut |_| {},
+        )
+    }
+
+    fn compile_function_with_pre_regalloc_hook(
+        &self,
+        func: &Function,
+        domtree: &DominatorTree,
+        want_disasm: bool,
+        ctrl_plane: &mut ControlPlane,
+        pre_regalloc: &mut dyn FnMut(&dyn LoweredVCode),
+    ) -> CodegenResult<CompiledCodeStencil> {
+        let CompileOutput {
+            vcode,
+            regalloc_result,
+            report,
+        } = self.compile_vcode(func, domtree, ctrl_plane, pre_regalloc)?;
 
         let emit_result = vcode.emit(&regalloc_result, want_disasm, &self.flags, ctrl_plane);
         let frame_size = emit_result.frame_size;
@@ -92,9 +120,24 @@ impl TargetIsa for X64Backend {
             dynamic_stackslot_offsets,
             bb_starts: emit_result.bb_offsets,
             bb_edges: emit_result.bb_edges,
+            report,
         })
     }
 
+    fn lower_function(
+        &self,
+        func: &Function,
+        domtree: &DominatorTree,
+        ctrl_plane: &mut ControlPlane,
+    ) -> CodegenResult<Box<dyn LoweredVCode>> {
+        let emit_info = EmitInfo::new(self.flags.clone(), self.x64_flags.clone());
+        let sigs = SigSet::new::<abi::X64ABIMachineSpec>(func, &self.flags)?;
+        let abi = abi::X64Callee::new(func, self, &self.x64_flags, &sigs)?;
+        let vcode =
+            compile::lower_only::<Self>(func, domtree, self, abi, emit_info, sigs, ctrl_plane)?;
+        Ok(Box::new(vcode))
+    }
+
     fn flags(&self) -> &Flags {
         &self.flags
     }
@@ -260,3 +303,648 @@ fn isa_constructor(
     let backend = X64Backend::new_with_flags(triple, shared_flags, isa_flags);
     Ok(backend.wrapped())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CodegenError;
+    use crate::cursor::{Cursor, FuncCursor};
+    use crate::flowgraph::ControlFlowGraph;
+    use crate::ir::{AbiParam, InstBuilder, Signature, UserFuncName};
+    use crate::isa::CallConv;
+    use crate::machinst::{RegallocStats, VCode};
+    use crate::settings::Configurable;
+
+    /// Build `fn add(i64, i64) -> i64`.
+    fn add_function() -> Function {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(types::I64));
+        sig.params.push(AbiParam::new(types::I64));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(UserFuncName::testcase("add"), sig);
+        let block0 = func.dfg.make_block();
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        let v0 = pos.func.dfg.append_block_param(block0, types::I64);
+        let v1 = pos.func.dfg.append_block_param(block0, types::I64);
+        let v2 = pos.ins().iadd(v0, v1);
+        pos.ins().return_(&[v2]);
+        func
+    }
+
+    /// Build a function that keeps more values live at once than there are
+    /// registers, forcing the register allocator to spill.
+    fn pressure_function() -> Function {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(types::I64));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(UserFuncName::testcase("pressure"), sig);
+        let block0 = func.dfg.make_block();
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        let v0 = pos.func.dfg.append_block_param(block0, types::I64);
+        let live: Vec<_> = (1..=32_i64)
+            .map(|i| {
+                let imm = pos.ins().iconst(types::I64, i);
+                pos.ins().iadd(v0, imm)
+            })
+            .collect();
+        let sum = live.iter().fold(v0, |acc, &v| pos.ins().imul(acc, v));
+        let sum = live.iter().fold(sum, |acc, &v| pos.ins().iadd(acc, v));
+        pos.ins().return_(&[sum]);
+        func
+    }
+
+    /// Build a function with a loop whose body keeps more values live at once
+    /// than there are registers, returning it and the loop's block.
+    fn pressure_loop_function() -> (Function, ir::Block) {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(types::I64));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(UserFuncName::testcase("loop"), sig);
+        let (entry, body, exit) = (
+            func.dfg.make_block(),
+            func.dfg.make_block(),
+            func.dfg.make_block(),
+        );
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(entry);
+        let v0 = pos.func.dfg.append_block_param(entry, types::I64);
+        pos.ins().jump(body, &[v0.into(), v0.into()]);
+
+        pos.insert_block(body);
+        let acc = pos.func.dfg.append_block_param(body, types::I64);
+        let i = pos.func.dfg.append_block_param(body, types::I64);
+        let live: Vec<_> = (1..=32_i64)
+            .map(|n| {
+                let imm = pos.ins().iconst(types::I64, n);
+                pos.ins().iadd(acc, imm)
+            })
+            .collect();
+        let sum = live.iter().fold(acc, |acc, &v| pos.ins().imul(acc, v));
+        let sum = live.iter().fold(sum, |acc, &v| pos.ins().iadd(acc, v));
+        let one = pos.ins().iconst(types::I64, 1);
+        let i = pos.ins().isub(i, one);
+        pos.ins()
+            .brif(i, body, &[sum.into(), i.into()], exit, &[sum.into()]);
+
+        pos.insert_block(exit);
+        let result = pos.func.dfg.append_block_param(exit, types::I64);
+        pos.ins().return_(&[result]);
+        (func, body)
+    }
+
+    /// Build `fn load(i64) -> i64`, which loads through its argument with the
+    /// given `flags`.
+    fn load_function(flags: ir::MemFlags) -> Function {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(types::I64));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(UserFuncName::testcase("load"), sig);
+        let block0 = func.dfg.make_block();
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        let v0 = pos.func.dfg.append_block_param(block0, types::I64);
+        let v1 = pos.ins().load(types::I64, flags, v0, 0);
+        pos.ins().return_(&[v1]);
+        func
+    }
+
+    /// Run the backend pipeline on `func` with the given shared settings,
+    /// calling `pre_regalloc` just before register allocation.
+    fn compile_with(
+        func: &Function,
+        settings: &[(&str, &str)],
+        pre_regalloc: &mut dyn FnMut(&VCode<Inst>),
+    ) -> CompileOutput<Inst> {
+        let (domtree, backend, emit_info, sigs, callee) = compile_inputs(func, settings);
+        let options = compile::CompileOptions {
+            pre_regalloc: Some(pre_regalloc),
+            ..Default::default()
+        };
+        compile::compile_with_options::<X64Backend>(
+            func,
+            &domtree,
+            &backend,
+            callee,
+            emit_info,
+            sigs,
+            &mut Default::default(),
+            options,
+        )
+        .unwrap()
+    }
+
+    /// Build everything the backend pipeline needs to compile `func` with the
+    /// given shared settings.
+    fn compile_inputs(
+        func: &Function,
+        settings: &[(&str, &str)],
+    ) -> (DominatorTree, X64Backend, EmitInfo, SigSet, abi::X64Callee) {
+        let cfg = ControlFlowGraph::with_function(func);
+        let domtree = DominatorTree::with_function(func, &cfg);
+
+        let mut flag_builder = shared_settings::builder();
+        for (name, value) in settings {
+            flag_builder.set(name, value).unwrap();
+        }
+        let flags = Flags::new(flag_builder);
+        let x64_flags = x64_settings::Flags::new(&flags, &x64_settings::builder());
+        let backend =
+            X64Backend::new_with_flags("x86_64".parse().unwrap(), flags.clone(), x64_flags.clone());
+
+        let emit_info = EmitInfo::new(flags.clone(), x64_flags.clone());
+        let sigs = SigSet::new::<abi::X64ABIMachineSpec>(func, &flags).unwrap();
+        let callee = abi::X64Callee::new(func, &backend, &x64_flags, &sigs).unwrap();
+        (domtree, backend, emit_info, sigs, callee)
+    }
+
+    #[test]
+    fn pre_regalloc_hook_observes_lowered_vcode() {
+        let func = add_function();
+        let mut observed = None;
+        let vcode = compile_with(&func, &[], &mut |vcode| {
+            observed = Some(vcode.num_insts());
+        })
+        .vcode;
+
+        // Register allocation records its edits separately rather than
+        // rewriting the VCode, so the hook sees the same instructions.
+        assert!(vcode.num_insts() > 0);
+        assert_eq!(observed, Some(vcode.num_insts()));
+    }
+
+    #[test]
+    fn context_pre_regalloc_hook() {
+        let isa = crate::isa::lookup("x86_64".parse().unwrap())
+            .unwrap()
+            .finish(Flags::new(shared_settings::builder()))
+            .unwrap();
+        let mut ctx = crate::Context::for_function(add_function());
+        let mut observed = None;
+        let code = ctx
+            .compile_with_pre_regalloc_hook(&*isa, &mut Default::default(), &mut |vcode| {
+                observed = Some((vcode.num_insts(), vcode.num_blocks()));
+            })
+            .unwrap();
+        let stats = &code.report.stats;
+        assert_eq!(observed, Some((stats.vcode_insts, stats.vcode_blocks)));
+    }
+
+    #[test]
+    fn reuse_block_order() {
+        let mut func = add_function();
+        let vcode = compile_with(&func, &[], &mut |_| {}).vcode;
+        let block_order = vcode.block_order().clone();
+
+        // Edit an instruction without changing the control flow graph.
+        let block0 = func.layout.entry_block().unwrap();
+        let iadd = func.layout.first_inst(block0).unwrap();
+        let [x, y] = *func.dfg.inst_args(iadd) else {
+            unreachable!()
+        };
+        func.dfg.replace(iadd).isub(x, y);
+
+        let expected = compile_with(&func, &[], &mut |_| {});
+        let (domtree, backend, emit_info, sigs, callee) = compile_inputs(&func, &[]);
+        let options = compile::CompileOptions {
+            block_order: Some(block_order),
+            ..Default::default()
+        };
+        let output = compile::compile_with_options::<X64Backend>(
+            &func,
+            &domtree,
+            &backend,
+            callee,
+            emit_info,
+            sigs,
+            &mut Default::default(),
+            options,
+        )
+        .unwrap();
+        assert!(format!("{:?}", expected.vcode).contains("sub"));
+        assert_eq!(
+            format!("{:?}", output.vcode),
+            format!("{:?}", expected.vcode)
+        );
+        assert_eq!(
+            format!("{:?}", output.regalloc_result),
+            format!("{:?}", expected.regalloc_result)
+        );
+    }
+
+    #[test]
+    fn lowered_block_order() {
+        let (func, body) = pressure_loop_function();
+        let output = compile_with(&func, &[], &mut |_| {});
+        let order = output.vcode.block_order().lowered_order();
+        assert_eq!(order.len(), output.vcode.num_blocks());
+        assert_eq!(order[0].orig_block(), func.layout.entry_block());
+        assert!(order.iter().any(|b| b.orig_block() == Some(body)));
+
+        // The same order is reported as CLIF blocks.
+        let lowered_blocks = output.report.stats.lowered_blocks;
+        assert!(
+            lowered_blocks
+                .iter()
+                .zip(order)
+                .all(|(&b, l)| b == l.orig_block())
+        );
+    }
+
+    #[test]
+    fn compile_phase_timing() {
+        let func = add_function();
+        let times = compile_with(&func, &[], &mut |_| {}).report.phase_times;
+        assert_eq!(times, None);
+
+        let settings = [
+            ("compile_phase_timing", "true"),
+            ("enable_pcc", "true"),
+            ("regalloc_checker", "true"),
+        ];
+        let times = compile_with(&func, &settings, &mut |_| {})
+            .report
+            .phase_times;
+        let times = times.unwrap();
+        assert!(!times.lowering.is_zero());
+        assert!(!times.pcc.is_zero());
+        assert!(!times.regalloc.is_zero());
+        assert!(!times.regalloc_checker.is_zero());
+    }
+
+    #[test]
+    fn regalloc_stats_count_spills() {
+        let output = compile_with(&add_function(), &[], &mut |_| {});
+        assert_eq!(
+            RegallocStats::from_output(&output.regalloc_result).spills,
+            0
+        );
+
+        let output = compile_with(&pressure_function(), &[], &mut |_| {});
+        let stats = RegallocStats::from_output(&output.regalloc_result);
+        assert!(stats.spills > 0, "{stats:?}");
+        assert!(stats.reloads > 0, "{stats:?}");
+        assert_eq!(output.report.stats.regalloc, stats);
+    }
+
+    #[test]
+    fn regalloc_block_stats() {
+        let (func, body) = pressure_loop_function();
+        let stats = compile_with(&func, &[], &mut |_| {}).report.stats;
+        assert_eq!(stats.regalloc_blocks, None);
+
+        let settings = [("regalloc_block_stats", "true")];
+        let CompileOutput {
+            vcode,
+            regalloc_result: output,
+            report,
+        } = compile_with(&func, &settings, &mut |_| {});
+        let blocks = report.stats.regalloc_blocks.unwrap();
+        assert_eq!(blocks.len(), vcode.num_blocks());
+        let edits = |s: &RegallocStats| s.spills + s.reloads + s.moves;
+        assert_eq!(blocks.iter().map(edits).sum::<usize>(), output.edits.len());
+
+        // The loop body incurs most of the moves.
+        let hottest = (0..blocks.len())
+            .max_by_key(|&i| edits(&blocks[i]))
+            .unwrap();
+        let lowered = &vcode.block_order().lowered_order()[hottest];
+        assert_eq!(lowered.orig_block(), Some(body), "{blocks:?}");
+    }
+
+    #[test]
+    fn compile_stats() {
+        let output = compile_with(&add_function(), &[], &mut |_| {});
+        let (vcode, stats) = (output.vcode, output.report.stats);
+        assert_eq!(stats.clif_insts, 2);
+        assert_eq!(stats.clif_blocks, 1);
+        assert_eq!(stats.vcode_insts, vcode.num_insts());
+        assert_eq!(stats.vcode_blocks, vcode.num_blocks());
+    }
+
+    #[test]
+    fn compile_report_reaches_compiled_code() {
+        let mut flag_builder = shared_settings::builder();
+        flag_builder.set("compile_phase_timing", "true").unwrap();
+        let isa = crate::isa::lookup("x86_64".parse().unwrap())
+            .unwrap()
+            .finish(Flags::new(flag_builder))
+            .unwrap();
+        let mut ctx = crate::Context::for_function(pressure_function());
+        let code = ctx.compile(&*isa, &mut Default::default()).unwrap();
+
+        let report = &code.report;
+        assert!(report.phase_times.is_some());
+        assert!(report.stats.regalloc.spills > 0, "{report:?}");
+        assert_eq!(report.stats.lowered_blocks.len(), report.stats.vcode_blocks);
+    }
+
+    #[test]
+    fn lower_only() {
+        let func = pressure_loop_function().0;
+        let (domtree, backend, emit_info, sigs, callee) = compile_inputs(&func, &[]);
+        let vcode = compile::lower_only::<X64Backend>(
+            &func,
+            &domtree,
+            &backend,
+            callee,
+            emit_info,
+            sigs,
+            &mut Default::default(),
+        )
+        .unwrap();
+        let stats = compile_with(&func, &[], &mut |_| {}).report.stats;
+        assert_eq!(vcode.num_insts(), stats.vcode_insts);
+        assert_eq!(vcode.num_blocks(), stats.vcode_blocks);
+    }
+
+    #[test]
+    fn context_lower_only() {
+        let isa = crate::isa::lookup("x86_64".parse().unwrap())
+            .unwrap()
+            .finish(Flags::new(shared_settings::builder()))
+            .unwrap();
+        let vcode = crate::Context::for_function(pressure_loop_function().0)
+            .lower_only(&*isa, &mut Default::default())
+            .unwrap();
+        let code = crate::Context::for_function(pressure_loop_function().0)
+            .compile(&*isa, &mut Default::default())
+            .unwrap()
+            .clone();
+        assert_eq!(vcode.num_insts(), code.report.stats.vcode_insts);
+        assert_eq!(vcode.num_blocks(), code.report.stats.vcode_blocks);
+    }
+
+    #[test]
+    fn compile_determinism_check() {
+        compile_with(
+            &pressure_function(),
+            &[("compile_determinism_check", "true")],
+            &mut |_| {},
+        );
+    }
+
+    #[test]
+    fn compile_determinism_check_detects_differences() {
+        let func = pressure_function();
+        let output = compile_with(&func, &[], &mut |_| {});
+        let first = format!("{:?}\n\n{:#?}\n", output.vcode, output.regalloc_result);
+        // Stand in for nondeterministic register allocation.
+        let second = first.replacen("%rax", "%rcx", 1);
+        match compile::check_deterministic(&func, &first, &second) {
+            Err(CodegenError::Nondeterministic(diff)) => {
+                assert!(diff.starts_with("%pressure:"), "{diff}");
+                assert!(diff.contains("%rcx"), "{diff}");
+            }
+            result => panic!("expected a nondeterminism error, got {result:?}"),
+        }
+        compile::check_deterministic(&func, &first, &first).unwrap();
+    }
+
+    #[test]
+    fn dump_vcode() {
+        let mut func = add_function();
+        func.name = UserFuncName::testcase("x64_dump_vcode_test");
+        let path = compile::vcode_dump_path(&func);
+        let _ = std::fs::remove_file(&path);
+
+        compile_with(&func, &[], &mut |_| {});
+        assert!(!path.exists());
+
+        compile_with(&func, &[("dump_vcode", "true")], &mut |_| {});
+        let dump = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(dump.contains("VCode {"), "{dump}");
+        assert!(dump.contains("Output {"), "{dump}");
+    }
+
+    #[test]
+    fn regalloc_validate_ssa() {
+        let flags = Flags::new(shared_settings::builder());
+        assert_eq!(
+            compile::regalloc_options(&flags).validate_ssa,
+            cfg!(debug_assertions)
+        );
+
+        let mut flag_builder = shared_settings::builder();
+        flag_builder.set("regalloc_validate_ssa", "true").unwrap();
+        let flags = Flags::new(flag_builder);
+        assert!(compile::regalloc_options(&flags).validate_ssa);
+
+        compile_with(
+            &add_function(),
+            &[("regalloc_validate_ssa", "true")],
+            &mut |_| {},
+        );
+    }
+
+    #[test]
+    fn regalloc_checks_disabled() {
+        let mut flag_builder = shared_settings::builder();
+        flag_builder.set("regalloc_validate_ssa", "true").unwrap();
+        flag_builder
+            .set("regalloc_checks_disabled", "true")
+            .unwrap();
+        let flags = Flags::new(flag_builder);
+        assert!(!compile::regalloc_options(&flags).validate_ssa);
+
+        // The checker's phase time stays zero because it never runs.
+        let settings = [
+            ("compile_phase_timing", "true"),
+            ("regalloc_checker", "true"),
+            ("regalloc_checks_disabled", "true"),
+        ];
+        let times = compile_with(&add_function(), &settings, &mut |_| {})
+            .report
+            .phase_times
+            .unwrap();
+        assert!(!times.regalloc.is_zero());
+        assert!(times.regalloc_checker.is_zero());
+    }
+
+    // Fuel is only tracked in chaos mode; otherwise the control plane never
+    // runs out.
+    #[test]
+    #[cfg(feature = "chaos")]
+    fn control_plane_fuel_cancels_compilation() {
+        let func = pressure_function();
+        let (domtree, backend, emit_info, sigs, callee) = compile_inputs(&func, &[]);
+        let mut ctrl_plane = ControlPlane::default();
+        ctrl_plane.set_fuel(1);
+        let result = compile::compile_with_options::<X64Backend>(
+            &func,
+            &domtree,
+            &backend,
+            callee,
+            emit_info,
+            sigs,
+            &mut ctrl_plane,
+            Default::default(),
+        );
+        assert!(ctrl_plane.is_out_of_fuel());
+        assert!(
+            matches!(result, Err(CodegenError::CompilationCancelled)),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn regalloc_algorithm() {
+        // Each supported allocator compiles the function...
+        let func = pressure_function();
+        for algorithm in ["backtracking", "single_pass"] {
+            compile_with(&func, &[("regalloc_algorithm", algorithm)], &mut |_| {});
+        }
+
+        // ...while an unknown one is rejected with an error.
+        let mut flag_builder = shared_settings::builder();
+        let err = flag_builder
+            .set("regalloc_algorithm", "linear_scan")
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::settings::SetError::BadValue(_)),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn regalloc_failure() {
+        // A function using a value that no reachable block defines is rejected
+        // by the verifier; without it, register allocation finds that value
+        // live into the entry block and compilation fails with an error.
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(types::I64));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(UserFuncName::testcase("livein"), sig);
+        let (entry, dead) = (func.dfg.make_block(), func.dfg.make_block());
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(entry);
+        let v0 = pos.func.dfg.append_block_param(entry, types::I64);
+        pos.insert_block(dead);
+        let v1 = pos.func.dfg.append_block_param(dead, types::I64);
+        let undef = pos.ins().imul(v1, v1);
+        pos.ins().return_(&[undef]);
+        pos.goto_bottom(entry);
+        let sum = pos.ins().iadd(v0, undef);
+        pos.ins().return_(&[sum]);
+
+        let cfg = ControlFlowGraph::with_function(&func);
+        let domtree = DominatorTree::with_function(&func, &cfg);
+        let flags = Flags::new(shared_settings::builder());
+        let x64_flags = x64_settings::Flags::new(&flags, &x64_settings::builder());
+        let backend =
+            X64Backend::new_with_flags("x86_64".parse().unwrap(), flags.clone(), x64_flags.clone());
+        let err = backend
+            .compile_vcode(&func, &domtree, &mut Default::default(), &mut |_| {})
+            .unwrap_err();
+        assert!(matches!(err, CodegenError::RegallocFailure(_)), "{err}");
+    }
+
+    #[test]
+    fn regalloc_reject_moves() {
+        let func = pressure_function();
+        let output = compile_with(&func, &[], &mut |_| {}).regalloc_result;
+        assert!(output.edits.len() > 1);
+
+        let cfg = ControlFlowGraph::with_function(&func);
+        let domtree = DominatorTree::with_function(&func, &cfg);
+        let mut flag_builder = shared_settings::builder();
+        flag_builder
+            .set("regalloc_reject_moves_log2_minus_one", "1")
+            .unwrap();
+        let flags = Flags::new(flag_builder);
+        let x64_flags = x64_settings::Flags::new(&flags, &x64_settings::builder());
+        let backend =
+            X64Backend::new_with_flags("x86_64".parse().unwrap(), flags.clone(), x64_flags.clone());
+        let err = backend
+            .compile_vcode(&func, &domtree, &mut Default::default(), &mut |_| {})
+            .unwrap_err();
+        assert!(matches!(err, CodegenError::RegallocBudgetExceeded), "{err}");
+    }
+
+    #[test]
+    fn regalloc_size_budget() {
+        let func = pressure_function();
+        let stats = compile_with(&func, &[], &mut |_| {}).report.stats;
+        assert!(stats.vcode_insts > 4);
+
+        // The budget is checked before register allocation: a function over it
+        // fails, while one under it still compiles.
+        let compile = |budget| {
+            let settings = [("regalloc_size_budget_log2_minus_one", budget)];
+            let (domtree, backend, emit_info, sigs, callee) = compile_inputs(&func, &settings);
+            compile::compile_with_options::<X64Backend>(
+                &func,
+                &domtree,
+                &backend,
+                callee,
+                emit_info,
+                sigs,
+                &mut Default::default(),
+                Default::default(),
+            )
+        };
+        let err = compile("3").unwrap_err();
+        assert!(matches!(err, CodegenError::RegallocBudgetExceeded), "{err}");
+        compile("20").unwrap();
+    }
+
+    #[test]
+    fn lowering_errors() {
+        // `iadd_imm` is only legal before legalization, so the backend has no
+        // lowering for it.
+        let mut func = add_function();
+        let block0 = func.layout.entry_block().unwrap();
+        let iadd = func.layout.first_inst(block0).unwrap();
+        let x = func.dfg.inst_args(iadd)[0];
+        func.dfg.replace(iadd).iadd_imm(x, 1);
+
+        let (domtree, backend, ..) = compile_inputs(&func, &[]);
+        let err = backend
+            .compile_vcode(&func, &domtree, &mut Default::default(), &mut |_| {})
+            .unwrap_err();
+        let source = std::error::Error::source(&err)
+            .and_then(|source| source.downcast_ref::<CodegenError>());
+        assert!(
+            matches!(source, Some(CodegenError::Unsupported(_))),
+            "{source:?}"
+        );
+        match err {
+            CodegenError::Lowering { inst, error } => {
+                assert_eq!(inst, Some(iadd));
+                assert!(matches!(*error, CodegenError::Unsupported(_)), "{error}");
+            }
+            err => panic!("expected a lowering error, got {err}"),
+        }
+
+        // Implementation limits hit while lowering are not wrapped.
+        let err = CodegenError::lowering(CodegenError::ImplLimitExceeded, Some(iadd));
+        assert!(matches!(err, CodegenError::ImplLimitExceeded), "{err}");
+        let err = CodegenError::lowering(CodegenError::CodeTooLarge, None);
+        assert!(matches!(err, CodegenError::CodeTooLarge), "{err}");
+    }
+
+    #[test]
+    fn require_pcc() {
+        let func = load_function(ir::MemFlags::new());
+        compile_with(&func, &[], &mut |_| {});
+        compile_with(
+            &func,
+            &[("require_pcc", "true"), ("enable_pcc", "true")],
+            &mut |_| {},
+        );
+        // Loads from trusted addresses need no proof.
+        let trusted = load_function(ir::MemFlags::trusted());
+        compile_with(&trusted, &[("require_pcc", "true")], &mut |_| {});
+
+        let (domtree, backend, ..) = compile_inputs(&func, &[("require_pcc", "true")]);
+        let err = backend
+            .compile_vcode(&func, &domtree, &mut Default::default(), &mut |_| {})
+            .unwrap_err();
+        match err {
+            CodegenError::PccRequired(inst) => assert_eq!(inst, "v1 = load.i64 v0"),
+            err => panic!("expected a pcc-required error, got {err}"),
+        }
+    }
+}
@@ -260,7 +260,10 @@ fn check_mem<'a>(
         SyntheticAmode::Real(amode) if amode.get_flags().checked() => {
             compute_addr(ctx, vcode, amode, 64).ok_or(PccError::MissingFact)?
         }
-        _ => return Ok(None),
+        _ => {
+            ctx.record_access(AccessVerdict::Trusted);
+            return Ok(None);
+        }
     };
 
     match op {
@@ -275,6 +278,7 @@ fn check_mem<'a>(
                 loaded_fact, result_fact
             );
             if ctx.subsumes_fact_optionals(loaded_fact.as_ref(), result_fact) {
+                ctx.record_access(AccessVerdict::Proven);
                 Ok(loaded_fact.clone())
             } else {
                 Err(PccError::UnsupportedFact)
@@ -282,6 +286,7 @@ fn check_mem<'a>(
         }
         LoadOrStore::Store { stored_fact } => {
             ctx.store(&addr, ty, stored_fact)?;
+            ctx.record_access(AccessVerdict::Proven);
             Ok(None)
         }
     }
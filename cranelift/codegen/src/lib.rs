@@ -36,6 +36,7 @@ pub use cranelift_control as control;
 pub use cranelift_entity as entity;
 #[cfg(feature = "unwind")]
 pub use gimli;
+pub use regalloc2;
 
 // Pull in generated the `isle_numerics_methods` macro.
 include!(concat!(env!("ISLE_DIR"), "/isle_numerics.rs"));
@@ -68,9 +69,10 @@ pub use crate::machinst::buffer::{
     OpenPatchRegion, PatchRegion,
 };
 pub use crate::machinst::{
-    CallInfo, CompiledCode, Final, MachBuffer, MachBufferFinalized, MachInst, MachInstEmit,
-    MachInstEmitState, MachLabel, RealReg, Reg, RelocDistance, TextSectionBuilder, VCodeConstant,
-    VCodeConstantData, VCodeConstants, VCodeInst, Writable,
+    CallInfo, CompilePhaseTimes, CompileReport, CompileStats, CompiledCode, Final, LoweredVCode,
+    MachBuffer, MachBufferFinalized, MachInst, MachInstEmit, MachInstEmitState, MachLabel, RealReg,
+    Reg, RegallocStats, RelocDistance, TextSectionBuilder, VCodeConstant, VCodeConstantData,
+    VCodeConstants, VCodeInst, Writable,
 };
 
 mod alias_analysis;
@@ -772,6 +772,7 @@ impl SigData {
 /// require cloning non-copy types that will trigger heap allocations.
 ///
 /// This type can be indexed by `Sig` to access its associated `SigData`.
+#[derive(Clone)]
 pub struct SigSet {
     /// Interned `ir::Signature`s that we already have an ABI signature for.
     ir_signature_to_abi_sig: FxHashMap<ir::Signature, Sig>,
@@ -1178,6 +1179,31 @@ pub struct Callee<M: ABIMachineSpec> {
     _mach: PhantomData<M>,
 }
 
+// Implemented by hand because deriving would require `M: Clone`, and the
+// machine specs are never instantiated.
+impl<M: ABIMachineSpec> Clone for Callee<M> {
+    fn clone(&self) -> Self {
+        Self {
+            ir_sig: self.ir_sig.clone(),
+            sig: self.sig,
+            dynamic_type_sizes: self.dynamic_type_sizes.clone(),
+            dynamic_stackslots: self.dynamic_stackslots.clone(),
+            sized_stackslots: self.sized_stackslots.clone(),
+            stackslots_size: self.stackslots_size,
+            outgoing_args_size: self.outgoing_args_size,
+            tail_args_size: self.tail_args_size,
+            reg_args: self.reg_args.clone(),
+            frame_layout: self.frame_layout.clone(),
+            ret_area_ptr: self.ret_area_ptr,
+            call_conv: self.call_conv,
+            flags: self.flags.clone(),
+            isa_flags: self.isa_flags.clone(),
+            stack_limit: self.stack_limit.clone(),
+            _mach: PhantomData,
+        }
+    }
+}
+
 fn get_special_purpose_param_register(
     f: &ir::Function,
     sigs: &SigSet,
@@ -68,7 +68,7 @@ use crate::{machinst::*, trace};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 /// Mapping from CLIF BBs to VCode BBs.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct BlockLoweringOrder {
     /// Lowered blocks, in BlockIndex order. Each block is some combination of
     /// (i) a CLIF block, and (ii) inserted crit-edge blocks before or after;
@@ -309,6 +309,47 @@ impl BlockLoweringOrder {
         result
     }
 
+    /// Check whether this order is still valid for `f`, e.g. before reusing
+    /// an order computed for an earlier version of the function.
+    ///
+    /// Only instructions other than block terminators may have changed: the
+    /// reachable blocks must appear in the same order, with the same
+    /// terminators, successors and coldness, as when the order was computed.
+    pub fn matches_function(&self, f: &Function, domtree: &DominatorTree) -> bool {
+        let orig_blocks = self.lowered_order.iter().filter_map(|lb| lb.orig_block());
+        if !orig_blocks.eq(domtree.cfg_rpo().copied()) {
+            return false;
+        }
+
+        self.lowered_order.iter().enumerate().all(|(ix, lb)| {
+            let Some(block) = lb.orig_block() else {
+                return true;
+            };
+            let bindex = BlockIndex::new(ix);
+            let Some(last) = f.layout.last_inst(block) else {
+                return false;
+            };
+            let (opt_inst, succs) = self.succ_indices(bindex);
+            if opt_inst != f.dfg.insts[last].opcode().is_branch().then_some(last)
+                || self.is_cold(bindex) != f.layout.is_cold(block)
+            {
+                return false;
+            }
+
+            let mut clif_succs = Vec::new();
+            visit_block_succs(f, block, |_, succ, _| clif_succs.push(succ));
+            clif_succs.len() == succs.len()
+                && clif_succs.iter().zip(succs).all(|(&succ, &lowered)| {
+                    match self.lowered_order[lowered.index()] {
+                        LoweredBlock::Orig { block } => block == succ,
+                        LoweredBlock::CriticalEdge { pred, succ: to, .. } => {
+                            pred == block && to == succ
+                        }
+                    }
+                })
+        })
+    }
+
     /// Get the lowered order of blocks.
     pub fn lowered_order(&self) -> &[LoweredBlock] {
         &self.lowered_order[..]
@@ -352,6 +393,13 @@ mod test {
     use crate::isa::CallConv;
 
     fn build_test_func(n_blocks: usize, edges: &[(usize, usize)]) -> BlockLoweringOrder {
+        build_test_func_and_order(n_blocks, edges).2
+    }
+
+    fn build_test_func_and_order(
+        n_blocks: usize,
+        edges: &[(usize, usize)],
+    ) -> (Function, DominatorTree, BlockLoweringOrder) {
         assert!(n_blocks > 0);
 
         let name = UserFuncName::testcase("test0");
@@ -394,7 +442,8 @@ mod test {
         cfg.compute(&func);
         let dom_tree = DominatorTree::with_function(&func, &cfg);
 
-        BlockLoweringOrder::new(&func, &dom_tree, &mut Default::default())
+        let order = BlockLoweringOrder::new(&func, &dom_tree, &mut Default::default());
+        (func, dom_tree, order)
     }
 
     #[test]
@@ -406,6 +455,17 @@ mod test {
         assert_eq!(order.lowered_order.len(), 4);
     }
 
+    #[test]
+    fn test_blockorder_matches_function() {
+        let (func, dom_tree, order) =
+            build_test_func_and_order(4, &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+        assert!(order.matches_function(&func, &dom_tree));
+
+        // Swapping the branch targets changes the successors of block 0.
+        let (func, dom_tree, _) = build_test_func_and_order(4, &[(0, 2), (0, 1), (1, 3), (2, 3)]);
+        assert!(!order.matches_function(&func, &dom_tree));
+    }
+
     #[test]
     fn test_blockorder_critedge() {
         //            0
@@ -2,19 +2,327 @@
 
 use crate::CodegenError;
 use crate::dominator_tree::DominatorTree;
-use crate::ir::Function;
 use crate::ir::pcc;
+use crate::ir::{Block, Function};
 use crate::isa::TargetIsa;
 use crate::machinst::*;
-use crate::settings::RegallocAlgorithm;
+use crate::settings::{self, RegallocAlgorithm};
 use crate::timing;
 use crate::trace;
 
+use alloc::string::{String, ToString};
+use core::time::Duration;
 use regalloc2::{Algorithm, RegallocOptions};
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Environment variable naming the directory that `dump_vcode` writes to.
+const VCODE_DUMP_DIR_VAR: &str = "CRANELIFT_VCODE_DUMP_DIR";
+
+/// Wall-clock time spent in each phase of [`compile_with_options`] for a single
+/// function.
+///
+/// This is only collected when the `compile_phase_timing` setting is enabled.
+/// Phases that did not run (e.g. the regalloc checker when it is disabled)
+/// report a zero duration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CompilePhaseTimes {
+    /// Time spent lowering CLIF to VCode.
+    pub lowering: Duration,
+    /// Time spent validating proof-carrying-code facts.
+    pub pcc: Duration,
+    /// Time spent in register allocation.
+    pub regalloc: Duration,
+    /// Time spent in the register allocation checker.
+    pub regalloc_checker: Duration,
+}
+
+/// Counts of the moves that register allocation inserted into a function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RegallocStats {
+    /// Moves from a register to a stack slot.
+    pub spills: usize,
+    /// Moves from a stack slot to a register.
+    pub reloads: usize,
+    /// All other moves, i.e. register-to-register and stack-to-stack.
+    pub moves: usize,
+}
+
+impl RegallocStats {
+    /// Tally the edits that register allocation made, as recorded in its
+    /// output.
+    pub fn from_output(output: &regalloc2::Output) -> Self {
+        let mut stats = Self::default();
+        for (_, edit) in &output.edits {
+            stats.add(edit);
+        }
+        stats
+    }
+
+    /// Tally the edits that register allocation made in each block of `func`,
+    /// indexed by block.
+    pub fn per_block(func: &impl regalloc2::Function, output: &regalloc2::Output) -> Vec<Self> {
+        let mut stats = vec![Self::default(); func.num_blocks()];
+        let mut block = 0;
+        for (point, edit) in &output.edits {
+            // Edits are sorted by program point, as are the blocks' instructions.
+            while func.block_insns(regalloc2::Block::new(block)).last() < point.inst() {
+                block += 1;
+            }
+            stats[block].add(edit);
+        }
+        stats
+    }
+
+    fn add(&mut self, edit: &regalloc2::Edit) {
+        match edit {
+            regalloc2::Edit::Move { from, to } => match (from.is_reg(), to.is_reg()) {
+                (true, false) => self.spills += 1,
+                (false, true) => self.reloads += 1,
+                _ => self.moves += 1,
+            },
+        }
+    }
+}
+
+/// The size of a function before and after lowering, e.g. for tracking code
+/// size regressions. These counts are cheap to collect, so
+/// [`compile_with_options`] always reports them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompileStats {
+    /// The number of CLIF instructions in the input function.
+    pub clif_insts: usize,
+    /// The number of CLIF blocks in the input function.
+    pub clif_blocks: usize,
+    /// The number of lowered VCode instructions.
+    pub vcode_insts: usize,
+    /// The number of lowered VCode blocks.
+    pub vcode_blocks: usize,
+    /// The CLIF block that each lowered block came from, in lowering order;
+    /// `None` for blocks inserted to split critical edges. See
+    /// [`BlockLoweringOrder::lowered_order`].
+    pub lowered_blocks: Vec<Option<Block>>,
+    /// The moves that register allocation inserted into the function.
+    pub regalloc: RegallocStats,
+    /// The moves that register allocation inserted into each lowered block,
+    /// indexed by [`BlockIndex`]. This is only collected when the
+    /// `regalloc_block_stats` setting is enabled.
+    pub regalloc_blocks: Option<Vec<RegallocStats>>,
+}
+
+/// What [`compile_with_options`] reports about a function besides its code;
+/// backends pass this on to embedders through [`CompiledCodeBase::report`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompileReport {
+    /// Per-phase timings, when the `compile_phase_timing` setting is enabled.
+    pub phase_times: Option<CompilePhaseTimes>,
+    /// Instruction, block and register allocation counts.
+    pub stats: CompileStats,
+    /// Which memory accesses were proven in bounds, when both the
+    /// `enable_pcc` and `pcc_report` settings are enabled.
+    pub pcc_report: Option<pcc::PccReport>,
+}
+
+/// The result of [`compile_with_options`]: VCode with allocated registers,
+/// ready for binary emission, and the [`CompileReport`] describing its
+/// compilation.
+#[derive(Debug)]
+pub struct CompileOutput<I: VCodeInst> {
+    /// The lowered function.
+    pub vcode: VCode<I>,
+    /// The register allocation of `vcode`.
+    pub regalloc_result: regalloc2::Output,
+    /// Timings and statistics collected along the way.
+    pub report: CompileReport,
+}
+
+/// Adds the time elapsed between its creation and its drop to a phase of
+/// [`CompilePhaseTimes`], if phase timing is enabled.
+struct PhaseTimer<'a>(Option<(&'a mut Duration, Instant)>);
+
+impl<'a> PhaseTimer<'a> {
+    fn new(phase: Option<&'a mut Duration>) -> Self {
+        Self(phase.map(|phase| (phase, Instant::now())))
+    }
+}
+
+impl Drop for PhaseTimer<'_> {
+    fn drop(&mut self) {
+        if let Some((phase, start)) = &mut self.0 {
+            **phase += start.elapsed();
+        }
+    }
+}
+
+/// The file that the `dump_vcode` setting writes `f`'s VCode and register
+/// allocation result to.
+pub fn vcode_dump_path(f: &Function) -> PathBuf {
+    let dir = std::env::var_os(VCODE_DUMP_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    let name: String = f
+        .name
+        .to_string()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{name}.vcode"))
+}
+
+/// Write `vcode` and its register allocation result to
+/// [`vcode_dump_path`]. This is only a debugging aid, so failures are logged
+/// rather than failing compilation.
+fn dump_vcode<I: VCodeInst>(f: &Function, vcode: &VCode<I>, regalloc_result: &regalloc2::Output) {
+    let path = vcode_dump_path(f);
+    if let Err(err) = std::fs::write(&path, describe(vcode, regalloc_result)) {
+        log::warn!("Failed to write VCode dump to {}: {err}", path.display());
+    }
+}
+
+/// Print `vcode` and its register allocation result.
+fn describe<I: VCodeInst>(vcode: &VCode<I>, regalloc_result: &regalloc2::Output) -> String {
+    format!("{vcode:?}\n\n{regalloc_result:#?}\n")
+}
+
+/// Fail with [`CodegenError::Nondeterministic`], showing the first lines that
+/// differ, if two compilations of `f` printed by `describe` are not identical.
+pub(crate) fn check_deterministic(f: &Function, first: &str, second: &str) -> CodegenResult<()> {
+    if first == second {
+        return Ok(());
+    }
+    let mut diff: Vec<String> = first
+        .lines()
+        .zip(second.lines())
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .take(10)
+        .map(|(i, (a, b))| format!("line {}:\n- {a}\n+ {b}", i + 1))
+        .collect();
+    let (first_lines, second_lines) = (first.lines().count(), second.lines().count());
+    if first_lines != second_lines {
+        diff.push(format!("{first_lines} lines vs. {second_lines} lines"));
+    }
+    log::error!("Nondeterministic compilation of {}", f.name);
+    Err(CodegenError::Nondeterministic(format!(
+        "{}:\n{}",
+        f.name,
+        diff.join("\n")
+    )))
+}
+
+/// The options that [`compile_with_options`] passes to the register allocator
+/// for the given settings.
+pub fn regalloc_options(flags: &settings::Flags) -> RegallocOptions {
+    let mut options = RegallocOptions::default();
+    options.verbose_log = flags.regalloc_verbose_logs();
+    options.validate_ssa = !flags.regalloc_checks_disabled()
+        && (cfg!(debug_assertions) || flags.regalloc_validate_ssa());
+    options.algorithm = match flags.regalloc_algorithm() {
+        RegallocAlgorithm::Backtracking => Algorithm::Ion,
+        RegallocAlgorithm::SinglePass => Algorithm::Fastalloc,
+    };
+    options
+}
+
+/// Check the function about to be register-allocated against the budget
+/// configured by the `regalloc_size_budget_log2_minus_one` setting.
+fn check_regalloc_size_budget<I: VCodeInst>(
+    flags: &settings::Flags,
+    vcode: &VCode<I>,
+) -> CodegenResult<()> {
+    if let n @ 1.. = flags.regalloc_size_budget_log2_minus_one() {
+        let size = vcode.num_insts().max(regalloc2::Function::num_vregs(vcode));
+        if size as u64 > 1 << (n - 1) {
+            return Err(CodegenError::RegallocBudgetExceeded);
+        }
+    }
+    Ok(())
+}
+
+/// Reject the result of register allocation if it inserted more moves than
+/// the `regalloc_reject_moves_log2_minus_one` setting allows.
+///
+/// This runs after the allocator has finished, so unlike
+/// [`check_regalloc_size_budget`] it does not bound the time spent allocating.
+fn reject_excess_regalloc_moves(
+    flags: &settings::Flags,
+    output: &regalloc2::Output,
+) -> CodegenResult<()> {
+    if let n @ 1.. = flags.regalloc_reject_moves_log2_minus_one() {
+        if output.edits.len() as u64 > 1 << (n - 1) {
+            return Err(CodegenError::RegallocBudgetExceeded);
+        }
+    }
+    Ok(())
+}
+
+/// Fail with [`CodegenError::CompilationCancelled`] if `ctrl_plane` has run out
+/// of fuel; [`compile_with_options`] checks this between phases.
+fn check_fuel(ctrl_plane: &ControlPlane) -> CodegenResult<()> {
+    if ctrl_plane.is_out_of_fuel() {
+        return Err(CodegenError::CompilationCancelled);
+    }
+    Ok(())
+}
+
+/// Optional inputs to [`compile_with_options`].
+pub struct CompileOptions<'a, I: VCodeInst> {
+    /// Lower blocks in this order instead of a newly computed one.
+    ///
+    /// This lets a caller that recompiles a function whose control flow graph
+    /// has not changed (e.g. after editing only non-terminator instructions)
+    /// reuse the order from an earlier compilation, available through
+    /// [`VCode::block_order`]. In debug builds, this checks that the order
+    /// [matches the function](BlockLoweringOrder::matches_function).
+    pub block_order: Option<BlockLoweringOrder>,
+    /// Invoke this with the lowered VCode right before register allocation.
+    ///
+    /// The hook runs after lowering and proof-carrying-code validation, which
+    /// lets tooling dump or analyze the VCode (for example to measure register
+    /// pressure) without duplicating the rest of the pipeline.
+    pub pre_regalloc: Option<&'a mut dyn FnMut(&VCode<I>)>,
+}
+
+impl<I: VCodeInst> Default for CompileOptions<'_, I> {
+    fn default() -> Self {
+        Self {
+            block_order: None,
+            pre_regalloc: None,
+        }
+    }
+}
 
 /// Compile the given function down to VCode with allocated registers, ready
-/// for binary emission.
-pub fn compile<B: LowerBackend + TargetIsa>(
+/// for binary emission, along with a [`CompileReport`] describing the
+/// compilation; this is the entry point the backends use.
+///
+/// The returned [`CompileReport`] always holds [`CompileStats`], with
+/// per-block register allocation counts when the `regalloc_block_stats`
+/// setting is enabled. Per-phase timings are only collected when the
+/// `compile_phase_timing` setting is enabled and, when both the `enable_pcc`
+/// and `pcc_report` settings are enabled, a [`PccReport`](pcc::PccReport)
+/// lists which memory accesses were proven in bounds and which were trusted.
+///
+/// When the `require_pcc` setting is enabled but `enable_pcc` is not, any
+/// memory access that may trap or is marked `checked` is rejected with
+/// [`CodegenError::PccRequired`] before lowering.
+///
+/// When the `compile_determinism_check` setting is enabled, the function is
+/// first compiled twice more with fresh control planes, failing with
+/// [`CodegenError::Nondeterministic`] if the two results differ.
+///
+/// Errors while lowering are reported as [`CodegenError::Lowering`].
+///
+/// If `ctrl_plane` runs out of fuel, compilation stops after the current phase
+/// (lowering, proof-carrying-code validation or register allocation) with
+/// [`CodegenError::CompilationCancelled`].
+pub fn compile_with_options<B: LowerBackend + TargetIsa>(
     f: &Function,
     domtree: &DominatorTree,
     b: &B,
@@ -22,13 +330,98 @@ pub fn compile<B: LowerBackend + TargetIsa>(
     emit_info: <B::MInst as MachInstEmit>::Info,
     sigs: SigSet,
     ctrl_plane: &mut ControlPlane,
-) -> CodegenResult<(VCode<B::MInst>, regalloc2::Output)> {
-    // Compute lowered block order.
-    let block_order = BlockLoweringOrder::new(f, domtree, ctrl_plane);
+    options: CompileOptions<'_, B::MInst>,
+) -> CodegenResult<CompileOutput<B::MInst>> {
+    let CompileOptions {
+        block_order,
+        pre_regalloc,
+    } = options;
+    if let Some(block_order) = &block_order {
+        debug_assert!(
+            block_order.matches_function(f, domtree),
+            "block lowering order does not match the control flow graph of {}",
+            f.name
+        );
+    }
+
+    if b.flags().compile_determinism_check() {
+        let compile_fresh = || -> CodegenResult<String> {
+            let output = run_pipeline::<B>(
+                f,
+                domtree,
+                b,
+                abi.clone(),
+                emit_info.clone(),
+                sigs.clone(),
+                &mut ControlPlane::default(),
+                block_order.clone(),
+                &mut |_| {},
+            )?;
+            Ok(describe(&output.vcode, &output.regalloc_result))
+        };
+        let first = compile_fresh()?;
+        let second = compile_fresh()?;
+        check_deterministic(f, &first, &second)?;
+    }
+
+    run_pipeline::<B>(
+        f,
+        domtree,
+        b,
+        abi,
+        emit_info,
+        sigs,
+        ctrl_plane,
+        block_order,
+        pre_regalloc.unwrap_or(&mut |_| {}),
+    )
+}
+
+/// Lower the given function to VCode without allocating registers, e.g. for
+/// tooling which only inspects instruction selection.
+///
+/// This runs the same lowering, and proof-carrying-code validation when the
+/// `enable_pcc` setting is enabled, as [`compile_with_options`] but stops
+/// before register allocation, so the returned VCode still refers to virtual
+/// registers.
+pub fn lower_only<B: LowerBackend + TargetIsa>(
+    f: &Function,
+    domtree: &DominatorTree,
+    b: &B,
+    abi: Callee<<<B as LowerBackend>::MInst as MachInst>::ABIMachineSpec>,
+    emit_info: <B::MInst as MachInstEmit>::Info,
+    sigs: SigSet,
+    ctrl_plane: &mut ControlPlane,
+) -> CodegenResult<VCode<B::MInst>> {
+    lower_and_check::<B>(
+        f, domtree, b, abi, emit_info, sigs, ctrl_plane, None, None, None,
+    )
+}
+
+/// The phases of [`compile_with_options`] before register allocation: lowering
+/// and proof-carrying-code validation.
+fn lower_and_check<B: LowerBackend + TargetIsa>(
+    f: &Function,
+    domtree: &DominatorTree,
+    b: &B,
+    abi: Callee<<<B as LowerBackend>::MInst as MachInst>::ABIMachineSpec>,
+    emit_info: <B::MInst as MachInstEmit>::Info,
+    sigs: SigSet,
+    ctrl_plane: &mut ControlPlane,
+    block_order: Option<BlockLoweringOrder>,
+    mut phase_times: Option<&mut CompilePhaseTimes>,
+    pcc_report: Option<&mut pcc::PccReport>,
+) -> CodegenResult<VCode<B::MInst>> {
+    check_pcc_required(f, b.flags())?;
+
+    // Compute lowered block order, unless the caller provided one.
+    let block_order =
+        block_order.unwrap_or_else(|| BlockLoweringOrder::new(f, domtree, ctrl_plane));
 
     // Build the lowering context.
     let lower =
-        crate::machinst::Lower::new(f, abi, emit_info, block_order, sigs, b.flags().clone())?;
+        crate::machinst::Lower::new(f, abi, emit_info, block_order, sigs, b.flags().clone())
+            .map_err(|e| CodegenError::lowering(e, None))?;
 
     // Lower the IR.
     let mut vcode = {
@@ -39,7 +432,10 @@ pub fn compile<B: LowerBackend + TargetIsa>(
         log::debug!("Number of CLIF blocks to lower: {}", f.dfg.num_blocks());
 
         let _tt = timing::vcode_lower();
-        lower.lower(b, ctrl_plane)?
+        let _pt = PhaseTimer::new(phase_times.as_deref_mut().map(|t| &mut t.lowering));
+        lower
+            .lower(b, ctrl_plane)
+            .map_err(|e| CodegenError::lowering(e, None))?
     };
 
     log::debug!(
@@ -48,50 +444,143 @@ pub fn compile<B: LowerBackend + TargetIsa>(
     );
     log::debug!("Number of lowered vcode blocks: {}", vcode.num_blocks());
     trace!("vcode from lowering: \n{:?}", vcode);
+    check_fuel(ctrl_plane)?;
 
     // Perform validation of proof-carrying-code facts, if requested.
     if b.flags().enable_pcc() {
-        pcc::check_vcode_facts(f, &mut vcode, b).map_err(CodegenError::Pcc)?;
+        let _pt = PhaseTimer::new(phase_times.map(|t| &mut t.pcc));
+        pcc::check_vcode_facts(f, &mut vcode, b, pcc_report).map_err(CodegenError::Pcc)?;
     }
+    check_fuel(ctrl_plane)?;
 
-    // Perform register allocation.
-    let regalloc_result = {
-        let _tt = timing::regalloc();
-        let mut options = RegallocOptions::default();
-        options.verbose_log = b.flags().regalloc_verbose_logs();
+    Ok(vcode)
+}
 
-        if cfg!(debug_assertions) {
-            options.validate_ssa = true;
+/// Reject memory accesses that would compile without a proof when the
+/// `require_pcc` setting is enabled but `enable_pcc` is not.
+fn check_pcc_required(f: &Function, flags: &settings::Flags) -> CodegenResult<()> {
+    if !flags.require_pcc() || flags.enable_pcc() {
+        return Ok(());
+    }
+    for block in f.layout.blocks() {
+        for inst in f.layout.block_insts(block) {
+            let Some(memflags) = f.dfg.insts[inst].memflags() else {
+                continue;
+            };
+            if memflags.checked() || memflags.trap_code().is_some() {
+                return Err(CodegenError::PccRequired(
+                    f.dfg.display_inst(inst).to_string(),
+                ));
+            }
         }
+    }
+    Ok(())
+}
 
-        options.algorithm = match b.flags().regalloc_algorithm() {
-            RegallocAlgorithm::Backtracking => Algorithm::Ion,
-            RegallocAlgorithm::SinglePass => Algorithm::Fastalloc,
-        };
+fn run_pipeline<B: LowerBackend + TargetIsa>(
+    f: &Function,
+    domtree: &DominatorTree,
+    b: &B,
+    abi: Callee<<<B as LowerBackend>::MInst as MachInst>::ABIMachineSpec>,
+    emit_info: <B::MInst as MachInstEmit>::Info,
+    sigs: SigSet,
+    ctrl_plane: &mut ControlPlane,
+    block_order: Option<BlockLoweringOrder>,
+    pre_regalloc: &mut dyn FnMut(&VCode<B::MInst>),
+) -> CodegenResult<CompileOutput<B::MInst>> {
+    let mut phase_times = b
+        .flags()
+        .compile_phase_timing()
+        .then(CompilePhaseTimes::default);
+    let mut pcc_report =
+        (b.flags().enable_pcc() && b.flags().pcc_report()).then(pcc::PccReport::default);
+
+    let vcode = lower_and_check::<B>(
+        f,
+        domtree,
+        b,
+        abi,
+        emit_info,
+        sigs,
+        ctrl_plane,
+        block_order,
+        phase_times.as_mut(),
+        pcc_report.as_mut(),
+    )?;
 
-        regalloc2::run(&vcode, vcode.abi.machine_env(), &options)
+    let mut stats = CompileStats {
+        clif_insts: f.dfg.num_insts(),
+        clif_blocks: f.dfg.num_blocks(),
+        vcode_insts: vcode.num_insts(),
+        vcode_blocks: vcode.num_blocks(),
+        lowered_blocks: vcode
+            .block_order()
+            .lowered_order()
+            .iter()
+            .map(|b| b.orig_block())
+            .collect(),
+        regalloc: RegallocStats::default(),
+        regalloc_blocks: None,
+    };
+
+    pre_regalloc(&vcode);
+
+    // Perform register allocation.
+    let regalloc_result = {
+        let _tt = timing::regalloc();
+        let _pt = PhaseTimer::new(phase_times.as_mut().map(|t| &mut t.regalloc));
+        check_regalloc_size_budget(b.flags(), &vcode).inspect_err(|_| {
+            log::warn!("{} is too large to register-allocate within budget", f.name);
+        })?;
+        let options = regalloc_options(b.flags());
+        let output = regalloc2::run(&vcode, vcode.abi.machine_env(), &options)
             .map_err(|err| {
                 log::error!(
                     "Register allocation error for vcode\n{vcode:?}\nError: {err:?}\nCLIF for error:\n{f:?}",
                 );
-                err
-            })
-            .expect("register allocation")
+                CodegenError::RegallocFailure(err)
+            })?;
+        reject_excess_regalloc_moves(b.flags(), &output).inspect_err(|_| {
+            log::warn!("Register allocation for {} exceeded its budget", f.name);
+        })?;
+        output
     };
+    check_fuel(ctrl_plane)?;
+
+    stats.regalloc = RegallocStats::from_output(&regalloc_result);
+    log::debug!("Regalloc stats for {}: {:?}", f.name, stats.regalloc);
 
-    // Run the regalloc checker, if requested.
-    if b.flags().regalloc_checker() {
+    if b.flags().regalloc_block_stats() {
+        stats.regalloc_blocks = Some(RegallocStats::per_block(&vcode, &regalloc_result));
+    }
+
+    // Run the regalloc checker, if requested and not overridden.
+    if b.flags().regalloc_checker() && !b.flags().regalloc_checks_disabled() {
         let _tt = timing::regalloc_checker();
+        let _pt = PhaseTimer::new(phase_times.as_mut().map(|t| &mut t.regalloc_checker));
         let mut checker = regalloc2::checker::Checker::new(&vcode, vcode.abi.machine_env());
         checker.prepare(&regalloc_result);
-        checker
-            .run()
-            .map_err(|err| {
-                log::error!("Register allocation checker errors:\n{err:?}\nfor vcode:\n{vcode:?}");
-                err
-            })
-            .expect("register allocation checker");
+        checker.run().map_err(|err| {
+            log::error!("Register allocation checker errors:\n{err:?}\nfor vcode:\n{vcode:?}");
+            CodegenError::Regalloc(err)
+        })?;
+    }
+
+    if b.flags().dump_vcode() {
+        dump_vcode(f, &vcode, &regalloc_result);
+    }
+
+    if let Some(phase_times) = &phase_times {
+        log::debug!("Compile phase times for {}: {phase_times:?}", f.name);
     }
 
-    Ok((vcode, regalloc_result))
+    Ok(CompileOutput {
+        vcode,
+        regalloc_result,
+        report: CompileReport {
+            phase_times,
+            stats,
+            pcc_report,
+        },
+    })
 }
@@ -809,11 +809,12 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
                         } else {
                             None
                         };
-                        return Err(CodegenError::Unsupported(format!(
+                        let error = CodegenError::Unsupported(format!(
                             "should be implemented in ISLE: inst = `{}`, type = `{:?}`",
                             self.f.dfg.display_inst(inst),
                             ty
-                        )));
+                        ));
+                        return Err(CodegenError::lowering(error, Some(inst)));
                     }
                 };
 
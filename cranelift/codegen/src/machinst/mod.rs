@@ -345,7 +345,7 @@ pub trait MachInstEmit: MachInst {
     type State: MachInstEmitState<Self>;
 
     /// Constant information used in `emit` invocations.
-    type Info;
+    type Info: Clone;
 
     /// Emit the instruction.
     fn emit(&self, code: &mut MachBuffer<Self>, info: &Self::Info, state: &mut Self::State);
@@ -411,6 +411,11 @@ pub struct CompiledCodeBase<T: CompilePhase> {
     /// This info is generated only if the `machine_code_cfg_info`
     /// flag is set.
     pub bb_edges: Vec<(CodeOffset, CodeOffset)>,
+    /// Timings and statistics about this compilation. These describe one
+    /// particular run of the compiler, so they are not serialized; a stencil
+    /// loaded from the incremental cache reports the defaults.
+    #[cfg_attr(feature = "enable-serde", serde(skip))]
+    pub report: CompileReport,
 }
 
 impl CompiledCodeStencil {
@@ -425,6 +430,7 @@ impl CompiledCodeStencil {
             dynamic_stackslot_offsets: self.dynamic_stackslot_offsets,
             bb_starts: self.bb_starts,
             bb_edges: self.bb_edges,
+            report: self.report,
         }
     }
 }
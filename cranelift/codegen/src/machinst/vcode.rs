@@ -673,6 +673,14 @@ impl<I: VCodeInst> VCode<I> {
         self.insts.len()
     }
 
+    /// The block order this code was lowered in; its
+    /// [`lowered_order`](BlockLoweringOrder::lowered_order) maps each machine
+    /// block back to its CLIF block or critical edge, e.g. for visualizing
+    /// block placement.
+    pub fn block_order(&self) -> &BlockLoweringOrder {
+        &self.block_order
+    }
+
     fn compute_clobbers_and_function_calls(
         &self,
         regalloc: &regalloc2::Output,
@@ -1496,6 +1504,13 @@ impl<I: VCodeInst> std::ops::Index<InsnIndex> for VCode<I> {
     }
 }
 
+/// A backend-independent view of lowered [`VCode`], for tooling outside this
+/// crate: [`regalloc2::Function`] exposes its blocks, instructions and
+/// operands, and `Debug` prints it.
+pub trait LoweredVCode: RegallocFunction + fmt::Debug {}
+
+impl<I: VCodeInst> LoweredVCode for VCode<I> {}
+
 impl<I: VCodeInst> RegallocFunction for VCode<I> {
     fn num_insts(&self) -> usize {
         self.insts.len()
@@ -1,9 +1,14 @@
 //! Result and error types representing the outcome of compiling a function.
 
+use regalloc2::RegAllocError;
 use regalloc2::checker::CheckerErrors;
 
 use crate::ir::pcc::PccError;
-use crate::{ir::Function, verifier::VerifierErrors};
+use crate::{
+    ir::{Function, Inst},
+    verifier::VerifierErrors,
+};
+use std::boxed::Box;
 use std::string::String;
 
 /// A compilation error.
@@ -43,8 +48,47 @@ pub enum CodegenError {
     /// Register allocator internal error discovered by the symbolic checker.
     Regalloc(CheckerErrors),
 
+    /// The register allocator failed to allocate registers for a function.
+    ///
+    /// This usually represents a bug in Cranelift's lowering, but it may also
+    /// be triggered by input that exceeds what the allocator can handle.
+    RegallocFailure(RegAllocError),
+
+    /// Register allocation exceeded the size or move budget configured by the
+    /// `regalloc_size_budget_log2_minus_one` or
+    /// `regalloc_reject_moves_log2_minus_one` settings.
+    RegallocBudgetExceeded,
+
     /// Proof-carrying-code validation error.
     Pcc(PccError),
+
+    /// The `require_pcc` setting is enabled without `enable_pcc`, but the
+    /// function accesses untrusted memory; the message shows the access.
+    PccRequired(String),
+
+    /// Lowering the function to machine instructions failed.
+    ///
+    /// This distinguishes failures in lowering from those in later phases,
+    /// such as register allocation.
+    Lowering {
+        /// The CLIF instruction that failed to lower, if known.
+        inst: Option<Inst>,
+        /// The underlying error.
+        error: Box<CodegenError>,
+    },
+
+    /// Compilation stopped because the control plane ran out of fuel.
+    ///
+    /// This lets fuzzers that limit the control plane's fuel also bound the
+    /// work spent compiling a function.
+    CompilationCancelled,
+
+    /// Compiling the same function twice produced different results, as
+    /// detected by the `compile_determinism_check` setting.
+    ///
+    /// This always represents a bug in Cranelift. The message shows the first
+    /// lines that differ.
+    Nondeterministic(String),
 }
 
 /// A convenient alias for a `Result` that uses `CodegenError` as the error type.
@@ -62,7 +106,13 @@ impl std::error::Error for CodegenError {
             #[cfg(feature = "unwind")]
             CodegenError::RegisterMappingError { .. } => None,
             CodegenError::Regalloc(..) => None,
+            CodegenError::RegallocFailure(..) => None,
+            CodegenError::RegallocBudgetExceeded => None,
             CodegenError::Pcc(..) => None,
+            CodegenError::PccRequired(..) => None,
+            CodegenError::Lowering { error, .. } => Some(&**error),
+            CodegenError::CompilationCancelled => None,
+            CodegenError::Nondeterministic(..) => None,
         }
     }
 }
@@ -76,11 +126,51 @@ impl std::fmt::Display for CodegenError {
             CodegenError::Unsupported(feature) => write!(f, "Unsupported feature: {feature}"),
             #[cfg(feature = "unwind")]
             CodegenError::RegisterMappingError(_0) => write!(f, "Register mapping error"),
+            // NOTE: if these are changed, please update the `is_regalloc_error` function defined
+            // in `wasmtime/crates/fuzzing/src/oracles.rs`
             CodegenError::Regalloc(errors) => write!(f, "Regalloc validation errors: {errors:?}"),
+            CodegenError::RegallocFailure(err) => write!(f, "Register allocation error: {err:?}"),
+            CodegenError::RegallocBudgetExceeded => {
+                write!(f, "Register allocation exceeded its budget")
+            }
 
             // NOTE: if this is changed, please update the `is_pcc_error` function defined in
             // `wasmtime/crates/fuzzing/src/oracles.rs`
             CodegenError::Pcc(e) => write!(f, "Proof-carrying-code validation error: {e:?}"),
+            CodegenError::PccRequired(inst) => write!(
+                f,
+                "`require_pcc` is enabled without `enable_pcc`, but `{inst}` accesses untrusted memory"
+            ),
+            CodegenError::Lowering { error, .. } => write!(f, "Lowering error: {error}"),
+            CodegenError::CompilationCancelled => {
+                write!(
+                    f,
+                    "Compilation cancelled: the control plane ran out of fuel"
+                )
+            }
+            CodegenError::Nondeterministic(diff) => {
+                write!(f, "Nondeterministic compilation: {diff}")
+            }
+        }
+    }
+}
+
+impl CodegenError {
+    /// Wrap an error that occurred while lowering, at `inst` if known, in
+    /// [`CodegenError::Lowering`], unless it already is one.
+    ///
+    /// Implementation limits, i.e., [`CodegenError::ImplLimitExceeded`] and
+    /// [`CodegenError::CodeTooLarge`], are not lowering failures and are
+    /// returned unchanged so callers can keep matching on them directly.
+    pub fn lowering(error: CodegenError, inst: Option<Inst>) -> Self {
+        match error {
+            CodegenError::Lowering { .. }
+            | CodegenError::ImplLimitExceeded
+            | CodegenError::CodeTooLarge => error,
+            error => CodegenError::Lowering {
+                inst,
+                error: Box::new(error),
+            },
         }
     }
 }
@@ -487,6 +487,8 @@ mod tests {
         let f = Flags::new(b);
         let actual = f.to_string();
         let expected = r#"[shared]
+regalloc_reject_moves_log2_minus_one = 0
+regalloc_size_budget_log2_minus_one = 0
 regalloc_algorithm = "backtracking"
 opt_level = "none"
 tls_model = "none"
@@ -498,9 +500,17 @@ bb_padding_log2_minus_one = 0
 log2_min_function_alignment = 0
 regalloc_checker = false
 regalloc_verbose_logs = false
+regalloc_validate_ssa = false
+regalloc_checks_disabled = false
+compile_phase_timing = false
+regalloc_block_stats = false
+compile_determinism_check = false
+dump_vcode = false
 enable_alias_analysis = true
 enable_verifier = true
 enable_pcc = false
+pcc_report = false
+require_pcc = false
 is_pic = false
 use_colocated_libcalls = false
 enable_float = true
@@ -35,6 +35,12 @@ impl ControlPlane {
         self.fuel = (fuel != 0).then_some(fuel)
     }
 
+    /// Returns `true` if a [fuel limit](crate#fuel-limit) was set and all of
+    /// the fuel has been consumed.
+    pub fn is_out_of_fuel(&self) -> bool {
+        self.fuel == Some(0)
+    }
+
     /// Tries to consume fuel, returning `true` if successful (or if
     /// fuel-limiting is disabled).
     fn consume_fuel(&mut self) -> bool {
@@ -31,6 +31,10 @@
 //! cargo fuzz run --features chaos $TARGET -- --fuel=16
 //! ```
 //!
+//! Running out of fuel also bounds the work spent on a function: Cranelift
+//! checks the fuel between compilation phases and, once it is used up, stops
+//! compiling with `CodegenError::CompilationCancelled`.
+//!
 //! ## `no_std` support
 //!
 //! This crate compiles in `no_std` environments, although both the `fuzz`
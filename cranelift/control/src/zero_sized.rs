@@ -24,6 +24,14 @@ impl ControlPlane {
     /// chaos mode is disabled. It doesn't do anything.
     pub fn set_fuel(&mut self, _fuel: u8) {}
 
+    /// Returns whether the [fuel limit](crate#fuel-limit) has been reached.
+    /// This variant is used when chaos mode is disabled. It always returns
+    /// `false`.
+    #[inline]
+    pub fn is_out_of_fuel(&self) -> bool {
+        false
+    }
+
     /// Returns a pseudo-random boolean. This variant is used when chaos
     /// mode is disabled. It always returns `false`.
     #[inline]
@@ -105,6 +105,11 @@ impl Parse for Config {
                     }
                     Opt::Debug(val) => opts.debug = val,
                     Opt::TrappableErrorType(val) => opts.trappable_error_type = val,
+                    Opt::TrapType(val) => {
+                        opts.trap_type = Some(val.into_token_stream().to_string())
+                    }
+                    Opt::Mock(val) => opts.mock = val,
+                    Opt::FlattenUnitErrors(val) => opts.flatten_unit_errors = val,
                     Opt::Ownership(val) => opts.ownership = val,
                     Opt::Interfaces(s) => {
                         if inline.is_some() {
@@ -242,6 +247,9 @@ mod kw {
     syn::custom_keyword!(tracing);
     syn::custom_keyword!(verbose_tracing);
     syn::custom_keyword!(trappable_error_type);
+    syn::custom_keyword!(trap_type);
+    syn::custom_keyword!(mock);
+    syn::custom_keyword!(flatten_unit_errors);
     syn::custom_keyword!(world);
     syn::custom_keyword!(ownership);
     syn::custom_keyword!(interfaces);
@@ -269,6 +277,9 @@ enum Opt {
     Path(Vec<syn::LitStr>),
     Inline(syn::LitStr),
     TrappableErrorType(Vec<TrappableError>),
+    TrapType(syn::Path),
+    Mock(bool),
+    FlattenUnitErrors(bool),
     Ownership(Ownership),
     Interfaces(syn::LitStr),
     With(HashMap<String, String>),
@@ -364,6 +375,18 @@ impl Parse for Opt {
             let fields: Punctuated<_, Token![,]> =
                 contents.parse_terminated(trappable_error_field_parse, Token![,])?;
             Ok(Opt::TrappableErrorType(Vec::from_iter(fields)))
+        } else if l.peek(kw::trap_type) {
+            input.parse::<kw::trap_type>()?;
+            input.parse::<Token![:]>()?;
+            Ok(Opt::TrapType(input.parse()?))
+        } else if l.peek(kw::mock) {
+            input.parse::<kw::mock>()?;
+            input.parse::<Token![:]>()?;
+            Ok(Opt::Mock(input.parse::<syn::LitBool>()?.value))
+        } else if l.peek(kw::flatten_unit_errors) {
+            input.parse::<kw::flatten_unit_errors>()?;
+            input.parse::<Token![:]>()?;
+            Ok(Opt::FlattenUnitErrors(input.parse::<syn::LitBool>()?.value))
         } else if l.peek(kw::interfaces) {
             input.parse::<kw::interfaces>()?;
             input.parse::<Token![:]>()?;
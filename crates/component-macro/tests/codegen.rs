@@ -36,6 +36,12 @@ macro_rules! gentest {
                     imports: { default: store },
                 });
             }
+            mod mock {
+                wasmtime::component::bindgen!({
+                    path: $path,
+                    mock: true,
+                });
+            }
         }
     };
 }
@@ -560,7 +566,13 @@ mod custom_derives {
                     field3: abc
                 }
 
+                enum level {
+                    debug,
+                    warn,
+                }
+
                 bar: func(cool: foo);
+                log: func(level: level);
             }
 
             world baz {
@@ -572,7 +584,7 @@ mod custom_derives {
         additional_derives: [serde::Serialize, serde::Deserialize, Hash, Clone, PartialEq, Eq],
     });
 
-    use my::inline::blah::{Abc, Foo, Host};
+    use my::inline::blah::{Abc, Foo, Host, Level};
 
     struct X;
 
@@ -589,6 +601,16 @@ mod custom_derives {
             // compilation will fail here
             let _ = serde_json::to_string(&cool);
         }
+
+        fn log(&mut self, _level: Level) {}
+    }
+
+    #[test]
+    fn serde_enum() {
+        let s = serde_json::to_string(&Level::Warn).unwrap();
+        assert_eq!(s, "\"Warn\"");
+        let level: Level = serde_json::from_str(&s).unwrap();
+        assert_eq!(level, Level::Warn);
     }
 }
 
@@ -667,6 +689,273 @@ mod with_and_mixing_async {
     }
 }
 
+mod async_exports {
+    mod some {
+        use wasmtime::Store;
+
+        wasmtime::component::bindgen!({
+            inline: "
+                package example:x;
+
+                interface iface {
+                    slow-func: func();
+                    fast-func: func();
+                }
+
+                world x {
+                    export iface;
+                }
+            ",
+            exports: { "example:x/iface/slow-func": async },
+        });
+
+        // Only `slow-func` is async, `fast-func` is still called
+        // synchronously.
+        async fn _call(x: &X, store: &mut Store<()>) -> wasmtime::Result<()> {
+            let iface = x.example_x_iface();
+            iface.call_slow_func(&mut *store).await?;
+            iface.call_fast_func(&mut *store)?;
+            Ok(())
+        }
+    }
+}
+
+mod non_send_store_data {
+    use std::rc::Rc;
+    use wasmtime::component::{HasSelf, Linker};
+
+    wasmtime::component::bindgen!({
+        inline: "
+            package foo:foo;
+
+            world foo {
+                import foo: func();
+            }
+        ",
+    });
+
+    struct X(Rc<()>);
+
+    impl FooImports for X {
+        fn foo(&mut self) {}
+    }
+
+    // Synchronous bindings don't require the store data to be `Send`.
+    fn _add_to_linker(linker: &mut Linker<X>) -> wasmtime::Result<()> {
+        Foo::add_to_linker::<_, HasSelf<_>>(linker, |x| x)
+    }
+}
+
+mod enum_conversions {
+    wasmtime::component::bindgen!({
+        inline: "
+            package foo:foo;
+
+            interface logging {
+                enum level {
+                    debug,
+                    info,
+                    warn,
+                    error,
+                }
+            }
+
+            world foo {
+                import logging;
+            }
+        ",
+    });
+
+    use foo::foo::logging::Level;
+
+    #[test]
+    fn discriminants() {
+        assert_eq!(
+            Level::ALL,
+            [Level::Debug, Level::Info, Level::Warn, Level::Error]
+        );
+        assert_eq!(u8::from(Level::Debug), 0);
+        assert_eq!(u8::from(Level::Warn), 2);
+        assert_eq!(Level::try_from(2u8).unwrap(), Level::Warn);
+        assert!(Level::try_from(4u8).is_err());
+    }
+}
+
+mod flags_operations {
+    wasmtime::component::bindgen!({
+        inline: "
+            package foo:foo;
+
+            interface files {
+                flags permissions { read, write, exec }
+            }
+
+            world foo {
+                import files;
+            }
+        ",
+    });
+
+    use foo::foo::files::Permissions;
+
+    #[test]
+    fn bitwise() {
+        let rw = Permissions::READ | Permissions::WRITE;
+        assert!(rw.contains(Permissions::READ));
+        assert!(!rw.contains(Permissions::EXEC));
+        assert_eq!(rw & Permissions::WRITE, Permissions::WRITE);
+        assert_eq!(rw | Permissions::EXEC, Permissions::all());
+        assert!(!Permissions::empty().intersects(rw));
+    }
+}
+
+mod import_and_export_same_interface {
+    use wasmtime::component::{Resource, ResourceAny};
+
+    wasmtime::component::bindgen!({
+        inline: "
+            package example:imported-resources;
+
+            interface logging {
+                enum level { debug, info }
+
+                resource logger {
+                    constructor(max-level: level);
+                    log: func(level: level, msg: string);
+                }
+            }
+
+            world pass-through {
+                import logging;
+                export logging;
+            }
+        ",
+        with: { "example:imported-resources/logging/logger": MyLogger },
+    });
+
+    use example::imported_resources::logging as imports;
+    use exports::example::imported_resources::logging as guest;
+
+    pub struct MyLogger;
+
+    struct X;
+
+    impl imports::Host for X {}
+
+    impl imports::HostLogger for X {
+        fn new(&mut self, _: imports::Level) -> Resource<MyLogger> {
+            loop {}
+        }
+        fn log(&mut self, _: Resource<MyLogger>, _: imports::Level, _: String) {}
+        fn drop(&mut self, _: Resource<MyLogger>) -> wasmtime::Result<()> {
+            Ok(())
+        }
+    }
+
+    // The import and export sides each have their own `Level` type.
+    fn _forward(
+        guest: &guest::Guest,
+        store: &mut wasmtime::Store<X>,
+        level: imports::Level,
+    ) -> wasmtime::Result<ResourceAny> {
+        let level = match level {
+            imports::Level::Debug => guest::Level::Debug,
+            imports::Level::Info => guest::Level::Info,
+        };
+        guest.logger().call_constructor(store, level)
+    }
+}
+
+mod interfaces_metadata {
+    wasmtime::component::bindgen!({
+        inline: "
+            package example:imported-resources;
+
+            interface logging {
+                resource logger {
+                    constructor();
+                    log: func(msg: string);
+                }
+                flush: func();
+            }
+
+            world import-some-resources {
+                import logging;
+                import f: func();
+                export run: interface {
+                    run: func();
+                }
+            }
+        ",
+    });
+
+    #[test]
+    fn interfaces() {
+        assert_eq!(
+            ImportSomeResources::IMPORTED_INTERFACES,
+            [(
+                "example:imported-resources/logging",
+                &["[constructor]logger", "[method]logger.log", "flush"][..],
+            )],
+        );
+        assert_eq!(
+            ImportSomeResources::EXPORTED_INTERFACES,
+            [("run", &["run"][..])],
+        );
+    }
+}
+
+mod with_shared_across_worlds {
+    mod a {
+        wasmtime::component::bindgen!({
+            inline: "
+                package example:logging;
+
+                interface logging {
+                    enum level { debug, info }
+                    log: func(level: level, msg: string);
+                }
+
+                world a {
+                    import logging;
+                }
+            ",
+        });
+    }
+
+    mod b {
+        wasmtime::component::bindgen!({
+            inline: "
+                package example:logging;
+
+                interface logging {
+                    enum level { debug, info }
+                    log: func(level: level, msg: string);
+                }
+
+                world b {
+                    import logging;
+                    use logging.{level};
+                    export run: func(level: level);
+                }
+            ",
+            with: {
+                "example:logging/logging": super::a::example::logging::logging,
+            },
+        });
+
+        use wasmtime::Store;
+
+        // Both worlds use the same `Level` type so values flow between them
+        // without any conversions.
+        fn _run(b: &B, store: &mut Store<()>) -> wasmtime::Result<()> {
+            let level: super::a::example::logging::logging::Level =
+                example::logging::logging::Level::Info;
+            b.call_run(store, level)
+        }
+    }
+}
+
 mod trappable_error_type_and_versions {
     struct MyError;
 
@@ -1,5 +1,6 @@
 // The output of `bindgen!` should be compatible with `no_std` by default, so
-// test that here with a no_std crate.
+// test that here with a no_std crate, including the async and trappable
+// configurations.
 
 #![no_std]
 
@@ -8,7 +9,16 @@ extern crate std;
 macro_rules! gentest {
     ($id:ident $name:tt $path:tt) => {
         mod $id {
-            wasmtime::component::bindgen!(in $path);
+            mod sugar {
+                wasmtime::component::bindgen!(in $path);
+            }
+            mod async_ {
+                wasmtime::component::bindgen!({
+                    path: $path,
+                    imports: { default: async | trappable },
+                    exports: { default: async },
+                });
+            }
         }
     };
 }
@@ -180,6 +180,18 @@ const _: () = {
             foo::foo::chars::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/chars", &["take-char", "return-char"])];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/chars", &["take-char", "return-char"])];
         pub fn foo_foo_chars(&self) -> &exports::foo::foo::chars::Guest {
             &self.interface0
         }
@@ -182,6 +182,54 @@ const _: () = {
             foo::foo::conventions::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/conventions",
+                &[
+                    "kebab-case",
+                    "foo",
+                    "function-with-dashes",
+                    "function-with-no-weird-characters",
+                    "apple",
+                    "apple-pear",
+                    "apple-pear-grape",
+                    "a0",
+                    "is-XML",
+                    "explicit",
+                    "explicit-kebab",
+                    "bool",
+                ],
+            ),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/conventions",
+                &[
+                    "kebab-case",
+                    "foo",
+                    "function-with-dashes",
+                    "function-with-no-weird-characters",
+                    "apple",
+                    "apple-pear",
+                    "apple-pear-grape",
+                    "a0",
+                    "is-XML",
+                    "explicit",
+                    "explicit-kebab",
+                    "bool",
+                ],
+            ),
+        ];
         pub fn foo_foo_conventions(&self) -> &exports::foo::foo::conventions::Guest {
             &self.interface0
         }
@@ -179,6 +179,21 @@ const _: () = {
             a::b::interface_with_dead_type::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            ("a:b/interface-with-live-type", &["f"]),
+            ("a:b/interface-with-dead-type", &[]),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[];
     }
 };
 pub mod a {
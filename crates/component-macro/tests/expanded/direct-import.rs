@@ -208,5 +208,17 @@ const _: () = {
             Self::add_to_linker_imports::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[];
     }
 };
@@ -180,6 +180,44 @@ const _: () = {
             foo::foo::flegs::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/flegs",
+                &[
+                    "roundtrip-flag1",
+                    "roundtrip-flag2",
+                    "roundtrip-flag4",
+                    "roundtrip-flag8",
+                    "roundtrip-flag16",
+                    "roundtrip-flag32",
+                    "roundtrip-flag64",
+                ],
+            ),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/flegs",
+                &[
+                    "roundtrip-flag1",
+                    "roundtrip-flag2",
+                    "roundtrip-flag4",
+                    "roundtrip-flag8",
+                    "roundtrip-flag16",
+                    "roundtrip-flag32",
+                    "roundtrip-flag64",
+                ],
+            ),
+        ];
         pub fn foo_foo_flegs(&self) -> &exports::foo::foo::flegs::Guest {
             &self.interface0
         }
@@ -182,6 +182,22 @@ const _: () = {
             foo::foo::floats::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            ("foo:foo/floats", &["f32-param", "f64-param", "f32-result", "f64-result"]),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            ("foo:foo/floats", &["f32-param", "f64-param", "f32-result", "f64-result"]),
+        ];
         pub fn foo_foo_floats(&self) -> &exports::foo::foo::floats::Guest {
             &self.interface0
         }
@@ -180,6 +180,88 @@ const _: () = {
             foo::foo::lists::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/lists",
+                &[
+                    "list-u8-param",
+                    "list-u16-param",
+                    "list-u32-param",
+                    "list-u64-param",
+                    "list-s8-param",
+                    "list-s16-param",
+                    "list-s32-param",
+                    "list-s64-param",
+                    "list-f32-param",
+                    "list-f64-param",
+                    "list-u8-ret",
+                    "list-u16-ret",
+                    "list-u32-ret",
+                    "list-u64-ret",
+                    "list-s8-ret",
+                    "list-s16-ret",
+                    "list-s32-ret",
+                    "list-s64-ret",
+                    "list-f32-ret",
+                    "list-f64-ret",
+                    "tuple-list",
+                    "string-list-arg",
+                    "string-list-ret",
+                    "tuple-string-list",
+                    "string-list",
+                    "record-list",
+                    "record-list-reverse",
+                    "variant-list",
+                    "load-store-everything",
+                ],
+            ),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/lists",
+                &[
+                    "list-u8-param",
+                    "list-u16-param",
+                    "list-u32-param",
+                    "list-u64-param",
+                    "list-s8-param",
+                    "list-s16-param",
+                    "list-s32-param",
+                    "list-s64-param",
+                    "list-f32-param",
+                    "list-f64-param",
+                    "list-u8-ret",
+                    "list-u16-ret",
+                    "list-u32-ret",
+                    "list-u64-ret",
+                    "list-s8-ret",
+                    "list-s16-ret",
+                    "list-s32-ret",
+                    "list-s64-ret",
+                    "list-f32-ret",
+                    "list-f64-ret",
+                    "tuple-list",
+                    "string-list-arg",
+                    "string-list-ret",
+                    "tuple-string-list",
+                    "string-list",
+                    "record-list",
+                    "record-list-reverse",
+                    "variant-list",
+                    "load-store-everything",
+                ],
+            ),
+        ];
         pub fn foo_foo_lists(&self) -> &exports::foo::foo::lists::Guest {
             &self.interface0
         }
@@ -182,6 +182,18 @@ const _: () = {
             foo::foo::manyarg::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/manyarg", &["many-args", "big-argument"])];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/manyarg", &["many-args", "big-argument"])];
         pub fn foo_foo_manyarg(&self) -> &exports::foo::foo::manyarg::Guest {
             &self.interface0
         }
@@ -188,6 +188,18 @@ const _: () = {
             my::dep0_2_0::a::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("my:dep/a@0.1.0", &["x"]), ("my:dep/a@0.2.0", &["x"])];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("my:dep/a@0.1.0", &["x"]), ("my:dep/a@0.2.0", &["x"])];
         pub fn my_dep0_1_0_a(&self) -> &exports::my::dep0_1_0::a::Guest {
             &self.interface0
         }
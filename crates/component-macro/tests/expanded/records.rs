@@ -182,6 +182,52 @@ const _: () = {
             foo::foo::records::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/records",
+                &[
+                    "tuple-arg",
+                    "tuple-result",
+                    "empty-arg",
+                    "empty-result",
+                    "scalar-arg",
+                    "scalar-result",
+                    "flags-arg",
+                    "flags-result",
+                    "aggregate-arg",
+                    "aggregate-result",
+                    "typedef-inout",
+                ],
+            ),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/records",
+                &[
+                    "tuple-arg",
+                    "tuple-result",
+                    "empty-arg",
+                    "empty-result",
+                    "scalar-arg",
+                    "scalar-result",
+                    "flags-arg",
+                    "flags-result",
+                    "aggregate-arg",
+                    "aggregate-result",
+                    "typedef-inout",
+                ],
+            ),
+        ];
         pub fn foo_foo_records(&self) -> &exports::foo::foo::records::Guest {
             &self.interface0
         }
@@ -210,6 +210,29 @@ const _: () = {
             foo::foo::transitive_import::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/transitive-import", &[])];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/simple-export",
+                &["[constructor]a", "[static]a.static-a", "[method]a.method-a"],
+            ),
+            (
+                "foo:foo/export-using-import",
+                &["[constructor]a", "[static]a.static-a", "[method]a.method-a"],
+            ),
+            ("foo:foo/export-using-export1", &["[constructor]a"]),
+            ("foo:foo/export-using-export2", &["[constructor]b"]),
+        ];
         pub fn foo_foo_simple_export(&self) -> &exports::foo::foo::simple_export::Guest {
             &self.interface0
         }
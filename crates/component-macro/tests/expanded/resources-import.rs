@@ -351,6 +351,52 @@ const _: () = {
             >(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/resources",
+                &[
+                    "[constructor]bar",
+                    "[static]bar.static-a",
+                    "[method]bar.method-a",
+                    "bar-own-arg",
+                    "bar-borrow-arg",
+                    "bar-result",
+                    "tuple-own-arg",
+                    "tuple-borrow-arg",
+                    "tuple-result",
+                    "option-own-arg",
+                    "option-borrow-arg",
+                    "option-result",
+                    "result-own-arg",
+                    "result-borrow-arg",
+                    "result-result",
+                    "list-own-arg",
+                    "list-borrow-arg",
+                    "list-result",
+                    "record-own-arg",
+                    "record-borrow-arg",
+                    "record-result",
+                    "func-with-handle-typedef",
+                    "[constructor]fallible",
+                ],
+            ),
+            ("foo:foo/long-use-chain1", &[]),
+            ("foo:foo/long-use-chain2", &[]),
+            ("foo:foo/long-use-chain3", &[]),
+            ("foo:foo/long-use-chain4", &["foo"]),
+            ("foo:foo/transitive-interface-with-resource", &[]),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/uses-resource-transitively", &["handle"])];
         pub fn call_some_world_func2<S: wasmtime::AsContextMut>(
             &self,
             mut store: S,
@@ -181,6 +181,18 @@ const _: () = {
             http_fetch::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/http-types", &[]), ("http-fetch", &["fetch-request"])];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("http-handler", &["handle-request"])];
         pub fn http_handler(&self) -> &exports::http_handler::Guest {
             &self.interface0
         }
@@ -182,6 +182,18 @@ const _: () = {
             foo::foo::simple::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/simple", &["f1", "f2", "f3", "f4", "f5", "f6"])];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/simple", &["f1", "f2", "f3", "f4", "f5", "f6"])];
         pub fn foo_foo_simple(&self) -> &exports::foo::foo::simple::Guest {
             &self.interface0
         }
@@ -182,6 +182,28 @@ const _: () = {
             foo::foo::simple_lists::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/simple-lists",
+                &["simple-list1", "simple-list2", "simple-list3", "simple-list4"],
+            ),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/simple-lists",
+                &["simple-list1", "simple-list2", "simple-list3", "simple-list4"],
+            ),
+        ];
         pub fn foo_foo_simple_lists(&self) -> &exports::foo::foo::simple_lists::Guest {
             &self.interface0
         }
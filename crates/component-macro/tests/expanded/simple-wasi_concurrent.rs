@@ -178,6 +178,21 @@ const _: () = {
             foo::foo::wall_clock::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            ("foo:foo/wasi-filesystem", &["create-directory-at", "stat"]),
+            ("foo:foo/wall-clock", &[]),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[];
     }
 };
 pub mod foo {
@@ -216,6 +231,29 @@ pub mod foo {
                 #[component(name = "e")]
                 E,
             }
+            impl Errno {
+                pub const ALL: &'static [Errno] = &[Errno::E];
+            }
+            impl From<Errno> for u8 {
+                fn from(e: Errno) -> u8 {
+                    e as u8
+                }
+            }
+            impl TryFrom<u8> for Errno {
+                type Error = wasmtime::Error;
+                fn try_from(value: u8) -> wasmtime::Result<Errno> {
+                    match Errno::ALL.get(value as usize) {
+                        Some(e) => Ok(*e),
+                        None => {
+                            Err(
+                                wasmtime::component::__internal::anyhow::anyhow!(
+                                    "invalid discriminant for `Errno`: {value}"
+                                ),
+                            )
+                        }
+                    }
+                }
+            }
             impl Errno {
                 pub fn name(&self) -> &'static str {
                     match self {
@@ -180,6 +180,18 @@ const _: () = {
             foo::foo::anon::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/anon", &["option-test"])];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[("foo:foo/anon", &["option-test"])];
         pub fn foo_foo_anon(&self) -> &exports::foo::foo::anon::Guest {
             &self.interface0
         }
@@ -203,6 +215,29 @@ pub mod foo {
                 #[component(name = "failure")]
                 Failure,
             }
+            impl Error {
+                pub const ALL: &'static [Error] = &[Error::Success, Error::Failure];
+            }
+            impl From<Error> for u8 {
+                fn from(e: Error) -> u8 {
+                    e as u8
+                }
+            }
+            impl TryFrom<u8> for Error {
+                type Error = wasmtime::Error;
+                fn try_from(value: u8) -> wasmtime::Result<Error> {
+                    match Error::ALL.get(value as usize) {
+                        Some(e) => Ok(*e),
+                        None => {
+                            Err(
+                                wasmtime::component::__internal::anyhow::anyhow!(
+                                    "invalid discriminant for `Error`: {value}"
+                                ),
+                            )
+                        }
+                    }
+                }
+            }
             impl Error {
                 pub fn name(&self) -> &'static str {
                     match self {
@@ -292,6 +327,29 @@ pub mod exports {
                     #[component(name = "failure")]
                     Failure,
                 }
+                impl Error {
+                    pub const ALL: &'static [Error] = &[Error::Success, Error::Failure];
+                }
+                impl From<Error> for u8 {
+                    fn from(e: Error) -> u8 {
+                        e as u8
+                    }
+                }
+                impl TryFrom<u8> for Error {
+                    type Error = wasmtime::Error;
+                    fn try_from(value: u8) -> wasmtime::Result<Error> {
+                        match Error::ALL.get(value as usize) {
+                            Some(e) => Ok(*e),
+                            None => {
+                                Err(
+                                    wasmtime::component::__internal::anyhow::anyhow!(
+                                        "invalid discriminant for `Error`: {value}"
+                                    ),
+                                )
+                            }
+                        }
+                    }
+                }
                 impl Error {
                     pub fn name(&self) -> &'static str {
                         match self {
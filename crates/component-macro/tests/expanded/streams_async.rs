@@ -0,0 +1,592 @@
+/// Auto-generated bindings for a pre-instantiated version of a
+/// component which implements the world `the-world`.
+///
+/// This structure is created through [`TheWorldPre::new`] which
+/// takes a [`InstancePre`](wasmtime::component::InstancePre) that
+/// has been created through a [`Linker`](wasmtime::component::Linker).
+///
+/// For more information see [`TheWorld`] as well.
+pub struct TheWorldPre<T: 'static> {
+    instance_pre: wasmtime::component::InstancePre<T>,
+    indices: TheWorldIndices,
+}
+impl<T: 'static> Clone for TheWorldPre<T> {
+    fn clone(&self) -> Self {
+        Self {
+            instance_pre: self.instance_pre.clone(),
+            indices: self.indices.clone(),
+        }
+    }
+}
+impl<_T: 'static> TheWorldPre<_T> {
+    /// Creates a new copy of `TheWorldPre` bindings which can then
+    /// be used to instantiate into a particular store.
+    ///
+    /// This method may fail if the component behind `instance_pre`
+    /// does not have the required exports.
+    pub fn new(
+        instance_pre: wasmtime::component::InstancePre<_T>,
+    ) -> wasmtime::Result<Self> {
+        let indices = TheWorldIndices::new(&instance_pre)?;
+        Ok(Self { instance_pre, indices })
+    }
+    pub fn engine(&self) -> &wasmtime::Engine {
+        self.instance_pre.engine()
+    }
+    pub fn instance_pre(&self) -> &wasmtime::component::InstancePre<_T> {
+        &self.instance_pre
+    }
+    /// Instantiates a new instance of [`TheWorld`] within the
+    /// `store` provided.
+    ///
+    /// This function will use `self` as the pre-instantiated
+    /// instance to perform instantiation. Afterwards the preloaded
+    /// indices in `self` are used to lookup all exports on the
+    /// resulting instance.
+    pub fn instantiate(
+        &self,
+        mut store: impl wasmtime::AsContextMut<Data = _T>,
+    ) -> wasmtime::Result<TheWorld> {
+        let mut store = store.as_context_mut();
+        let instance = self.instance_pre.instantiate(&mut store)?;
+        self.indices.load(&mut store, &instance)
+    }
+}
+impl<_T: Send + 'static> TheWorldPre<_T> {
+    /// Same as [`Self::instantiate`], except with `async`.
+    pub async fn instantiate_async(
+        &self,
+        mut store: impl wasmtime::AsContextMut<Data = _T>,
+    ) -> wasmtime::Result<TheWorld> {
+        let mut store = store.as_context_mut();
+        let instance = self.instance_pre.instantiate_async(&mut store).await?;
+        self.indices.load(&mut store, &instance)
+    }
+}
+/// Auto-generated bindings for index of the exports of
+/// `the-world`.
+///
+/// This is an implementation detail of [`TheWorldPre`] and can
+/// be constructed if needed as well.
+///
+/// For more information see [`TheWorld`] as well.
+#[derive(Clone)]
+pub struct TheWorldIndices {
+    interface0: exports::foo::foo::streams::GuestIndices,
+}
+/// Auto-generated bindings for an instance a component which
+/// implements the world `the-world`.
+///
+/// This structure can be created through a number of means
+/// depending on your requirements and what you have on hand:
+///
+/// * The most convenient way is to use
+///   [`TheWorld::instantiate`] which only needs a
+///   [`Store`], [`Component`], and [`Linker`].
+///
+/// * Alternatively you can create a [`TheWorldPre`] ahead of
+///   time with a [`Component`] to front-load string lookups
+///   of exports once instead of per-instantiation. This
+///   method then uses [`TheWorldPre::instantiate`] to
+///   create a [`TheWorld`].
+///
+/// * If you've instantiated the instance yourself already
+///   then you can use [`TheWorld::new`].
+///
+/// These methods are all equivalent to one another and move
+/// around the tradeoff of what work is performed when.
+///
+/// [`Store`]: wasmtime::Store
+/// [`Component`]: wasmtime::component::Component
+/// [`Linker`]: wasmtime::component::Linker
+pub struct TheWorld {
+    interface0: exports::foo::foo::streams::Guest,
+}
+const _: () = {
+    #[allow(unused_imports)]
+    use wasmtime::component::__internal::anyhow;
+    impl TheWorldIndices {
+        /// Creates a new copy of `TheWorldIndices` bindings which can then
+        /// be used to instantiate into a particular store.
+        ///
+        /// This method may fail if the component does not have the
+        /// required exports.
+        pub fn new<_T>(
+            _instance_pre: &wasmtime::component::InstancePre<_T>,
+        ) -> wasmtime::Result<Self> {
+            let _component = _instance_pre.component();
+            let _instance_type = _instance_pre.instance_type();
+            let interface0 = exports::foo::foo::streams::GuestIndices::new(
+                _instance_pre,
+            )?;
+            Ok(TheWorldIndices { interface0 })
+        }
+        /// Uses the indices stored in `self` to load an instance
+        /// of [`TheWorld`] from the instance provided.
+        ///
+        /// Note that at this time this method will additionally
+        /// perform type-checks of all exports.
+        pub fn load(
+            &self,
+            mut store: impl wasmtime::AsContextMut,
+            instance: &wasmtime::component::Instance,
+        ) -> wasmtime::Result<TheWorld> {
+            let _ = &mut store;
+            let _instance = instance;
+            let interface0 = self.interface0.load(&mut store, &_instance)?;
+            Ok(TheWorld { interface0 })
+        }
+    }
+    impl TheWorld {
+        /// Convenience wrapper around [`TheWorldPre::new`] and
+        /// [`TheWorldPre::instantiate`].
+        pub fn instantiate<_T>(
+            store: impl wasmtime::AsContextMut<Data = _T>,
+            component: &wasmtime::component::Component,
+            linker: &wasmtime::component::Linker<_T>,
+        ) -> wasmtime::Result<TheWorld> {
+            let pre = linker.instantiate_pre(component)?;
+            TheWorldPre::new(pre)?.instantiate(store)
+        }
+        /// Convenience wrapper around [`TheWorldIndices::new`] and
+        /// [`TheWorldIndices::load`].
+        pub fn new(
+            mut store: impl wasmtime::AsContextMut,
+            instance: &wasmtime::component::Instance,
+        ) -> wasmtime::Result<TheWorld> {
+            let indices = TheWorldIndices::new(&instance.instance_pre(&store))?;
+            indices.load(&mut store, instance)
+        }
+        /// Convenience wrapper around [`TheWorldPre::new`] and
+        /// [`TheWorldPre::instantiate_async`].
+        pub async fn instantiate_async<_T>(
+            store: impl wasmtime::AsContextMut<Data = _T>,
+            component: &wasmtime::component::Component,
+            linker: &wasmtime::component::Linker<_T>,
+        ) -> wasmtime::Result<TheWorld>
+        where
+            _T: Send,
+        {
+            let pre = linker.instantiate_pre(component)?;
+            TheWorldPre::new(pre)?.instantiate_async(store).await
+        }
+        pub fn add_to_linker<T, D>(
+            linker: &mut wasmtime::component::Linker<T>,
+            host_getter: fn(&mut T) -> D::Data<'_>,
+        ) -> wasmtime::Result<()>
+        where
+            D: foo::foo::streams::HostWithStore + Send,
+            for<'a> D::Data<'a>: foo::foo::streams::Host + Send,
+            T: 'static + Send,
+        {
+            foo::foo::streams::add_to_linker::<T, D>(linker, host_getter)?;
+            Ok(())
+        }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/streams",
+                &[
+                    "[async]read-bytes",
+                    "[async]write-bytes",
+                    "[async]read-value",
+                    "[async]write-value",
+                    "[async]read-with-error",
+                ],
+            ),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/streams",
+                &[
+                    "[async]read-bytes",
+                    "[async]write-bytes",
+                    "[async]read-value",
+                    "[async]write-value",
+                    "[async]read-with-error",
+                ],
+            ),
+        ];
+        pub fn foo_foo_streams(&self) -> &exports::foo::foo::streams::Guest {
+            &self.interface0
+        }
+    }
+};
+pub mod foo {
+    pub mod foo {
+        #[allow(clippy::all)]
+        pub mod streams {
+            #[allow(unused_imports)]
+            use wasmtime::component::__internal::{anyhow, Box};
+            pub trait HostWithStore: wasmtime::component::HasData + Send {
+                fn read_bytes<T>(
+                    accessor: &wasmtime::component::Accessor<T, Self>,
+                ) -> impl ::core::future::Future<
+                    Output = wasmtime::component::StreamReader<u8>,
+                > + Send;
+                fn write_bytes<T>(
+                    accessor: &wasmtime::component::Accessor<T, Self>,
+                    bytes: wasmtime::component::StreamReader<u8>,
+                ) -> impl ::core::future::Future<Output = ()> + Send;
+                fn read_value<T>(
+                    accessor: &wasmtime::component::Accessor<T, Self>,
+                ) -> impl ::core::future::Future<
+                    Output = wasmtime::component::FutureReader<
+                        wasmtime::component::__internal::String,
+                    >,
+                > + Send;
+                fn write_value<T>(
+                    accessor: &wasmtime::component::Accessor<T, Self>,
+                    value: wasmtime::component::FutureReader<
+                        wasmtime::component::__internal::String,
+                    >,
+                ) -> impl ::core::future::Future<Output = ()> + Send;
+                fn read_with_error<T>(
+                    accessor: &wasmtime::component::Accessor<T, Self>,
+                ) -> impl ::core::future::Future<
+                    Output = (
+                        wasmtime::component::StreamReader<u8>,
+                        wasmtime::component::FutureReader<
+                            Result<(), wasmtime::component::__internal::String>,
+                        >,
+                    ),
+                > + Send;
+            }
+            pub trait Host: Send {}
+            impl<_T: Host + ?Sized + Send> Host for &mut _T {}
+            pub fn add_to_linker<T, D>(
+                linker: &mut wasmtime::component::Linker<T>,
+                host_getter: fn(&mut T) -> D::Data<'_>,
+            ) -> wasmtime::Result<()>
+            where
+                D: HostWithStore,
+                for<'a> D::Data<'a>: Host,
+                T: 'static + Send,
+            {
+                let mut inst = linker.instance("foo:foo/streams")?;
+                inst.func_wrap_concurrent(
+                    "[async]read-bytes",
+                    move |caller: &wasmtime::component::Accessor<T>, (): ()| {
+                        wasmtime::component::__internal::Box::pin(async move {
+                            let host = &caller.with_getter(host_getter);
+                            let r = <D as HostWithStore>::read_bytes(host).await;
+                            Ok((r,))
+                        })
+                    },
+                )?;
+                inst.func_wrap_concurrent(
+                    "[async]write-bytes",
+                    move |
+                        caller: &wasmtime::component::Accessor<T>,
+                        (arg0,): (wasmtime::component::StreamReader<u8>,)|
+                    {
+                        wasmtime::component::__internal::Box::pin(async move {
+                            let host = &caller.with_getter(host_getter);
+                            let r = <D as HostWithStore>::write_bytes(host, arg0).await;
+                            Ok(r)
+                        })
+                    },
+                )?;
+                inst.func_wrap_concurrent(
+                    "[async]read-value",
+                    move |caller: &wasmtime::component::Accessor<T>, (): ()| {
+                        wasmtime::component::__internal::Box::pin(async move {
+                            let host = &caller.with_getter(host_getter);
+                            let r = <D as HostWithStore>::read_value(host).await;
+                            Ok((r,))
+                        })
+                    },
+                )?;
+                inst.func_wrap_concurrent(
+                    "[async]write-value",
+                    move |
+                        caller: &wasmtime::component::Accessor<T>,
+                        (
+                            arg0,
+                        ): (
+                            wasmtime::component::FutureReader<
+                                wasmtime::component::__internal::String,
+                            >,
+                        )|
+                    {
+                        wasmtime::component::__internal::Box::pin(async move {
+                            let host = &caller.with_getter(host_getter);
+                            let r = <D as HostWithStore>::write_value(host, arg0).await;
+                            Ok(r)
+                        })
+                    },
+                )?;
+                inst.func_wrap_concurrent(
+                    "[async]read-with-error",
+                    move |caller: &wasmtime::component::Accessor<T>, (): ()| {
+                        wasmtime::component::__internal::Box::pin(async move {
+                            let host = &caller.with_getter(host_getter);
+                            let r = <D as HostWithStore>::read_with_error(host).await;
+                            Ok((r,))
+                        })
+                    },
+                )?;
+                Ok(())
+            }
+        }
+    }
+}
+pub mod exports {
+    pub mod foo {
+        pub mod foo {
+            #[allow(clippy::all)]
+            pub mod streams {
+                #[allow(unused_imports)]
+                use wasmtime::component::__internal::{anyhow, Box};
+                pub struct Guest {
+                    read_bytes: wasmtime::component::Func,
+                    write_bytes: wasmtime::component::Func,
+                    read_value: wasmtime::component::Func,
+                    write_value: wasmtime::component::Func,
+                    read_with_error: wasmtime::component::Func,
+                }
+                #[derive(Clone)]
+                pub struct GuestIndices {
+                    read_bytes: wasmtime::component::ComponentExportIndex,
+                    write_bytes: wasmtime::component::ComponentExportIndex,
+                    read_value: wasmtime::component::ComponentExportIndex,
+                    write_value: wasmtime::component::ComponentExportIndex,
+                    read_with_error: wasmtime::component::ComponentExportIndex,
+                }
+                impl GuestIndices {
+                    /// Constructor for [`GuestIndices`] which takes a
+                    /// [`Component`](wasmtime::component::Component) as input and can be executed
+                    /// before instantiation.
+                    ///
+                    /// This constructor can be used to front-load string lookups to find exports
+                    /// within a component.
+                    pub fn new<_T>(
+                        _instance_pre: &wasmtime::component::InstancePre<_T>,
+                    ) -> wasmtime::Result<GuestIndices> {
+                        let instance = _instance_pre
+                            .component()
+                            .get_export_index(None, "foo:foo/streams")
+                            .ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "no exported instance named `foo:foo/streams`"
+                                )
+                            })?;
+                        let mut lookup = move |name| {
+                            _instance_pre
+                                .component()
+                                .get_export_index(Some(&instance), name)
+                                .ok_or_else(|| {
+                                    anyhow::anyhow!(
+                                        "instance export `foo:foo/streams` does \
+                not have export `{name}`"
+                                    )
+                                })
+                        };
+                        let _ = &mut lookup;
+                        let read_bytes = lookup("[async]read-bytes")?;
+                        let write_bytes = lookup("[async]write-bytes")?;
+                        let read_value = lookup("[async]read-value")?;
+                        let write_value = lookup("[async]write-value")?;
+                        let read_with_error = lookup("[async]read-with-error")?;
+                        Ok(GuestIndices {
+                            read_bytes,
+                            write_bytes,
+                            read_value,
+                            write_value,
+                            read_with_error,
+                        })
+                    }
+                    pub fn load(
+                        &self,
+                        mut store: impl wasmtime::AsContextMut,
+                        instance: &wasmtime::component::Instance,
+                    ) -> wasmtime::Result<Guest> {
+                        let _instance = instance;
+                        let _instance_pre = _instance.instance_pre(&store);
+                        let _instance_type = _instance_pre.instance_type();
+                        let mut store = store.as_context_mut();
+                        let _ = &mut store;
+                        let read_bytes = *_instance
+                            .get_typed_func::<
+                                (),
+                                (wasmtime::component::StreamReader<u8>,),
+                            >(&mut store, &self.read_bytes)?
+                            .func();
+                        let write_bytes = *_instance
+                            .get_typed_func::<
+                                (wasmtime::component::StreamReader<u8>,),
+                                (),
+                            >(&mut store, &self.write_bytes)?
+                            .func();
+                        let read_value = *_instance
+                            .get_typed_func::<
+                                (),
+                                (
+                                    wasmtime::component::FutureReader<
+                                        wasmtime::component::__internal::String,
+                                    >,
+                                ),
+                            >(&mut store, &self.read_value)?
+                            .func();
+                        let write_value = *_instance
+                            .get_typed_func::<
+                                (
+                                    wasmtime::component::FutureReader<
+                                        wasmtime::component::__internal::String,
+                                    >,
+                                ),
+                                (),
+                            >(&mut store, &self.write_value)?
+                            .func();
+                        let read_with_error = *_instance
+                            .get_typed_func::<
+                                (),
+                                (
+                                    (
+                                        wasmtime::component::StreamReader<u8>,
+                                        wasmtime::component::FutureReader<
+                                            Result<(), wasmtime::component::__internal::String>,
+                                        >,
+                                    ),
+                                ),
+                            >(&mut store, &self.read_with_error)?
+                            .func();
+                        Ok(Guest {
+                            read_bytes,
+                            write_bytes,
+                            read_value,
+                            write_value,
+                            read_with_error,
+                        })
+                    }
+                }
+                impl Guest {
+                    pub async fn call_read_bytes<_T, _D>(
+                        &self,
+                        accessor: &wasmtime::component::Accessor<_T, _D>,
+                    ) -> wasmtime::Result<wasmtime::component::StreamReader<u8>>
+                    where
+                        _T: Send,
+                        _D: wasmtime::component::HasData,
+                    {
+                        let callee = unsafe {
+                            wasmtime::component::TypedFunc::<
+                                (),
+                                (wasmtime::component::StreamReader<u8>,),
+                            >::new_unchecked(self.read_bytes)
+                        };
+                        let ((ret0,), _) = callee.call_concurrent(accessor, ()).await?;
+                        Ok(ret0)
+                    }
+                    pub async fn call_write_bytes<_T, _D>(
+                        &self,
+                        accessor: &wasmtime::component::Accessor<_T, _D>,
+                        arg0: wasmtime::component::StreamReader<u8>,
+                    ) -> wasmtime::Result<()>
+                    where
+                        _T: Send,
+                        _D: wasmtime::component::HasData,
+                    {
+                        let callee = unsafe {
+                            wasmtime::component::TypedFunc::<
+                                (wasmtime::component::StreamReader<u8>,),
+                                (),
+                            >::new_unchecked(self.write_bytes)
+                        };
+                        let ((), _) = callee.call_concurrent(accessor, (arg0,)).await?;
+                        Ok(())
+                    }
+                    pub async fn call_read_value<_T, _D>(
+                        &self,
+                        accessor: &wasmtime::component::Accessor<_T, _D>,
+                    ) -> wasmtime::Result<
+                        wasmtime::component::FutureReader<
+                            wasmtime::component::__internal::String,
+                        >,
+                    >
+                    where
+                        _T: Send,
+                        _D: wasmtime::component::HasData,
+                    {
+                        let callee = unsafe {
+                            wasmtime::component::TypedFunc::<
+                                (),
+                                (
+                                    wasmtime::component::FutureReader<
+                                        wasmtime::component::__internal::String,
+                                    >,
+                                ),
+                            >::new_unchecked(self.read_value)
+                        };
+                        let ((ret0,), _) = callee.call_concurrent(accessor, ()).await?;
+                        Ok(ret0)
+                    }
+                    pub async fn call_write_value<_T, _D>(
+                        &self,
+                        accessor: &wasmtime::component::Accessor<_T, _D>,
+                        arg0: wasmtime::component::FutureReader<
+                            wasmtime::component::__internal::String,
+                        >,
+                    ) -> wasmtime::Result<()>
+                    where
+                        _T: Send,
+                        _D: wasmtime::component::HasData,
+                    {
+                        let callee = unsafe {
+                            wasmtime::component::TypedFunc::<
+                                (
+                                    wasmtime::component::FutureReader<
+                                        wasmtime::component::__internal::String,
+                                    >,
+                                ),
+                                (),
+                            >::new_unchecked(self.write_value)
+                        };
+                        let ((), _) = callee.call_concurrent(accessor, (arg0,)).await?;
+                        Ok(())
+                    }
+                    pub async fn call_read_with_error<_T, _D>(
+                        &self,
+                        accessor: &wasmtime::component::Accessor<_T, _D>,
+                    ) -> wasmtime::Result<
+                        (
+                            wasmtime::component::StreamReader<u8>,
+                            wasmtime::component::FutureReader<
+                                Result<(), wasmtime::component::__internal::String>,
+                            >,
+                        ),
+                    >
+                    where
+                        _T: Send,
+                        _D: wasmtime::component::HasData,
+                    {
+                        let callee = unsafe {
+                            wasmtime::component::TypedFunc::<
+                                (),
+                                (
+                                    (
+                                        wasmtime::component::StreamReader<u8>,
+                                        wasmtime::component::FutureReader<
+                                            Result<(), wasmtime::component::__internal::String>,
+                                        >,
+                                    ),
+                                ),
+                            >::new_unchecked(self.read_with_error)
+                        };
+                        let ((ret0,), _) = callee.call_concurrent(accessor, ()).await?;
+                        Ok(ret0)
+                    }
+                }
+            }
+        }
+    }
+}
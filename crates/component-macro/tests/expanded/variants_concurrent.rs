@@ -182,6 +182,70 @@ const _: () = {
             foo::foo::variants::add_to_linker::<T, D>(linker, host_getter)?;
             Ok(())
         }
+        /// The names of the interfaces imported by this world,
+        /// along with the names of their functions.
+        pub const IMPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/variants",
+                &[
+                    "e1-arg",
+                    "e1-result",
+                    "v1-arg",
+                    "v1-result",
+                    "bool-arg",
+                    "bool-result",
+                    "option-arg",
+                    "option-result",
+                    "casts",
+                    "result-arg",
+                    "result-result",
+                    "return-result-sugar",
+                    "return-result-sugar2",
+                    "return-result-sugar3",
+                    "return-result-sugar4",
+                    "return-option-sugar",
+                    "return-option-sugar2",
+                    "result-simple",
+                    "is-clone-arg",
+                    "is-clone-return",
+                ],
+            ),
+        ];
+        /// The names of the interfaces exported by this world,
+        /// along with the names of their functions.
+        pub const EXPORTED_INTERFACES: &'static [(
+            &'static str,
+            &'static [&'static str],
+        )] = &[
+            (
+                "foo:foo/variants",
+                &[
+                    "e1-arg",
+                    "e1-result",
+                    "v1-arg",
+                    "v1-result",
+                    "bool-arg",
+                    "bool-result",
+                    "option-arg",
+                    "option-result",
+                    "casts",
+                    "result-arg",
+                    "result-result",
+                    "return-result-sugar",
+                    "return-result-sugar2",
+                    "return-result-sugar3",
+                    "return-result-sugar4",
+                    "return-option-sugar",
+                    "return-option-sugar2",
+                    "result-simple",
+                    "is-clone-arg",
+                    "is-clone-return",
+                ],
+            ),
+        ];
         pub fn foo_foo_variants(&self) -> &exports::foo::foo::variants::Guest {
             &self.interface0
         }
@@ -203,6 +267,29 @@ pub mod foo {
                 #[component(name = "a")]
                 A,
             }
+            impl E1 {
+                pub const ALL: &'static [E1] = &[E1::A];
+            }
+            impl From<E1> for u8 {
+                fn from(e: E1) -> u8 {
+                    e as u8
+                }
+            }
+            impl TryFrom<u8> for E1 {
+                type Error = wasmtime::Error;
+                fn try_from(value: u8) -> wasmtime::Result<E1> {
+                    match E1::ALL.get(value as usize) {
+                        Some(e) => Ok(*e),
+                        None => {
+                            Err(
+                                wasmtime::component::__internal::anyhow::anyhow!(
+                                    "invalid discriminant for `E1`: {value}"
+                                ),
+                            )
+                        }
+                    }
+                }
+            }
             impl core::fmt::Debug for E1 {
                 fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     match self {
@@ -414,6 +501,29 @@ pub mod foo {
                 #[component(name = "bad2")]
                 Bad2,
             }
+            impl MyErrno {
+                pub const ALL: &'static [MyErrno] = &[MyErrno::Bad1, MyErrno::Bad2];
+            }
+            impl From<MyErrno> for u8 {
+                fn from(e: MyErrno) -> u8 {
+                    e as u8
+                }
+            }
+            impl TryFrom<u8> for MyErrno {
+                type Error = wasmtime::Error;
+                fn try_from(value: u8) -> wasmtime::Result<MyErrno> {
+                    match MyErrno::ALL.get(value as usize) {
+                        Some(e) => Ok(*e),
+                        None => {
+                            Err(
+                                wasmtime::component::__internal::anyhow::anyhow!(
+                                    "invalid discriminant for `MyErrno`: {value}"
+                                ),
+                            )
+                        }
+                    }
+                }
+            }
             impl MyErrno {
                 pub fn name(&self) -> &'static str {
                     match self {
@@ -896,6 +1006,29 @@ pub mod exports {
                     #[component(name = "a")]
                     A,
                 }
+                impl E1 {
+                    pub const ALL: &'static [E1] = &[E1::A];
+                }
+                impl From<E1> for u8 {
+                    fn from(e: E1) -> u8 {
+                        e as u8
+                    }
+                }
+                impl TryFrom<u8> for E1 {
+                    type Error = wasmtime::Error;
+                    fn try_from(value: u8) -> wasmtime::Result<E1> {
+                        match E1::ALL.get(value as usize) {
+                            Some(e) => Ok(*e),
+                            None => {
+                                Err(
+                                    wasmtime::component::__internal::anyhow::anyhow!(
+                                        "invalid discriminant for `E1`: {value}"
+                                    ),
+                                )
+                            }
+                        }
+                    }
+                }
                 impl core::fmt::Debug for E1 {
                     fn fmt(
                         &self,
@@ -1162,6 +1295,29 @@ pub mod exports {
                     #[component(name = "bad2")]
                     Bad2,
                 }
+                impl MyErrno {
+                    pub const ALL: &'static [MyErrno] = &[MyErrno::Bad1, MyErrno::Bad2];
+                }
+                impl From<MyErrno> for u8 {
+                    fn from(e: MyErrno) -> u8 {
+                        e as u8
+                    }
+                }
+                impl TryFrom<u8> for MyErrno {
+                    type Error = wasmtime::Error;
+                    fn try_from(value: u8) -> wasmtime::Result<MyErrno> {
+                        match MyErrno::ALL.get(value as usize) {
+                            Some(e) => Ok(*e),
+                            None => {
+                                Err(
+                                    wasmtime::component::__internal::anyhow::anyhow!(
+                                        "invalid discriminant for `MyErrno`: {value}"
+                                    ),
+                                )
+                            }
+                        }
+                    }
+                }
                 impl MyErrno {
                     pub fn name(&self) -> &'static str {
                         match self {
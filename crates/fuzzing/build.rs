@@ -25,6 +25,7 @@ fn main() {
             path,
             contents: _,
             config,
+            expected_failures: _,
         } = test;
         println!("cargo:rerun-if-changed={}", path.to_str().unwrap());
         code.push_str(&format!(
@@ -33,6 +34,8 @@ fn main() {
                     path: {path:?}.into(),
                     contents: include_str!({path:?}).into(),
                     config: wasmtime_test_util::wast::{config:?},
+                    expected_failures: wasmtime_test_util::wast::WastExpectations::builtin()
+                        .failures_for({path:?}.as_ref()),
                 }}
             }},"
         ));
@@ -144,10 +144,20 @@ impl Config {
             legacy_exceptions: _,
 
             hogs_memory: _,
+            requires_pooling: _,
+            both_allocators: _,
+            mpk: _,
             nan_canonicalization: _,
             gc_types: _,
             stack_switching: _,
             spec_test: _,
+            flaky: _,
+            requires_avx: _,
+            requires_avx2: _,
+            requires_avx512: _,
+            max_memories,
+            max_tables,
+            max_table_elements,
         } = test.config;
 
         // Enable/disable some proposals that aren't configurable in wasm-smith
@@ -187,6 +197,9 @@ impl Config {
         } else {
             config.max_memories = 1;
         }
+        if let Some(n) = max_memories {
+            config.max_memories = config.max_memories.max(n as usize);
+        }
 
         if let Some(n) = &mut self.wasmtime.memory_config.memory_reservation {
             *n = (*n).max(limits::MEMORY_SIZE as u64);
@@ -229,6 +242,17 @@ impl Config {
                 .component_instance_size
                 .max(limits::CORE_INSTANCE_SIZE);
             pooling.total_stacks = pooling.total_stacks.max(limits::TOTAL_STACKS);
+
+            // Some tests need more than the limits above.
+            if let Some(n) = max_memories {
+                pooling.max_memories_per_module = pooling.max_memories_per_module.max(n);
+            }
+            if let Some(n) = max_tables {
+                pooling.max_tables_per_module = pooling.max_tables_per_module.max(n);
+            }
+            if let Some(n) = max_table_elements {
+                pooling.table_elements = pooling.table_elements.max(n);
+            }
         }
 
         // Return the test configuration that this fuzz configuration represents
@@ -241,6 +265,14 @@ impl Config {
                     wasmtime_test_util::wast::Collector::DeferredReferenceCounting
                 }
             },
+            regalloc_algorithm: match self.wasmtime.regalloc_algorithm {
+                RegallocAlgorithm::Backtracking => {
+                    wasmtime_test_util::wast::RegallocAlgorithm::Backtracking
+                }
+                RegallocAlgorithm::SinglePass => {
+                    wasmtime_test_util::wast::RegallocAlgorithm::SinglePass
+                }
+            },
             pooling: matches!(
                 self.wasmtime.strategy,
                 InstanceAllocationStrategy::Pooling(_)
@@ -254,6 +286,7 @@ impl Config {
                 }
                 CompilerStrategy::Winch => wasmtime_test_util::wast::Compiler::Winch,
             },
+            ignore_skip_lists: false,
         }
     }
 
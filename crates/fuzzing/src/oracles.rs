@@ -320,11 +320,21 @@ fn compile_module(
         e.to_string().to_lowercase().contains("proof-carrying-code")
     }
 
+    fn is_regalloc_error(e: &anyhow::Error) -> bool {
+        // NOTE: please keep this predicate in sync with the display format of CodegenError,
+        // defined in `wasmtime/cranelift/codegen/src/result.rs`
+        let s = e.to_string().to_lowercase();
+        s.contains("regalloc validation errors") || s.contains("register allocation error")
+    }
+
     match config.compile(engine, bytes) {
         Ok(module) => Some(module),
         Err(e) if is_pcc_error(&e) => {
             panic!("pcc error in input: {e:#?}");
         }
+        Err(e) if is_regalloc_error(&e) => {
+            panic!("regalloc error in input: {e:#?}");
+        }
         Err(_) if known_valid == KnownValid::No => None,
         Err(e) => {
             if let generators::InstanceAllocationStrategy::Pooling(c) = &config.wasmtime.strategy {
@@ -725,6 +735,11 @@ pub fn wast_test(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<()> {
         return Err(arbitrary::Error::IncorrectFormat);
     }
 
+    // Tests which require the pooling allocator are only meaningful with it.
+    if test.config.requires_pooling() && !wast_config.pooling {
+        return Err(arbitrary::Error::IncorrectFormat);
+    }
+
     // Winch requires AVX and AVX2 for SIMD tests to pass so don't run the test
     // if either isn't enabled.
     if fuzz_config.wasmtime.compiler_strategy == CompilerStrategy::Winch
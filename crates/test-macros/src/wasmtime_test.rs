@@ -282,6 +282,8 @@ fn expand(test_config: &TestConfig, func: Fn) -> Result<TokenStream> {
                         compiler,
                         pooling: false,
                         collector: wasmtime_test_util::wast::Collector::Auto,
+                        regalloc_algorithm: wasmtime_test_util::wast::RegallocAlgorithm::Auto,
+                        ignore_skip_lists: false,
                     },
                 );
                 let result = #func_name(&mut config) #await_;
@@ -1,5 +1,7 @@
 use crate::wast;
-use wasmtime::Config;
+use anyhow::{Result, bail};
+use wasmtime::{Config, Enabled, Engine, InstanceAllocationStrategy, PoolingAllocationConfig};
+use wasmtime_wast::{Async, SpectestConfig, WastContext};
 
 /// Helper method to apply `wast_config` to `config`.
 pub fn apply_wast_config(config: &mut Config, wast_config: &wast::WastConfig) {
@@ -20,6 +22,21 @@ pub fn apply_wast_config(config: &mut Config, wast_config: &wast::WastConfig) {
         Collector::Null => wasmtime::Collector::Null,
         Collector::DeferredReferenceCounting => wasmtime::Collector::DeferredReferenceCounting,
     });
+    if let Some(algorithm) = cranelift_regalloc_algorithm(wast_config) {
+        config.cranelift_regalloc_algorithm(algorithm);
+    }
+}
+
+/// Returns the register allocator that [`apply_wast_config`] selects for
+/// `wast_config`, or `None` to leave Cranelift's default.
+pub fn cranelift_regalloc_algorithm(
+    wast_config: &wast::WastConfig,
+) -> Option<wasmtime::RegallocAlgorithm> {
+    match wast_config.regalloc_algorithm {
+        wast::RegallocAlgorithm::Auto => None,
+        wast::RegallocAlgorithm::Backtracking => Some(wasmtime::RegallocAlgorithm::Backtracking),
+        wast::RegallocAlgorithm::SinglePass => Some(wasmtime::RegallocAlgorithm::SinglePass),
+    }
 }
 
 /// Helper method to apply `test_config` to `config`.
@@ -49,8 +66,18 @@ pub fn apply_test_config(config: &mut Config, test_config: &wast::TestConfig) {
         stack_switching,
 
         hogs_memory: _,
+        requires_pooling: _,
+        both_allocators: _,
+        mpk: _,
         gc_types: _,
         spec_test: _,
+        flaky: _,
+        requires_avx: _,
+        requires_avx2: _,
+        requires_avx512: _,
+        max_memories: _,
+        max_tables: _,
+        max_table_elements: _,
     } = *test_config;
     // Note that all of these proposals/features are currently default-off to
     // ensure that we annotate all tests accurately with what features they
@@ -110,3 +137,395 @@ pub fn apply_test_config(config: &mut Config, test_config: &wast::TestConfig) {
     #[expect(deprecated, reason = "forwarding legacy-exceptions")]
     config.wasm_legacy_exceptions(legacy_exceptions);
 }
+
+/// Applies the pooling allocator limits that `test_config` raises, such as
+/// `max_tables`, to `pool`.
+///
+/// This should be called after `pool` is configured with the default
+/// [`limits`](wast::limits) so that a test can raise exactly the limits it
+/// needs rather than being skipped under the pooling allocator.
+pub fn apply_test_limits(pool: &mut PoolingAllocationConfig, test_config: &wast::TestConfig) {
+    if let Some(n) = test_config.max_memories {
+        pool.max_memories_per_module(n);
+    }
+    if let Some(n) = test_config.max_tables {
+        pool.max_tables_per_module(n);
+    }
+    if let Some(n) = test_config.max_table_elements {
+        pool.table_elements(n);
+    }
+}
+
+/// Builds a [`Config`] to run a test configured with `test_config` under
+/// `wast_config`.
+///
+/// This applies [`apply_test_config`] and [`apply_wast_config`] and, when the
+/// test runs with the pooling allocator, configures the pool with the default
+/// [`limits`](wast::limits) raised by [`apply_test_limits`]. Settings specific
+/// to a particular harness, such as `async_support` or memory reservations for
+/// constrained hosts, are left at their defaults.
+pub fn build_engine_config(
+    wast_config: &wast::WastConfig,
+    test_config: &wast::TestConfig,
+) -> Config {
+    let mut config = Config::new();
+    apply_test_config(&mut config, test_config);
+    apply_wast_config(&mut config, wast_config);
+
+    if wast_config.pooling || test_config.requires_pooling() {
+        // Without guards and growth reservations, multi-memory tests would
+        // reserve more virtual memory than most hosts allow.
+        let multi_memory = test_config.multi_memory();
+        if multi_memory {
+            config.memory_reservation(wast::limits::MEMORY_SIZE as u64);
+            config.memory_reservation_for_growth(0);
+            config.memory_guard_size(0);
+        }
+
+        let mut pool = PoolingAllocationConfig::default();
+        pool.total_memories(wast::limits::MEMORIES * 2)
+            .max_memory_protection_keys(2)
+            .max_memory_size(wast::limits::MEMORY_SIZE)
+            .max_memories_per_module(if multi_memory {
+                wast::limits::MEMORIES_PER_MODULE
+            } else {
+                1
+            })
+            .max_tables_per_module(wast::limits::TABLES_PER_MODULE);
+        apply_test_limits(&mut pool, test_config);
+        // Tests which exercise MPK always force it on, as do all pooling
+        // tests when `WASMTIME_TEST_FORCE_MPK` is set.
+        if test_config.mpk() || std::env::var("WASMTIME_TEST_FORCE_MPK").is_ok() {
+            pool.memory_protection_keys(Enabled::Yes);
+        }
+        config.allocation_strategy(InstanceAllocationStrategy::Pooling(pool));
+    }
+    config
+}
+
+/// Runs `test` with `engine`, checking that it behaves as expected under
+/// `config`.
+///
+/// The `engine` should be configured for the test with [`apply_test_config`]
+/// and [`apply_wast_config`]. If the test is expected to fail under `config`,
+/// as determined by [`wast::WastTest::should_fail`], then this returns `Ok`
+/// when it fails and an error if it unexpectedly succeeds.
+pub fn run_wast_test(
+    test: &wast::WastTest,
+    config: &wast::WastConfig,
+    engine: &Engine,
+) -> Result<()> {
+    let async_ = if engine.is_async() {
+        Async::Yes
+    } else {
+        Async::No
+    };
+    let mut wast_context = WastContext::new(engine, async_, |_store| {});
+    wast_context.generate_dwarf(true);
+    let result = wast_context
+        .register_spectest(&SpectestConfig {
+            use_shared_memory: true,
+            suppress_prints: true,
+        })
+        .and_then(|()| {
+            wast_context.run_wast(test.path.to_str().unwrap(), test.contents.as_bytes())
+        });
+
+    if let Some(reason) = test.should_fail_reason(config) {
+        if result.is_ok() {
+            bail!("this test is flagged as should-fail ({reason}) but it succeeded")
+        }
+        Ok(())
+    } else {
+        result
+    }
+}
+
+/// Runs `test` under `config` with `engine`, ignoring its entries in
+/// `tests/wast-expectations.toml`, to check whether they are stale.
+///
+/// Returns the expected failure for which `test` would be skipped if the test
+/// passes anyway; its entry in the manifest can then be removed. Tests which
+/// aren't listed in the manifest, or which are skipped for other reasons such
+/// as an unsupported proposal, aren't run and return `None`.
+pub fn find_stale_expectation(
+    test: &wast::WastTest,
+    config: &wast::WastConfig,
+    engine: &Engine,
+) -> Option<wast::ExpectedFailure> {
+    let Some(wast::SkipReason::ExpectedFailure(failure)) = test.should_fail_reason(config) else {
+        return None;
+    };
+    let config = wast::WastConfig {
+        compiler: config.compiler,
+        pooling: config.pooling,
+        collector: config.collector,
+        regalloc_algorithm: config.regalloc_algorithm,
+        ignore_skip_lists: true,
+    };
+    run_wast_test(test, &config, engine)
+        .is_ok()
+        .then_some(failure)
+}
+
+/// Runs `test` under each of `compilers`, requiring that they agree on whether
+/// it passes.
+///
+/// Each run checks the assertions of the test itself, so this is a
+/// differential test between compilers, such as those returned by
+/// [`wast::WastTest::runnable_compilers`]. The `engine` function creates an
+/// engine for each configuration, as for [`run_wast_test`].
+pub fn run_wast_test_differential(
+    test: &wast::WastTest,
+    compilers: &[wast::Compiler],
+    engine: impl Fn(&wast::WastConfig) -> Result<Engine>,
+) -> Result<()> {
+    let mut passed = Vec::new();
+    let mut failed = Vec::new();
+    for &compiler in compilers {
+        let config = wast::WastConfig {
+            compiler,
+            pooling: false,
+            collector: test.collectors()[0],
+            regalloc_algorithm: wast::RegallocAlgorithm::Auto,
+            ignore_skip_lists: false,
+        };
+        match engine(&config).and_then(|engine| run_wast_test(test, &config, &engine)) {
+            Ok(()) => passed.push(compiler),
+            Err(e) => failed.push((compiler, e)),
+        }
+    }
+    match failed.into_iter().next() {
+        None => Ok(()),
+        Some((compiler, e)) if passed.is_empty() => Err(e.context(format!(
+            "test failed under every compiler, first {compiler:?}"
+        ))),
+        Some((compiler, e)) => Err(e.context(format!(
+            "test passed under {passed:?} but failed under {compiler:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wast::{
+        Collector, Compiler, ExpectedFailure, RegallocAlgorithm, WastConfig, WastTest,
+    };
+
+    fn native_test(contents: &str) -> (WastTest, WastConfig, Engine) {
+        let config = WastConfig {
+            compiler: Compiler::CraneliftNative,
+            pooling: false,
+            collector: Collector::Auto,
+            regalloc_algorithm: RegallocAlgorithm::Auto,
+            ignore_skip_lists: false,
+        };
+        let test = WastTest {
+            path: "tests/misc_testsuite/test.wast".into(),
+            contents: contents.to_string(),
+            config: Default::default(),
+            expected_failures: vec![ExpectedFailure {
+                compiler: Some(Compiler::CraneliftNative),
+                target_arch: None,
+                pooling: None,
+                missing_host_features: Vec::new(),
+            }],
+        };
+        let mut cfg = Config::new();
+        apply_test_config(&mut cfg, &test.config);
+        apply_wast_config(&mut cfg, &config);
+        (test, config, Engine::new(&cfg).unwrap())
+    }
+
+    #[test]
+    fn stale_expectations() {
+        // A test that passes despite being listed as an expected failure.
+        let (test, config, engine) = native_test(
+            r#"
+                (module (func (export "f") (result i32) i32.const 1))
+                (assert_return (invoke "f") (i32.const 1))
+            "#,
+        );
+        assert_eq!(
+            find_stale_expectation(&test, &config, &engine),
+            test.expected_failures.first().cloned()
+        );
+
+        // A test that still fails.
+        let (test, config, engine) = native_test(
+            r#"
+                (module (func (export "f") (result i32) i32.const 1))
+                (assert_return (invoke "f") (i32.const 2))
+            "#,
+        );
+        assert_eq!(find_stale_expectation(&test, &config, &engine), None);
+    }
+
+    #[test]
+    fn regalloc_algorithm() {
+        let regalloc_setting = |regalloc_algorithm| {
+            cranelift_regalloc_algorithm(&WastConfig {
+                compiler: Compiler::CraneliftNative,
+                pooling: false,
+                collector: Collector::Auto,
+                regalloc_algorithm,
+                ignore_skip_lists: false,
+            })
+        };
+        assert_eq!(regalloc_setting(RegallocAlgorithm::Auto), None);
+        assert_eq!(
+            regalloc_setting(RegallocAlgorithm::Backtracking),
+            Some(wasmtime::RegallocAlgorithm::Backtracking)
+        );
+        assert_eq!(
+            regalloc_setting(RegallocAlgorithm::SinglePass),
+            Some(wasmtime::RegallocAlgorithm::SinglePass)
+        );
+
+        // The resulting configuration can compile and run tests.
+        let (test, mut config, _) = native_test(
+            r#"
+                (module (func (export "f") (result i32) i32.const 1))
+                (assert_return (invoke "f") (i32.const 1))
+            "#,
+        );
+        config.regalloc_algorithm = RegallocAlgorithm::SinglePass;
+        config.ignore_skip_lists = true;
+        let mut cfg = Config::new();
+        apply_test_config(&mut cfg, &test.config);
+        apply_wast_config(&mut cfg, &config);
+        run_wast_test(&test, &config, &Engine::new(&cfg).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn build_gc_engine_config() {
+        let contents = r#";;! gc = true
+            (module
+                (type $s (struct (field i32)))
+                (func (export "f") (result i32)
+                    (struct.get $s 0 (struct.new $s (i32.const 7)))))
+            (assert_return (invoke "f") (i32.const 7))
+        "#;
+        let test = WastTest {
+            path: "tests/misc_testsuite/gc/test.wast".into(),
+            contents: contents.to_string(),
+            config: crate::wast::parse_test_config(contents, ";;!").unwrap(),
+            expected_failures: Vec::new(),
+        };
+        assert!(test.config.gc());
+        for pooling in [false, true] {
+            let config = WastConfig {
+                compiler: Compiler::CraneliftNative,
+                pooling,
+                collector: Collector::DeferredReferenceCounting,
+                regalloc_algorithm: RegallocAlgorithm::Auto,
+                ignore_skip_lists: false,
+            };
+            let engine = Engine::new(&build_engine_config(&config, &test.config)).unwrap();
+            run_wast_test(&test, &config, &engine).unwrap();
+        }
+    }
+
+    #[test]
+    fn differential() {
+        let contents = r#"
+            (module (func (export "f") (result i32 i32) i32.const 1 i32.const 2))
+            (assert_return (invoke "f") (i32.const 1) (i32.const 2))
+        "#;
+        let test = WastTest {
+            path: "tests/misc_testsuite/test.wast".into(),
+            contents: contents.to_string(),
+            config: Default::default(),
+            expected_failures: Vec::new(),
+        };
+        let compilers = test.runnable_compilers();
+        assert!(compilers.contains(&Compiler::CraneliftNative));
+        assert!(compilers.contains(&Compiler::Winch));
+        let compilers = [Compiler::CraneliftNative, Compiler::Winch];
+
+        // Creates engines which, under `diverging`, reject the test's
+        // multi-value function.
+        let test_config = &test.config;
+        let engine = |diverging: Option<Compiler>| {
+            move |config: &WastConfig| {
+                let mut cfg = build_engine_config(config, test_config);
+                cfg.wasm_multi_value(Some(config.compiler) != diverging);
+                Engine::new(&cfg)
+            }
+        };
+        run_wast_test_differential(&test, &compilers, engine(None)).unwrap();
+
+        let err = run_wast_test_differential(&test, &compilers, engine(Some(Compiler::Winch)))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "test passed under [CraneliftNative] but failed under Winch"
+        );
+
+        let err = run_wast_test_differential(&test, &compilers[..1], |config| {
+            let mut cfg = build_engine_config(config, &test.config);
+            cfg.wasm_multi_value(false);
+            Engine::new(&cfg)
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "test failed under every compiler, first CraneliftNative"
+        );
+    }
+
+    #[test]
+    fn misc_testsuite_wat() {
+        // `*.wat` tests are found alongside `*.wast` tests, with their
+        // configuration, and run as a script defining their module.
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let tests = crate::wast::find_tests(&root).unwrap();
+        let test = tests
+            .iter()
+            .find(|t| t.path.ends_with("tests/misc_testsuite/issue11563.wat"))
+            .unwrap();
+        assert!(test.config.gc());
+        assert!(test.config.exceptions());
+
+        let config = WastConfig {
+            compiler: Compiler::CraneliftNative,
+            pooling: false,
+            collector: test.collectors()[0],
+            regalloc_algorithm: RegallocAlgorithm::Auto,
+            ignore_skip_lists: false,
+        };
+        let engine = Engine::new(&build_engine_config(&config, &test.config)).unwrap();
+        run_wast_test(test, &config, &engine).unwrap();
+    }
+
+    #[test]
+    fn test_limits() {
+        use crate::wast::{limits, parse_test_config};
+        use wasmtime::{InstanceAllocationStrategy, Module};
+
+        let tables = "(table 0 funcref)".repeat(limits::TABLES_PER_MODULE as usize + 1);
+        let wat = format!(";;! reference_types = true\n;;! max_tables = 8\n(module {tables})");
+        let test_config: wast::TestConfig = parse_test_config(&wat, ";;!").unwrap();
+        assert_eq!(test_config.max_tables, Some(8));
+
+        let compiles = |test_config: &wast::TestConfig| {
+            let mut pool = PoolingAllocationConfig::default();
+            pool.total_memories(1)
+                .total_tables(10)
+                .max_tables_per_module(limits::TABLES_PER_MODULE);
+            apply_test_limits(&mut pool, test_config);
+            let mut cfg = Config::new();
+            apply_test_config(&mut cfg, test_config);
+            cfg.allocation_strategy(InstanceAllocationStrategy::Pooling(pool));
+            let engine = Engine::new(&cfg).unwrap();
+            Module::new(&engine, &wat).is_ok()
+        };
+        assert!(compiles(&test_config));
+        let defaults = wast::TestConfig {
+            max_tables: None,
+            ..test_config.clone()
+        };
+        assert!(!compiles(&defaults));
+    }
+}
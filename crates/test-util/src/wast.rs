@@ -5,6 +5,7 @@ use std::fmt;
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 /// Limits for running wast tests.
 ///
@@ -28,31 +29,153 @@ pub mod limits {
     pub const TOTAL_STACKS: u32 = 10;
 }
 
-/// Local all `*.wast` tests under `root` which should be the path to the root
-/// of the wasmtime repository.
+/// The directories under `tests` which hold the test suites.
+const SPEC_TESTSUITE: &str = "spec_testsuite";
+const MISC_TESTSUITE: &str = "misc_testsuite";
+const COMPONENT_TESTSUITE: &str = "component-model";
+
+/// Expand `tests` and `configs` into every pair of a test and a configuration
+/// that it should pass under, i.e. for which [`WastTest::should_fail`] is
+/// false.
+///
+/// Each pair can be run independently, e.g. by a thread pool.
+pub fn runnable_pairs<'a>(
+    tests: &'a [WastTest],
+    configs: &'a [WastConfig],
+) -> Vec<(&'a WastTest, &'a WastConfig)> {
+    tests
+        .iter()
+        .flat_map(|test| configs.iter().map(move |config| (test, config)))
+        .filter(|(test, config)| !test.should_fail(config))
+        .collect()
+}
+
+/// Locate all `*.wast` and `*.wat` tests under `root` which should be the path
+/// to the root of the wasmtime repository.
+///
+/// Tests are annotated with the expected failures listed for them in
+/// `tests/wast-expectations.toml`.
 pub fn find_tests(root: &Path) -> Result<Vec<WastTest>> {
+    find_tests_with_roots(root, &[])
+}
+
+/// An additional directory of `*.wast` and `*.wat` tests, such as a
+/// downstream crate's own test suite, for [`find_tests_with_roots`].
+#[derive(Debug, Clone, Copy)]
+pub struct TestRoot<'a> {
+    /// The directory, which is searched recursively.
+    pub path: &'a Path,
+    /// Whether the tests declare their configuration in a `;;!` block, as
+    /// those of `tests/misc_testsuite` do; otherwise every test uses the
+    /// default [`TestConfig`].
+    pub has_config: bool,
+}
+
+/// Like [`find_tests`], but also finds the tests under each of `extra`, after
+/// those of the built-in suites.
+pub fn find_tests_with_roots(root: &Path, extra: &[TestRoot<'_>]) -> Result<Vec<WastTest>> {
+    check_spec_proposals(root)?;
+    let expectations = WastExpectations::load(root)?;
     let mut tests = Vec::new();
     add_tests(
         &mut tests,
-        &root.join("tests/spec_testsuite"),
+        &root.join("tests").join(SPEC_TESTSUITE),
         &FindConfig::Infer(spec_test_config),
     )?;
     add_tests(
         &mut tests,
-        &root.join("tests/misc_testsuite"),
+        &root.join("tests").join(MISC_TESTSUITE),
         &FindConfig::InTest,
     )?;
     add_tests(
         &mut tests,
-        &root.join("tests/component-model/test"),
+        &root.join("tests").join(COMPONENT_TESTSUITE).join("test"),
         &FindConfig::Infer(component_test_config),
     )?;
+    for extra in extra {
+        let config = if extra.has_config {
+            FindConfig::InTest
+        } else {
+            FindConfig::Default
+        };
+        add_tests(&mut tests, extra.path, &config)
+            .with_context(|| format!("failed to find tests in {:?}", extra.path))?;
+    }
+    for test in tests.iter_mut() {
+        test.expected_failures = expectations.failures_for(&test.path);
+    }
     Ok(tests)
 }
 
+/// Returns the names of the spec proposals, such as `"threads"`, whose tests
+/// this harness knows how to configure.
+pub fn known_spec_proposals() -> &'static [&'static str] {
+    static NAMES: OnceLock<Vec<&'static str>> = OnceLock::new();
+    NAMES.get_or_init(|| SPEC_PROPOSALS.iter().filter_map(|p| p.name).collect())
+}
+
+/// Checks that every `proposals/<name>` directory of the spec testsuite under
+/// `root`, the root of the wasmtime repository, names one of the
+/// [`known_spec_proposals`].
+///
+/// This runs as part of [`find_tests`] so that an updated testsuite with a new
+/// proposal fails up front rather than partway through a test run.
+pub fn check_spec_proposals(root: &Path) -> Result<()> {
+    let proposals = root.join("tests").join(SPEC_TESTSUITE).join("proposals");
+    if !proposals.exists() {
+        return Ok(());
+    }
+    let mut unknown = Vec::new();
+    for entry in proposals.read_dir().context("failed to read directory")? {
+        let entry = entry.context("failed to read directory entry")?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.path().is_dir() && !known_spec_proposals().contains(&name.as_str()) {
+            unknown.push(name);
+        }
+    }
+    if !unknown.is_empty() {
+        unknown.sort();
+        anyhow::bail!(
+            "unknown spec proposals in {proposals:?}: {}; add them to `SPEC_PROPOSALS` \
+             (known proposals: {})",
+            unknown.join(", "),
+            known_spec_proposals().join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Keeps only the spec tests of `proposal`, such as `"threads"`, from `tests`,
+/// i.e. those under a `proposals/<proposal>` directory.
+///
+/// This is useful for running a single proposal's suite during development.
+pub fn filter_by_proposal(mut tests: Vec<WastTest>, proposal: &str) -> Vec<WastTest> {
+    tests.retain(|test| test.spec_proposal() == Some(proposal));
+    tests
+}
+
 enum FindConfig {
     InTest,
     Infer(fn(&Path) -> TestConfig),
+    Default,
+}
+
+impl FindConfig {
+    /// Returns the configuration of the test at `path` with `contents`.
+    ///
+    /// Inferred configurations can be overridden by a `;;!` block in the test
+    /// itself, e.g. to mark a single spec test as `hogs_memory`.
+    fn test_config(&self, path: &Path, contents: &str) -> Result<TestConfig> {
+        Ok(match self {
+            FindConfig::InTest => parse_wast_test_config(contents)?,
+            FindConfig::Infer(f) => {
+                let mut config = f(path);
+                config.merge(&parse_wast_test_config(contents)?);
+                config
+            }
+            FindConfig::Default => TestConfig::default(),
+        })
+    }
 }
 
 fn add_tests(tests: &mut Vec<WastTest>, path: &Path, config: &FindConfig) -> Result<()> {
@@ -68,90 +191,130 @@ fn add_tests(tests: &mut Vec<WastTest>, path: &Path, config: &FindConfig) -> Res
             continue;
         }
 
-        if path.extension().and_then(|s| s.to_str()) != Some("wast") {
-            continue;
+        // `*.wat` files hold a single module and are run as a script which
+        // just defines that module, asserting that it compiles and
+        // instantiates.
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("wast" | "wat") => {}
+            _ => continue,
         }
 
         let contents =
             fs::read_to_string(&path).with_context(|| format!("failed to read test: {path:?}"))?;
-        let config = match config {
-            FindConfig::InTest => parse_test_config(&contents, ";;!")
-                .with_context(|| format!("failed to parse test configuration: {path:?}"))?,
-            FindConfig::Infer(f) => f(&path),
-        };
+        let config = config
+            .test_config(&path, &contents)
+            .with_context(|| format!("failed to parse test configuration: {path:?}"))?;
         tests.push(WastTest {
             path,
             contents,
             config,
+            expected_failures: Vec::new(),
         })
     }
     Ok(())
 }
 
+/// Configuration for the spec tests of a particular proposal.
+struct SpecProposal {
+    /// The name of the proposal's directory under `proposals`, or `None` for
+    /// the tests of the core specification.
+    name: Option<&'static str>,
+    /// Options enabled, or disabled, for all tests of this proposal.
+    options: &'static [(&'static str, bool)],
+    /// Additional options set for tests within a directory of the given name.
+    directory_options: &'static [(&'static str, &'static [(&'static str, bool)])],
+    /// Tests which are marked as `hogs_memory`.
+    hogs_memory: &'static [&'static str],
+}
+
+impl SpecProposal {
+    const DEFAULT: SpecProposal = SpecProposal {
+        name: None,
+        options: &[],
+        directory_options: &[],
+        hogs_memory: &[],
+    };
+}
+
+/// Configuration for spec tests, keyed by proposal.
+const SPEC_PROPOSALS: &[SpecProposal] = &[
+    SpecProposal {
+        options: &[("reference_types", true), ("simd", true)],
+        ..SpecProposal::DEFAULT
+    },
+    SpecProposal {
+        name: Some("wide-arithmetic"),
+        options: &[("wide_arithmetic", true)],
+        ..SpecProposal::DEFAULT
+    },
+    SpecProposal {
+        name: Some("threads"),
+        options: &[("threads", true), ("reference_types", false)],
+        ..SpecProposal::DEFAULT
+    },
+    SpecProposal {
+        name: Some("relaxed-simd"),
+        options: &[("relaxed_simd", true)],
+        ..SpecProposal::DEFAULT
+    },
+    SpecProposal {
+        name: Some("custom-page-sizes"),
+        options: &[
+            ("custom_page_sizes", true),
+            ("multi_memory", true),
+            ("memory64", true),
+        ],
+        // See commentary below in `wasm-3.0` case for why these "hog memory"
+        hogs_memory: &["memory_max.wast", "memory_max_i64.wast"],
+        ..SpecProposal::DEFAULT
+    },
+    SpecProposal {
+        name: Some("annotations"),
+        options: &[("simd", true)],
+        ..SpecProposal::DEFAULT
+    },
+    SpecProposal {
+        name: Some("wasm-3.0"),
+        options: &[
+            ("simd", true),
+            ("relaxed_simd", true),
+            ("multi_memory", true),
+            ("gc", true),
+            ("reference_types", true),
+            ("memory64", true),
+            ("tail_call", true),
+            ("extended_const", true),
+            ("exceptions", true),
+        ],
+        directory_options: &[("legacy", &[("legacy_exceptions", true)])],
+        // These tests technically don't actually hog any memory but they do
+        // have a module definition with a table/memory that is the maximum
+        // size. These modules fail to compile in the pooling allocator which
+        // has limits on the minimum size of memories/tables by default.
+        //
+        // Pretend that these hog memory to avoid running the tests in the
+        // pooling allocator.
+        hogs_memory: &["memory.wast", "table.wast", "memory64.wast"],
+    },
+];
+
 fn spec_test_config(test: &Path) -> TestConfig {
     let mut ret = TestConfig::default();
     ret.spec_test = Some(true);
-    match spec_proposal_from_path(test) {
-        Some("wide-arithmetic") => {
-            ret.wide_arithmetic = Some(true);
-        }
-        Some("threads") => {
-            ret.threads = Some(true);
-            ret.reference_types = Some(false);
-        }
-        Some("relaxed-simd") => {
-            ret.relaxed_simd = Some(true);
-        }
-        Some("custom-page-sizes") => {
-            ret.custom_page_sizes = Some(true);
-            ret.multi_memory = Some(true);
-            ret.memory64 = Some(true);
-
-            // See commentary below in `wasm-3.0` case for why these "hog
-            // memory"
-            if test.ends_with("memory_max.wast") || test.ends_with("memory_max_i64.wast") {
-                ret.hogs_memory = Some(true);
-            }
-        }
-        Some("annotations") => {
-            ret.simd = Some(true);
-        }
-        Some("wasm-3.0") => {
-            ret.simd = Some(true);
-            ret.relaxed_simd = Some(true);
-            ret.multi_memory = Some(true);
-            ret.gc = Some(true);
-            ret.reference_types = Some(true);
-            ret.memory64 = Some(true);
-            ret.tail_call = Some(true);
-            ret.extended_const = Some(true);
-            ret.exceptions = Some(true);
-
-            if test.parent().unwrap().ends_with("legacy") {
-                ret.legacy_exceptions = Some(true);
-            }
 
-            // These tests technically don't actually hog any memory but they
-            // do have a module definition with a table/memory that is the
-            // maximum size. These modules fail to compile in the pooling
-            // allocator which has limits on the minimum size of
-            // memories/tables by default.
-            //
-            // Pretend that these hog memory to avoid running the tests in the
-            // pooling allocator.
-            if test.ends_with("memory.wast")
-                || test.ends_with("table.wast")
-                || test.ends_with("memory64.wast")
-            {
-                ret.hogs_memory = Some(true);
-            }
-        }
-        Some(proposal) => panic!("unsuported proposal {proposal:?}"),
-        None => {
-            ret.reference_types = Some(true);
-            ret.simd = Some(true);
+    let name = spec_proposal_from_path(test);
+    let Some(proposal) = SPEC_PROPOSALS.iter().find(|p| p.name == name) else {
+        panic!("unsuported proposal {name:?}");
+    };
+    ret.set_options(proposal.options);
+    for (directory, options) in proposal.directory_options {
+        if test.parent().unwrap().ends_with(directory) {
+            ret.set_options(options);
         }
     }
+    if proposal.hogs_memory.iter().any(|t| test.ends_with(t)) {
+        ret.hogs_memory = Some(true);
+    }
 
     ret
 }
@@ -187,15 +350,50 @@ where
         .collect();
     let config_text = config_lines.join("\n");
 
-    toml::from_str(&config_text).context("failed to parse the test configuration")
+    toml::from_str(&config_text).map_err(|e| {
+        // Point at the line and column of the error in the original file,
+        // rather than in `config_text`, which has the comment prefixes
+        // stripped.
+        let location = e.span().map(|span| {
+            let before = &config_text[..span.start];
+            let line = before.matches('\n').count() + 1;
+            let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1);
+            format!(" at line {line}, column {}", column + comment.len() + 1)
+        });
+        anyhow::Error::new(e).context(format!(
+            "failed to parse the test configuration{}",
+            location.unwrap_or_default()
+        ))
+    })
 }
 
-/// A `*.wast` test with its path, contents, and configuration.
+/// Parses the `;;!` configuration of a wast test, listing the valid options if
+/// the configuration has an unknown one.
+fn parse_wast_test_config(wast: &str) -> Result<TestConfig> {
+    parse_test_config(wast, ";;!").map_err(|e: anyhow::Error| {
+        if !e.chain().any(|e| e.to_string().contains("unknown field")) {
+            return e;
+        }
+        let options: Vec<_> = TestConfig::default()
+            .options()
+            .map(|(name, _)| name)
+            .collect();
+        e.context(format!(
+            "unknown test configuration option, valid options are: {}",
+            options.join(", ")
+        ))
+    })
+}
+
+/// A `*.wast` or `*.wat` test with its path, contents, and configuration.
 #[derive(Clone)]
 pub struct WastTest {
     pub path: PathBuf,
     pub contents: String,
     pub config: TestConfig,
+    /// Configurations under which this test is known to fail, from
+    /// `tests/wast-expectations.toml`.
+    pub expected_failures: Vec<ExpectedFailure>,
 }
 
 impl fmt::Debug for WastTest {
@@ -204,12 +402,16 @@ impl fmt::Debug for WastTest {
             .field("path", &self.path)
             .field("contents", &"...")
             .field("config", &self.config)
+            .field("expected_failures", &self.expected_failures)
             .finish()
     }
 }
 
-macro_rules! foreach_config_option {
-    ($m:ident) => {
+/// Invokes `$m` with the options of [`TestConfig`] which enable a wasm
+/// proposal, or feature of one, that a compiler may or may not support,
+/// followed by any `$extra` options.
+macro_rules! foreach_proposal_option {
+    ($m:ident $($extra:ident)*) => {
         $m! {
             memory64
             custom_page_sizes
@@ -223,8 +425,6 @@ macro_rules! foreach_config_option {
             tail_call
             extended_const
             wide_arithmetic
-            hogs_memory
-            nan_canonicalization
             component_model_async
             component_model_async_builtins
             component_model_async_stackful
@@ -235,19 +435,65 @@ macro_rules! foreach_config_option {
             exceptions
             legacy_exceptions
             stack_switching
+            $($extra)*
+        }
+    };
+}
+
+macro_rules! foreach_config_option {
+    ($m:ident) => {
+        foreach_proposal_option! {
+            $m
+            hogs_memory
+            requires_pooling
+            both_allocators
+            mpk
+            nan_canonicalization
             spec_test
+            flaky
+            requires_avx
+            requires_avx2
+            requires_avx512
         }
     };
 }
 
+macro_rules! define_proposals {
+    ($($option:ident)*) => {
+        /// The options of [`TestConfig`] which enable a wasm proposal, or
+        /// feature of one, that a compiler may or may not support.
+        const PROPOSALS: &[&str] = &[$(stringify!($option),)*];
+    };
+}
+
+foreach_proposal_option!(define_proposals);
+
+/// The options of [`TestConfig`] which declare that a test requires a host CPU
+/// feature.
+const HOST_FEATURE_OPTIONS: &[(&str, HostFeature)] = &[
+    ("requires_avx", HostFeature::Avx),
+    ("requires_avx2", HostFeature::Avx2),
+    ("requires_avx512", HostFeature::Avx512),
+];
+
 macro_rules! define_test_config {
     ($($option:ident)*) => {
         /// Per-test configuration which is written down in the test file itself for
-        /// `misc_testsuite/**/*.wast` or in `spec_test_config` above for spec tests.
+        /// `misc_testsuite/**/*.wast` or in `spec_test_config` above for spec tests,
+        /// where the test file can still override individual options.
         #[derive(Debug, PartialEq, Default, Deserialize, Clone)]
         #[serde(deny_unknown_fields)]
         pub struct TestConfig {
             $(pub $option: Option<bool>,)*
+            /// Raises the number of memories a module may define under the
+            /// pooling allocator above [`limits::MEMORIES_PER_MODULE`].
+            pub max_memories: Option<u32>,
+            /// Raises the number of tables a module may define under the
+            /// pooling allocator above [`limits::TABLES_PER_MODULE`].
+            pub max_tables: Option<u32>,
+            /// Raises the number of elements a table may hold under the pooling
+            /// allocator above [`limits::TABLE_ELEMENTS`].
+            pub max_table_elements: Option<usize>,
         }
 
         impl TestConfig {
@@ -263,6 +509,18 @@ macro_rules! define_test_config {
 foreach_config_option!(define_test_config);
 
 impl TestConfig {
+    /// Returns an iterator over each option and its value.
+    pub fn options(&self) -> impl Iterator<Item = (&'static str, Option<bool>)> {
+        macro_rules! mk {
+            ($($option:ident)*) => {
+                [
+                    $((stringify!($option), self.$option),)*
+                ].into_iter()
+            }
+        }
+        foreach_config_option!(mk)
+    }
+
     /// Returns an iterator over each option.
     pub fn options_mut(&mut self) -> impl Iterator<Item = (&'static str, &mut Option<bool>)> {
         macro_rules! mk {
@@ -274,10 +532,58 @@ impl TestConfig {
         }
         foreach_config_option!(mk)
     }
+
+    /// Returns the host CPU features which this test requires, as declared by
+    /// its `requires_*` options.
+    pub fn required_host_features(&self) -> impl Iterator<Item = HostFeature> + '_ {
+        self.options().filter_map(|(name, value)| {
+            let (_, feature) = HOST_FEATURE_OPTIONS.iter().find(|(o, _)| *o == name)?;
+            value.unwrap_or(false).then_some(*feature)
+        })
+    }
+
+    /// Returns each option whose value differs between `self` and `other`,
+    /// along with both values.
+    ///
+    /// This helps explain why a test is skipped by comparing its configuration
+    /// against one that isn't.
+    pub fn diff(&self, other: &TestConfig) -> Vec<(&'static str, Option<bool>, Option<bool>)> {
+        self.options()
+            .zip(other.options())
+            .filter(|((_, a), (_, b))| a != b)
+            .map(|((name, a), (_, b))| (name, a, b))
+            .collect()
+    }
+
+    /// Overrides the options and limits of `self` with each one that is set in
+    /// `other`.
+    pub fn merge(&mut self, other: &TestConfig) {
+        for ((_, option), (_, value)) in self.options_mut().zip(other.options()) {
+            if value.is_some() {
+                *option = value;
+            }
+        }
+        self.max_memories = other.max_memories.or(self.max_memories);
+        self.max_tables = other.max_tables.or(self.max_tables);
+        self.max_table_elements = other.max_table_elements.or(self.max_table_elements);
+    }
+
+    /// Sets each of the named `options` to the given value.
+    ///
+    /// Panics if an option doesn't exist.
+    fn set_options(&mut self, options: &[(&str, bool)]) {
+        for (name, value) in options {
+            let (_, option) = self
+                .options_mut()
+                .find(|(option, _)| option == name)
+                .unwrap_or_else(|| panic!("unknown test config option {name:?}"));
+            *option = Some(*value);
+        }
+    }
 }
 
 /// Configuration that spec tests can run under.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct WastConfig {
     /// Compiler chosen to run this test.
     pub compiler: Compiler,
@@ -285,10 +591,16 @@ pub struct WastConfig {
     pub pooling: bool,
     /// What garbage collector is being used.
     pub collector: Collector,
+    /// Which register allocator Cranelift uses.
+    pub regalloc_algorithm: RegallocAlgorithm,
+    /// Whether to run tests even if `tests/wast-expectations.toml` lists them
+    /// as failing, e.g. to check whether a fix makes them pass.
+    pub ignore_skip_lists: bool,
 }
 
 /// Different compilers that can be tested in Wasmtime.
-#[derive(PartialEq, Debug, Copy, Clone)]
+#[derive(PartialEq, Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Compiler {
     /// Cranelift backend.
     ///
@@ -314,49 +626,119 @@ pub enum Compiler {
 }
 
 impl Compiler {
+    /// All compilers, in the order the test harness prefers them.
+    pub const ALL: [Compiler; 3] = [
+        Compiler::CraneliftNative,
+        Compiler::Winch,
+        Compiler::CraneliftPulley,
+    ];
+
     /// Returns whether this compiler is known to fail for the provided
     /// `TestConfig`.
     ///
     /// This function will determine if the configuration of the test provided
     /// is known to guarantee fail. This effectively tracks the proposal support
-    /// for each compiler backend/runtime and tests whether `config` enables or
-    /// disables features that aren't supported.
+    /// for each compiler backend/runtime, as reported by [`Compiler::supports`],
+    /// and tests whether `config` enables features that aren't supported.
     ///
     /// Note that this is closely aligned with
     /// `Config::compiler_panicking_wasm_features`.
     pub fn should_fail(&self, config: &TestConfig) -> bool {
-        match self {
-            Compiler::CraneliftNative => config.legacy_exceptions(),
+        self.should_fail_reason(config).is_some()
+    }
 
-            Compiler::Winch => {
-                if config.gc()
-                    || config.tail_call()
-                    || config.function_references()
-                    || config.gc()
-                    || config.relaxed_simd()
-                    || config.gc_types()
-                    || config.exceptions()
-                    || config.legacy_exceptions()
-                    || config.stack_switching()
-                    || config.legacy_exceptions()
-                    || config.component_model_async()
-                {
-                    return true;
-                }
+    /// Returns why this compiler is known to fail for the provided
+    /// `TestConfig`, or `None` if it isn't, as for [`Compiler::should_fail`].
+    pub fn should_fail_reason(&self, config: &TestConfig) -> Option<SkipReason> {
+        let unsupported_proposal = config.options().find(|(option, value)| {
+            *value == Some(true) && PROPOSALS.contains(option) && !self.supports(option)
+        });
+        if let Some((proposal, _)) = unsupported_proposal {
+            return Some(SkipReason::UnsupportedProposal(proposal));
+        }
+        if config.nan_canonicalization() && !self.supports_nan_canonicalization() {
+            return Some(SkipReason::NoNanCanonicalization);
+        }
 
+        let unsupported = match self {
+            Compiler::Winch => {
                 if cfg!(target_arch = "aarch64") {
-                    return config.wide_arithmetic()
-                        || (config.simd() && !config.spec_test())
-                        || config.threads();
+                    config.simd() && !config.spec_test()
+                } else {
+                    !cfg!(target_arch = "x86_64")
                 }
-
-                !cfg!(target_arch = "x86_64")
             }
 
-            Compiler::CraneliftPulley => {
-                config.threads() || config.legacy_exceptions() || config.stack_switching()
-            }
+            Compiler::CraneliftNative | Compiler::CraneliftPulley => false,
+        };
+        unsupported.then_some(SkipReason::WinchUnsupportedPath)
+    }
+
+    /// Returns whether this compiler supports `proposal`, named as the
+    /// corresponding option in [`TestConfig`], on the current host.
+    pub fn supports(&self, proposal: &str) -> bool {
+        let unsupported: &[&str] = match self {
+            Compiler::CraneliftNative => &["legacy_exceptions"],
+            Compiler::Winch => &[
+                "gc",
+                "tail_call",
+                "function_references",
+                "relaxed_simd",
+                "gc_types",
+                "exceptions",
+                "legacy_exceptions",
+                "stack_switching",
+                "component_model_async",
+            ],
+            Compiler::CraneliftPulley => &["threads", "legacy_exceptions", "stack_switching"],
+        };
+        if unsupported.contains(&proposal) {
+            return false;
         }
+
+        // Winch's aarch64 backend doesn't yet support some proposals that its
+        // x86_64 backend does.
+        !(*self == Compiler::Winch
+            && cfg!(target_arch = "aarch64")
+            && matches!(proposal, "wide_arithmetic" | "threads"))
+    }
+
+    /// Returns whether this compiler implements NaN canonicalization, as
+    /// required by tests with the `nan_canonicalization` option.
+    ///
+    /// Winch ignores the `cranelift_nan_canonicalization` setting.
+    pub fn supports_nan_canonicalization(&self) -> bool {
+        match self {
+            Compiler::CraneliftNative | Compiler::CraneliftPulley => true,
+            Compiler::Winch => false,
+        }
+    }
+
+    /// Returns the tests that the built-in `tests/wast-expectations.toml`
+    /// lists as failing with Winch on the current host, as written in the
+    /// manifest.
+    ///
+    /// These are the entries that [`WastTest::should_fail`] consults for Winch
+    /// without the pooling allocator; tests skipped because Winch doesn't
+    /// support a whole proposal or architecture aren't included.
+    pub fn winch_skipped_paths() -> Vec<String> {
+        let config = WastConfig {
+            compiler: Compiler::Winch,
+            pooling: false,
+            collector: Collector::Auto,
+            regalloc_algorithm: RegallocAlgorithm::Auto,
+            ignore_skip_lists: false,
+        };
+        WastExpectations::builtin()
+            .patterns_for(&config)
+            .map(|pattern| pattern.to_string())
+            .collect()
+    }
+
+    /// Returns the proposals, named as the corresponding options in
+    /// [`TestConfig`], that this compiler supports on the current host.
+    pub fn supported_proposals(&self) -> impl Iterator<Item = &'static str> {
+        PROPOSALS.iter().copied().filter(|p| self.supports(p))
     }
 
     /// Returns whether this compiler configuration supports the current host
@@ -375,13 +757,44 @@ impl Compiler {
     }
 }
 
+/// Garbage collectors that tests can run under.
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub enum Collector {
+    /// Let Wasmtime pick the collector.
     Auto,
+    /// The null collector, which never collects.
     Null,
+    /// The deferred reference-counting collector.
     DeferredReferenceCounting,
 }
 
+impl Collector {
+    /// Returns the concrete collector that `test` runs under with this
+    /// collector.
+    ///
+    /// `Auto` resolves to `DeferredReferenceCounting` for tests which exercise
+    /// GC types and to `Null` for all other tests, while the other collectors
+    /// resolve to themselves.
+    pub fn resolve(&self, test: &WastTest) -> Collector {
+        match self {
+            Collector::Auto if test.test_uses_gc_types() => Collector::DeferredReferenceCounting,
+            Collector::Auto => Collector::Null,
+            other => *other,
+        }
+    }
+}
+
+/// Register allocators that Cranelift-compiled tests can run under.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum RegallocAlgorithm {
+    /// Let Wasmtime pick the register allocator.
+    Auto,
+    /// The backtracking allocator, which generates better code.
+    Backtracking,
+    /// The single-pass allocator, which compiles faster.
+    SinglePass,
+}
+
 impl WastTest {
     /// Returns whether this test exercises the GC types and might want to use
     /// multiple different garbage collectors.
@@ -389,289 +802,397 @@ impl WastTest {
         self.config.gc() || self.config.function_references()
     }
 
+    /// Returns the garbage collectors that this test should be run under.
+    ///
+    /// Tests which exercise GC types are run under each concrete collector
+    /// while all other tests only need the default. The first collector
+    /// returned is the one to use when the test is only run once.
+    pub fn collectors(&self) -> &'static [Collector] {
+        if self.test_uses_gc_types() {
+            &[Collector::DeferredReferenceCounting, Collector::Null]
+        } else {
+            &[Collector::Auto]
+        }
+    }
+
+    /// Returns a name for this test which is stable across checkouts, for
+    /// reporting results.
+    ///
+    /// This is the `/`-delimited path of the test starting at its test suite's
+    /// directory within `tests`, such as `spec_testsuite/simd_load.wast`.
+    pub fn name(&self) -> String {
+        let components: Vec<_> = self.path.iter().map(|c| c.to_string_lossy()).collect();
+        let suites = [SPEC_TESTSUITE, MISC_TESTSUITE, COMPONENT_TESTSUITE];
+        let start = components
+            .windows(2)
+            .rposition(|w| w[0] == "tests" && suites.contains(&&*w[1]))
+            .map_or(0, |i| i + 1);
+        components[start..].join("/")
+    }
+
     /// Returns the optional spec proposal that this test is associated with.
     pub fn spec_proposal(&self) -> Option<&str> {
         spec_proposal_from_path(&self.path)
     }
 
+    /// Returns the compilers which this test can run under on the current
+    /// host, i.e. those for which [`WastTest::should_fail`] is false without
+    /// the pooling allocator and with the test's default collector.
+    ///
+    /// Running the test under each of these and comparing the outcomes is a
+    /// differential test of the compilers.
+    pub fn runnable_compilers(&self) -> Vec<Compiler> {
+        Compiler::ALL
+            .into_iter()
+            .filter(|&compiler| {
+                !self.should_fail(&WastConfig {
+                    compiler,
+                    pooling: false,
+                    collector: self.collectors()[0],
+                    regalloc_algorithm: RegallocAlgorithm::Auto,
+                    ignore_skip_lists: false,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns whether this test runs with the pooling allocator under
+    /// `config`; tests marked `requires_pooling` always do.
+    pub fn uses_pooling(&self, config: &WastConfig) -> bool {
+        config.pooling || self.config.requires_pooling()
+    }
+
+    /// Returns the configurations to run this test under in place of
+    /// `config`, all of which must succeed.
+    ///
+    /// Tests marked `both_allocators` run both with and without the pooling
+    /// allocator, regardless of `config.pooling`, unless they also require
+    /// pooling.
+    pub fn allocator_configs(&self, config: &WastConfig) -> Vec<WastConfig> {
+        if self.config.both_allocators() && !self.config.requires_pooling() {
+            [false, true]
+                .into_iter()
+                .map(|pooling| WastConfig { pooling, ..*config })
+                .collect()
+        } else {
+            vec![*config]
+        }
+    }
+
     /// Returns whether this test should fail under the specified extra
     /// configuration.
     pub fn should_fail(&self, config: &WastConfig) -> bool {
+        self.should_fail_reason(config).is_some()
+    }
+
+    /// Returns why this test should fail under the specified extra
+    /// configuration, or `None` if it shouldn't, as for
+    /// [`WastTest::should_fail`].
+    pub fn should_fail_reason(&self, config: &WastConfig) -> Option<SkipReason> {
+        let config = &WastConfig {
+            pooling: self.uses_pooling(config),
+            ..*config
+        };
         if !config.compiler.supports_host() {
-            return true;
-        }
-
-        // Some tests are known to fail with the pooling allocator
-        if config.pooling {
-            let unsupported = [
-                // allocates too much memory for the pooling configuration here
-                "misc_testsuite/memory64/more-than-4gb.wast",
-                // shared memories + pooling allocator aren't supported yet
-                "misc_testsuite/memory-combos.wast",
-                "misc_testsuite/threads/LB.wast",
-                "misc_testsuite/threads/LB_atomic.wast",
-                "misc_testsuite/threads/MP.wast",
-                "misc_testsuite/threads/MP_atomic.wast",
-                "misc_testsuite/threads/MP_wait.wast",
-                "misc_testsuite/threads/SB.wast",
-                "misc_testsuite/threads/SB_atomic.wast",
-                "misc_testsuite/threads/atomics_notify.wast",
-                "misc_testsuite/threads/atomics_wait_address.wast",
-                "misc_testsuite/threads/wait_notify.wast",
-                "spec_testsuite/proposals/threads/atomic.wast",
-                "spec_testsuite/proposals/threads/exports.wast",
-                "spec_testsuite/proposals/threads/memory.wast",
-            ];
-
-            if unsupported.iter().any(|part| self.path.ends_with(part)) {
-                return true;
-            }
+            return Some(SkipReason::UnsupportedHost);
         }
 
-        if config.compiler.should_fail(&self.config) {
-            return true;
-        }
-
-        // Disable spec tests per target for proposals that Winch does not implement yet.
-        if config.compiler == Compiler::Winch {
-            // Common list for tests that fail in all targets supported by Winch.
-            let unsupported = [
-                "extended-const/elem.wast",
-                "extended-const/global.wast",
-                "misc_testsuite/component-model/modules.wast",
-                "misc_testsuite/externref-id-function.wast",
-                "misc_testsuite/externref-segment.wast",
-                "misc_testsuite/externref-segments.wast",
-                "misc_testsuite/externref-table-dropped-segment-issue-8281.wast",
-                "misc_testsuite/linking-errors.wast",
-                "misc_testsuite/many_table_gets_lead_to_gc.wast",
-                "misc_testsuite/mutable_externref_globals.wast",
-                "misc_testsuite/no-mixup-stack-maps.wast",
-                "misc_testsuite/no-panic.wast",
-                "misc_testsuite/simple_ref_is_null.wast",
-                "misc_testsuite/table_grow_with_funcref.wast",
-                "spec_testsuite/br_table.wast",
-                "spec_testsuite/global.wast",
-                "spec_testsuite/ref_func.wast",
-                "spec_testsuite/ref_is_null.wast",
-                "spec_testsuite/ref_null.wast",
-                "spec_testsuite/select.wast",
-                "spec_testsuite/table_fill.wast",
-                "spec_testsuite/table_get.wast",
-                "spec_testsuite/table_grow.wast",
-                "spec_testsuite/table_set.wast",
-                "spec_testsuite/table_size.wast",
-                "spec_testsuite/elem.wast",
-                "spec_testsuite/linking.wast",
-            ];
-
-            if unsupported.iter().any(|part| self.path.ends_with(part)) {
-                return true;
-            }
+        if let Some(reason) = config.compiler.should_fail_reason(&self.config) {
+            return Some(reason);
+        }
 
-            #[cfg(target_arch = "aarch64")]
-            {
-                let unsupported = [
-                    "misc_testsuite/int-to-float-splat.wast",
-                    "misc_testsuite/issue6562.wast",
-                    "misc_testsuite/memory64/simd.wast",
-                    "misc_testsuite/simd/almost-extmul.wast",
-                    "misc_testsuite/simd/canonicalize-nan.wast",
-                    "misc_testsuite/simd/cvt-from-uint.wast",
-                    "misc_testsuite/simd/edge-of-memory.wast",
-                    "misc_testsuite/simd/interesting-float-splat.wast",
-                    "misc_testsuite/simd/issue4807.wast",
-                    "misc_testsuite/simd/issue6725-no-egraph-panic.wast",
-                    "misc_testsuite/simd/issue_3173_select_v128.wast",
-                    "misc_testsuite/simd/issue_3327_bnot_lowering.wast",
-                    "misc_testsuite/simd/load_splat_out_of_bounds.wast",
-                    "misc_testsuite/simd/replace-lane-preserve.wast",
-                    "misc_testsuite/simd/spillslot-size-fuzzbug.wast",
-                    "misc_testsuite/simd/sse-cannot-fold-unaligned-loads.wast",
-                    "misc_testsuite/simd/unaligned-load.wast",
-                    "misc_testsuite/simd/v128-select.wast",
-                    "misc_testsuite/winch/issue-10331.wast",
-                    "misc_testsuite/winch/issue-10357.wast",
-                    "misc_testsuite/winch/issue-10460.wast",
-                    "misc_testsuite/winch/replace_lane.wast",
-                    "misc_testsuite/winch/simd_multivalue.wast",
-                    "misc_testsuite/winch/v128_load_lane_invalid_address.wast",
-                    "spec_testsuite/proposals/annotations/simd_lane.wast",
-                    "spec_testsuite/proposals/multi-memory/simd_memory-multi.wast",
-                    "spec_testsuite/simd_address.wast",
-                    "spec_testsuite/simd_align.wast",
-                    "spec_testsuite/simd_bit_shift.wast",
-                    "spec_testsuite/simd_bitwise.wast",
-                    "spec_testsuite/simd_boolean.wast",
-                    "spec_testsuite/simd_const.wast",
-                    "spec_testsuite/simd_conversions.wast",
-                    "spec_testsuite/simd_f32x4.wast",
-                    "spec_testsuite/simd_f32x4_arith.wast",
-                    "spec_testsuite/simd_f32x4_cmp.wast",
-                    "spec_testsuite/simd_f32x4_pmin_pmax.wast",
-                    "spec_testsuite/simd_f32x4_rounding.wast",
-                    "spec_testsuite/simd_f64x2.wast",
-                    "spec_testsuite/simd_f64x2_arith.wast",
-                    "spec_testsuite/simd_f64x2_cmp.wast",
-                    "spec_testsuite/simd_f64x2_pmin_pmax.wast",
-                    "spec_testsuite/simd_f64x2_rounding.wast",
-                    "spec_testsuite/simd_i16x8_arith.wast",
-                    "spec_testsuite/simd_i16x8_arith2.wast",
-                    "spec_testsuite/simd_i16x8_cmp.wast",
-                    "spec_testsuite/simd_i16x8_extadd_pairwise_i8x16.wast",
-                    "spec_testsuite/simd_i16x8_extmul_i8x16.wast",
-                    "spec_testsuite/simd_i16x8_q15mulr_sat_s.wast",
-                    "spec_testsuite/simd_i16x8_sat_arith.wast",
-                    "spec_testsuite/simd_i32x4_arith.wast",
-                    "spec_testsuite/simd_i32x4_arith2.wast",
-                    "spec_testsuite/simd_i32x4_cmp.wast",
-                    "spec_testsuite/simd_i32x4_dot_i16x8.wast",
-                    "spec_testsuite/simd_i32x4_extadd_pairwise_i16x8.wast",
-                    "spec_testsuite/simd_i32x4_extmul_i16x8.wast",
-                    "spec_testsuite/simd_i32x4_trunc_sat_f32x4.wast",
-                    "spec_testsuite/simd_i32x4_trunc_sat_f64x2.wast",
-                    "spec_testsuite/simd_i64x2_arith.wast",
-                    "spec_testsuite/simd_i64x2_arith2.wast",
-                    "spec_testsuite/simd_i64x2_cmp.wast",
-                    "spec_testsuite/simd_i64x2_extmul_i32x4.wast",
-                    "spec_testsuite/simd_i8x16_arith.wast",
-                    "spec_testsuite/simd_i8x16_arith2.wast",
-                    "spec_testsuite/simd_i8x16_cmp.wast",
-                    "spec_testsuite/simd_i8x16_sat_arith.wast",
-                    "spec_testsuite/simd_int_to_int_extend.wast",
-                    "spec_testsuite/simd_lane.wast",
-                    "spec_testsuite/simd_load.wast",
-                    "spec_testsuite/simd_load16_lane.wast",
-                    "spec_testsuite/simd_load32_lane.wast",
-                    "spec_testsuite/simd_load64_lane.wast",
-                    "spec_testsuite/simd_load8_lane.wast",
-                    "spec_testsuite/simd_load_extend.wast",
-                    "spec_testsuite/simd_load_splat.wast",
-                    "spec_testsuite/simd_load_zero.wast",
-                    "spec_testsuite/simd_select.wast",
-                    "spec_testsuite/simd_splat.wast",
-                    "spec_testsuite/simd_store.wast",
-                    "spec_testsuite/simd_store16_lane.wast",
-                    "spec_testsuite/simd_store32_lane.wast",
-                    "spec_testsuite/simd_store64_lane.wast",
-                    "spec_testsuite/simd_store8_lane.wast",
-                ];
-
-                if unsupported.iter().any(|part| self.path.ends_with(part)) {
-                    return true;
-                }
-            }
+        // Tests can't run on hosts without the CPU features they require.
+        if let Some(feature) = self
+            .config
+            .required_host_features()
+            .find(|feature| !feature.is_detected())
+        {
+            return Some(SkipReason::MissingHostFeature(feature));
+        }
 
-            #[cfg(target_arch = "x86_64")]
-            {
-                let unsupported = [
-                    // externref/reference-types related
-                    // simd-related failures
-                    "misc_testsuite/simd/canonicalize-nan.wast",
-                ];
+        // Tests which require memory protection keys force them on in the
+        // pooling allocator, which fails if the host doesn't support them.
+        if config.pooling && self.config.mpk() && !host_supports_mpk() {
+            return Some(SkipReason::PoolingIncompatible);
+        }
 
-                if unsupported.iter().any(|part| self.path.ends_with(part)) {
-                    return true;
-                }
+        if config.ignore_skip_lists {
+            return None;
+        }
 
-                // SIMD on Winch requires AVX instructions.
-                #[cfg(target_arch = "x86_64")]
-                if !(std::is_x86_feature_detected!("avx") && std::is_x86_feature_detected!("avx2"))
-                {
-                    let unsupported = [
-                        "annotations/simd_lane.wast",
-                        "memory64/simd.wast",
-                        "misc_testsuite/int-to-float-splat.wast",
-                        "misc_testsuite/issue6562.wast",
-                        "misc_testsuite/simd/almost-extmul.wast",
-                        "misc_testsuite/simd/cvt-from-uint.wast",
-                        "misc_testsuite/simd/edge-of-memory.wast",
-                        "misc_testsuite/simd/issue_3327_bnot_lowering.wast",
-                        "misc_testsuite/simd/issue6725-no-egraph-panic.wast",
-                        "misc_testsuite/simd/replace-lane-preserve.wast",
-                        "misc_testsuite/simd/spillslot-size-fuzzbug.wast",
-                        "misc_testsuite/simd/sse-cannot-fold-unaligned-loads.wast",
-                        "misc_testsuite/winch/issue-10331.wast",
-                        "misc_testsuite/winch/replace_lane.wast",
-                        "spec_testsuite/simd_align.wast",
-                        "spec_testsuite/simd_boolean.wast",
-                        "spec_testsuite/simd_conversions.wast",
-                        "spec_testsuite/simd_f32x4.wast",
-                        "spec_testsuite/simd_f32x4_arith.wast",
-                        "spec_testsuite/simd_f32x4_cmp.wast",
-                        "spec_testsuite/simd_f32x4_pmin_pmax.wast",
-                        "spec_testsuite/simd_f32x4_rounding.wast",
-                        "spec_testsuite/simd_f64x2.wast",
-                        "spec_testsuite/simd_f64x2_arith.wast",
-                        "spec_testsuite/simd_f64x2_cmp.wast",
-                        "spec_testsuite/simd_f64x2_pmin_pmax.wast",
-                        "spec_testsuite/simd_f64x2_rounding.wast",
-                        "spec_testsuite/simd_i16x8_cmp.wast",
-                        "spec_testsuite/simd_i32x4_cmp.wast",
-                        "spec_testsuite/simd_i64x2_arith2.wast",
-                        "spec_testsuite/simd_i64x2_cmp.wast",
-                        "spec_testsuite/simd_i8x16_arith2.wast",
-                        "spec_testsuite/simd_i8x16_cmp.wast",
-                        "spec_testsuite/simd_int_to_int_extend.wast",
-                        "spec_testsuite/simd_load.wast",
-                        "spec_testsuite/simd_load_extend.wast",
-                        "spec_testsuite/simd_load_splat.wast",
-                        "spec_testsuite/simd_load_zero.wast",
-                        "spec_testsuite/simd_splat.wast",
-                        "spec_testsuite/simd_store16_lane.wast",
-                        "spec_testsuite/simd_store32_lane.wast",
-                        "spec_testsuite/simd_store64_lane.wast",
-                        "spec_testsuite/simd_store8_lane.wast",
-                        "spec_testsuite/simd_load16_lane.wast",
-                        "spec_testsuite/simd_load32_lane.wast",
-                        "spec_testsuite/simd_load64_lane.wast",
-                        "spec_testsuite/simd_load8_lane.wast",
-                        "spec_testsuite/simd_bitwise.wast",
-                        "misc_testsuite/simd/load_splat_out_of_bounds.wast",
-                        "misc_testsuite/simd/unaligned-load.wast",
-                        "multi-memory/simd_memory-multi.wast",
-                        "misc_testsuite/simd/issue4807.wast",
-                        "spec_testsuite/simd_const.wast",
-                        "spec_testsuite/simd_i8x16_sat_arith.wast",
-                        "spec_testsuite/simd_i64x2_arith.wast",
-                        "spec_testsuite/simd_i16x8_arith.wast",
-                        "spec_testsuite/simd_i16x8_arith2.wast",
-                        "spec_testsuite/simd_i16x8_q15mulr_sat_s.wast",
-                        "spec_testsuite/simd_i16x8_sat_arith.wast",
-                        "spec_testsuite/simd_i32x4_arith.wast",
-                        "spec_testsuite/simd_i32x4_dot_i16x8.wast",
-                        "spec_testsuite/simd_i32x4_trunc_sat_f32x4.wast",
-                        "spec_testsuite/simd_i32x4_trunc_sat_f64x2.wast",
-                        "spec_testsuite/simd_i8x16_arith.wast",
-                        "spec_testsuite/simd_bit_shift.wast",
-                        "spec_testsuite/simd_lane.wast",
-                        "spec_testsuite/simd_i16x8_extmul_i8x16.wast",
-                        "spec_testsuite/simd_i32x4_extmul_i16x8.wast",
-                        "spec_testsuite/simd_i64x2_extmul_i32x4.wast",
-                        "spec_testsuite/simd_i16x8_extadd_pairwise_i8x16.wast",
-                        "spec_testsuite/simd_i32x4_extadd_pairwise_i16x8.wast",
-                        "spec_testsuite/simd_i32x4_arith2.wast",
-                    ];
-
-                    if unsupported.iter().any(|part| self.path.ends_with(part)) {
-                        return true;
-                    }
-                }
+        self.expected_failures
+            .iter()
+            .find(|failure| failure.applies_to(config))
+            .map(|failure| SkipReason::ExpectedFailure(failure.clone()))
+    }
+}
+
+/// The reason that a test is expected to fail under a configuration, as
+/// returned by [`WastTest::should_fail_reason`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    /// The compiler doesn't support the host architecture.
+    UnsupportedHost,
+    /// The compiler doesn't support a proposal that the test uses, named as
+    /// the corresponding option in [`TestConfig`].
+    UnsupportedProposal(&'static str),
+    /// The compiler doesn't implement NaN canonicalization.
+    NoNanCanonicalization,
+    /// Winch doesn't support this kind of test on the host architecture.
+    WinchUnsupportedPath,
+    /// The host lacks a CPU feature that the test requires.
+    MissingHostFeature(HostFeature),
+    /// The test can't run with the pooling allocator on this host.
+    PoolingIncompatible,
+    /// The test is listed as failing in `tests/wast-expectations.toml`.
+    ExpectedFailure(ExpectedFailure),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::UnsupportedHost => write!(f, "the compiler doesn't support this host"),
+            SkipReason::UnsupportedProposal(proposal) => {
+                write!(f, "the compiler doesn't support `{proposal}`")
+            }
+            SkipReason::NoNanCanonicalization => {
+                write!(f, "the compiler doesn't implement NaN canonicalization")
+            }
+            SkipReason::WinchUnsupportedPath => {
+                write!(f, "Winch doesn't support this test on this host")
+            }
+            SkipReason::MissingHostFeature(feature) => {
+                write!(f, "the host doesn't support {feature:?}")
+            }
+            SkipReason::PoolingIncompatible => {
+                write!(f, "the test can't use the pooling allocator on this host")
+            }
+            SkipReason::ExpectedFailure(failure) => {
+                write!(f, "listed as an expected failure: {failure:?}")
             }
         }
+    }
+}
 
-        let failing_component_model_tests = [
-            // FIXME(#11683)
-            "component-model/test/values/trap-in-post-return.wast",
-        ];
-        if failing_component_model_tests
+/// Returns whether the host supports memory protection keys.
+fn host_supports_mpk() -> bool {
+    #[cfg(feature = "wasmtime-wast")]
+    return wasmtime::PoolingAllocationConfig::are_memory_protection_keys_available();
+    #[cfg(not(feature = "wasmtime-wast"))]
+    return false;
+}
+
+/// The built-in copy of `tests/wast-expectations.toml`, used when
+/// `find_tests` is pointed at a root which doesn't have its own manifest.
+const BUILTIN_EXPECTATIONS: &str = include_str!("../../../tests/wast-expectations.toml");
+
+/// A manifest of tests which are known to fail under particular
+/// configurations, such as `tests/wast-expectations.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct WastExpectations {
+    #[serde(default)]
+    expected_failure: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct ManifestEntry {
+    #[serde(default)]
+    compiler: Option<Compiler>,
+    #[serde(default)]
+    target_arch: Option<String>,
+    #[serde(default)]
+    pooling: Option<bool>,
+    #[serde(default)]
+    missing_host_features: Vec<HostFeature>,
+    tests: Vec<TestPattern>,
+}
+
+impl WastExpectations {
+    /// Loads `tests/wast-expectations.toml` under `root`, falling back to the
+    /// copy built into this crate if that file doesn't exist.
+    pub fn load(root: &Path) -> Result<WastExpectations> {
+        let path = root.join("tests/wast-expectations.toml");
+        if !path.exists() {
+            return Ok(WastExpectations::builtin());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read expectations: {path:?}"))?;
+        WastExpectations::parse(&contents)
+            .with_context(|| format!("failed to parse expectations: {path:?}"))
+    }
+
+    /// Returns the expectations built into this crate.
+    pub fn builtin() -> WastExpectations {
+        WastExpectations::parse(BUILTIN_EXPECTATIONS).unwrap()
+    }
+
+    /// Parses a manifest in the format of `tests/wast-expectations.toml`.
+    pub fn parse(contents: &str) -> Result<WastExpectations> {
+        toml::from_str(contents).context("failed to parse the wast expectations")
+    }
+
+    /// Returns the expected failures which list the test at `path`.
+    pub fn failures_for(&self, path: &Path) -> Vec<ExpectedFailure> {
+        self.expected_failure
             .iter()
-            .any(|part| self.path.ends_with(part))
-        {
-            return true;
+            .filter(|entry| entry.tests.iter().any(|test| test.matches(path)))
+            .map(ManifestEntry::failure)
+            .collect()
+    }
+
+    /// Returns the patterns of the tests which are expected to fail under
+    /// `config` on the current host.
+    pub fn patterns_for(&self, config: &WastConfig) -> impl Iterator<Item = &TestPattern> {
+        self.expected_failure
+            .iter()
+            .filter(|entry| entry.failure().applies_to(config))
+            .flat_map(|entry| &entry.tests)
+    }
+}
+
+impl ManifestEntry {
+    fn failure(&self) -> ExpectedFailure {
+        ExpectedFailure {
+            compiler: self.compiler,
+            target_arch: self.target_arch.clone(),
+            pooling: self.pooling,
+            missing_host_features: self.missing_host_features.clone(),
         }
+    }
+}
 
-        false
+/// A pattern for the paths of tests listed in `tests/wast-expectations.toml`.
+///
+/// Patterns are matched against the trailing components of a test's path, so
+/// `misc_testsuite/foo.wast` matches `tests/misc_testsuite/foo.wast`. Within a
+/// component `*` matches any sequence of characters, so
+/// `misc_testsuite/simd/*.wast` matches every test in that directory.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "String")]
+pub struct TestPattern {
+    components: Vec<String>,
+}
+
+impl From<String> for TestPattern {
+    fn from(pattern: String) -> TestPattern {
+        TestPattern::new(&pattern)
+    }
+}
+
+impl TestPattern {
+    /// Compiles `pattern` into a `TestPattern`.
+    pub fn new(pattern: &str) -> TestPattern {
+        TestPattern {
+            components: pattern
+                .split('/')
+                .filter(|c| !c.is_empty())
+                .map(|c| c.to_string())
+                .collect(),
+        }
+    }
+
+    /// Returns whether `path` matches this pattern.
+    pub fn matches(&self, path: &Path) -> bool {
+        let path: Vec<_> = path.iter().collect();
+        if path.len() < self.components.len() {
+            return false;
+        }
+        path[path.len() - self.components.len()..]
+            .iter()
+            .zip(&self.components)
+            .all(|(component, pattern)| {
+                component
+                    .to_str()
+                    .is_some_and(|component| glob_matches(pattern, component))
+            })
+    }
+}
+
+impl fmt::Display for TestPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.components.join("/"))
+    }
+}
+
+/// Returns whether `text` matches `pattern`, where `*` in `pattern` matches any
+/// sequence of characters.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return rest.len() >= part.len() && rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+/// Conditions under which a particular test is known to fail.
+///
+/// A test is expected to fail when every condition that is specified holds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedFailure {
+    /// The compiler which the test fails with.
+    pub compiler: Option<Compiler>,
+    /// The host architecture, as in `std::env::consts::ARCH`, which the test
+    /// fails on.
+    pub target_arch: Option<String>,
+    /// Whether the test fails with, or without, the pooling allocator.
+    pub pooling: Option<bool>,
+    /// Host features of which at least one must be missing for the test to
+    /// fail.
+    pub missing_host_features: Vec<HostFeature>,
+}
+
+impl ExpectedFailure {
+    /// Returns whether this failure is expected under `config` on the current
+    /// host.
+    pub fn applies_to(&self, config: &WastConfig) -> bool {
+        self.compiler.is_none_or(|c| c == config.compiler)
+            && self
+                .target_arch
+                .as_ref()
+                .is_none_or(|arch| arch == std::env::consts::ARCH)
+            && self.pooling.is_none_or(|p| p == config.pooling)
+            && (self.missing_host_features.is_empty()
+                || self.missing_host_features.iter().any(|f| !f.is_detected()))
+    }
+}
+
+/// Host CPU features which tests may depend on.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HostFeature {
+    Avx,
+    Avx2,
+    Avx512,
+}
+
+impl HostFeature {
+    /// Returns whether the current host supports this feature.
+    pub fn is_detected(&self) -> bool {
+        #[cfg(target_arch = "x86_64")]
+        {
+            match self {
+                HostFeature::Avx => std::is_x86_feature_detected!("avx"),
+                HostFeature::Avx2 => std::is_x86_feature_detected!("avx2"),
+                HostFeature::Avx512 => std::is_x86_feature_detected!("avx512f"),
+            }
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            false
+        }
     }
 }
 
@@ -685,3 +1206,517 @@ fn spec_proposal_from_path(path: &Path) -> Option<&str> {
     }
     Some(iter.next()?.to_str()?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wast_config(compiler: Compiler, pooling: bool) -> WastConfig {
+        WastConfig {
+            compiler,
+            pooling,
+            collector: Collector::Auto,
+            regalloc_algorithm: RegallocAlgorithm::Auto,
+            ignore_skip_lists: false,
+        }
+    }
+
+    fn wast_test(config: &str) -> WastTest {
+        WastTest {
+            path: "tests/misc_testsuite/test.wast".into(),
+            contents: String::new(),
+            config: parse_test_config(config, ";;!").unwrap(),
+            expected_failures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn collectors() {
+        let gc_test = wast_test(";;! gc = true");
+        assert_eq!(
+            gc_test.collectors(),
+            [Collector::DeferredReferenceCounting, Collector::Null]
+        );
+
+        let non_gc_test = wast_test(";;! simd = true");
+        assert_eq!(non_gc_test.collectors(), [Collector::Auto]);
+    }
+
+    #[test]
+    fn mpk_tests_require_host_support() {
+        let test = wast_test(";;! mpk = true");
+        assert!(test.config.mpk());
+        let compiler = Compiler::CraneliftPulley;
+        assert!(!test.should_fail(&wast_config(compiler, false)));
+        assert_eq!(
+            test.should_fail(&wast_config(compiler, true)),
+            !host_supports_mpk()
+        );
+    }
+
+    #[test]
+    fn required_host_features() {
+        let test = wast_test(";;! requires_avx512 = true\n;;! requires_avx = false");
+        assert_eq!(
+            test.config.required_host_features().collect::<Vec<_>>(),
+            [HostFeature::Avx512]
+        );
+        assert_eq!(
+            test.should_fail(&wast_config(Compiler::CraneliftPulley, false)),
+            !HostFeature::Avx512.is_detected()
+        );
+
+        for (option, _) in HOST_FEATURE_OPTIONS {
+            assert!(TestConfig::default().options().any(|(o, _)| o == *option));
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        let name = |path: &str| {
+            WastTest {
+                path: path.into(),
+                ..wast_test("")
+            }
+            .name()
+        };
+        assert_eq!(
+            name("/home/a/wasmtime/tests/spec_testsuite/simd_load.wast"),
+            "spec_testsuite/simd_load.wast"
+        );
+        assert_eq!(
+            name("./tests/spec_testsuite/simd_load.wast"),
+            "spec_testsuite/simd_load.wast"
+        );
+        assert_eq!(
+            name("/ci/tests/component-model/test/async/a.wast"),
+            "component-model/test/async/a.wast"
+        );
+        assert_eq!(
+            name("/ci/misc_testsuite/wasmtime/tests/misc_testsuite/simd/a.wast"),
+            "misc_testsuite/simd/a.wast"
+        );
+        assert_eq!(
+            name("tests/misc_testsuite/component-model/modules.wast"),
+            "misc_testsuite/component-model/modules.wast"
+        );
+    }
+
+    #[test]
+    fn spec_proposal_configs() {
+        let config = spec_test_config("tests/spec_testsuite/simd_lane.wast".as_ref());
+        let mut expected = TestConfig::default();
+        expected.spec_test = Some(true);
+        expected.reference_types = Some(true);
+        expected.simd = Some(true);
+        assert_eq!(config, expected);
+
+        let config =
+            spec_test_config("tests/spec_testsuite/proposals/threads/atomic.wast".as_ref());
+        assert!(config.threads());
+        assert_eq!(config.reference_types, Some(false));
+
+        let config =
+            spec_test_config("tests/spec_testsuite/proposals/wasm-3.0/legacy/throw.wast".as_ref());
+        assert!(config.exceptions() && config.legacy_exceptions() && config.gc());
+        assert!(!config.hogs_memory());
+
+        let config =
+            spec_test_config("tests/spec_testsuite/proposals/wasm-3.0/memory.wast".as_ref());
+        assert!(!config.legacy_exceptions());
+        assert!(config.hogs_memory());
+    }
+
+    #[test]
+    fn known_spec_proposals() {
+        let known = super::known_spec_proposals();
+        assert!(known.contains(&"threads") && known.contains(&"wasm-3.0"));
+        for name in known {
+            let path = format!("tests/spec_testsuite/proposals/{name}/a.wast");
+            spec_test_config(path.as_ref());
+        }
+
+        let root = std::env::temp_dir().join(format!("spec-proposals-{}", std::process::id()));
+        let proposals = root.join("tests/spec_testsuite/proposals");
+        fs::create_dir_all(proposals.join("threads")).unwrap();
+        check_spec_proposals(&root).unwrap();
+        fs::create_dir_all(proposals.join("new-proposal")).unwrap();
+        let err = check_spec_proposals(&root).unwrap_err().to_string();
+        fs::remove_dir_all(&root).unwrap();
+        assert!(err.contains("unknown spec proposals"), "{err}");
+        assert!(err.contains(": new-proposal;"), "{err}");
+    }
+
+    #[test]
+    fn extra_test_roots() {
+        let root = std::env::temp_dir().join(format!("extra-roots-{}", std::process::id()));
+        for suite in ["spec_testsuite", "misc_testsuite", "component-model/test"] {
+            fs::create_dir_all(root.join("tests").join(suite)).unwrap();
+        }
+        let configured = root.join("configured");
+        let plain = root.join("plain/nested");
+        fs::create_dir_all(&configured).unwrap();
+        fs::create_dir_all(&plain).unwrap();
+        fs::write(configured.join("gc.wast"), ";;! gc = true\n(module)").unwrap();
+        fs::write(configured.join("notes.txt"), "").unwrap();
+        fs::write(plain.join("a.wat"), ";;! gc = true\n(module)").unwrap();
+
+        let tests = find_tests_with_roots(
+            &root,
+            &[
+                TestRoot {
+                    path: &configured,
+                    has_config: true,
+                },
+                TestRoot {
+                    path: &root.join("plain"),
+                    has_config: false,
+                },
+            ],
+        );
+        fs::remove_dir_all(&root).unwrap();
+        let tests = tests.unwrap();
+        assert_eq!(tests.len(), 2);
+        assert_eq!(tests[0].path, configured.join("gc.wast"));
+        assert_eq!(tests[0].config.gc, Some(true));
+        assert_eq!(tests[1].path, plain.join("a.wat"));
+        assert_eq!(tests[1].config, TestConfig::default());
+    }
+
+    #[test]
+    fn spec_test_config_overrides() {
+        let path = Path::new("tests/spec_testsuite/proposals/wasm-3.0/legacy/throw.wast");
+        let spec = FindConfig::Infer(spec_test_config);
+        let config = spec.test_config(path, "(module)\n").unwrap();
+        assert_eq!(config, spec_test_config(path));
+
+        let config = spec
+            .test_config(path, ";;! hogs_memory = true\n;;! gc = false\n(module)\n")
+            .unwrap();
+        assert!(config.hogs_memory());
+        assert_eq!(config.gc, Some(false));
+        assert!(config.legacy_exceptions() && config.spec_test());
+    }
+
+    #[test]
+    fn config_diff() {
+        let config = wast_test(";;! gc = true\n;;! simd = false").config;
+        assert_eq!(
+            config.diff(&TestConfig::default()),
+            [("gc", Some(true), None), ("simd", Some(false), None)]
+        );
+        assert_eq!(
+            TestConfig::default().diff(&config)[0],
+            ("gc", None, Some(true))
+        );
+        assert!(config.diff(&config).is_empty());
+    }
+
+    #[test]
+    fn compiler_proposal_support() {
+        assert!(!Compiler::Winch.supports("gc"));
+        assert!(!Compiler::Winch.supports("tail_call"));
+        assert!(Compiler::CraneliftNative.supports("gc"));
+        assert!(Compiler::CraneliftNative.supports("tail_call"));
+
+        let supported: Vec<_> = Compiler::CraneliftNative.supported_proposals().collect();
+        assert!(supported.contains(&"gc") && supported.contains(&"tail_call"));
+        assert!(!supported.contains(&"legacy_exceptions"));
+
+        let winch = wast_test(";;! tail_call = true");
+        assert!(Compiler::Winch.should_fail(&winch.config));
+        assert!(!Compiler::CraneliftNative.should_fail(&winch.config));
+
+        for proposal in PROPOSALS {
+            assert!(TestConfig::default().options().any(|(o, _)| o == *proposal));
+        }
+    }
+
+    #[test]
+    fn runnable_compilers() {
+        let integer_test = wast_test("");
+        let compilers = integer_test.runnable_compilers();
+        assert!(compilers.contains(&Compiler::CraneliftPulley));
+        if cfg!(target_arch = "x86_64") {
+            assert!(compilers.contains(&Compiler::CraneliftNative));
+            assert!(compilers.contains(&Compiler::Winch));
+        }
+
+        let gc_test = wast_test(";;! gc = true");
+        assert!(!gc_test.runnable_compilers().contains(&Compiler::Winch));
+    }
+
+    #[test]
+    fn runnable_pairs() {
+        let gc_test = wast_test(";;! gc = true");
+        let mut failing_test = wast_test("");
+        failing_test.expected_failures.push(ExpectedFailure {
+            compiler: None,
+            target_arch: None,
+            pooling: Some(true),
+            missing_host_features: Vec::new(),
+        });
+        let tests = [gc_test, failing_test];
+        let configs = [
+            wast_config(Compiler::Winch, false),
+            wast_config(Compiler::CraneliftPulley, false),
+            wast_config(Compiler::CraneliftPulley, true),
+        ];
+
+        let pairs: Vec<_> = super::runnable_pairs(&tests, &configs)
+            .into_iter()
+            .map(|(test, config)| {
+                let test = tests.iter().position(|t| std::ptr::eq(t, test)).unwrap();
+                (test, config.compiler, config.pooling)
+            })
+            .collect();
+        let mut expected = vec![
+            (0, Compiler::CraneliftPulley, false),
+            (0, Compiler::CraneliftPulley, true),
+            (1, Compiler::CraneliftPulley, false),
+        ];
+        if Compiler::Winch.supports_host() {
+            expected.insert(2, (1, Compiler::Winch, false));
+        }
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn nan_canonicalization_support() {
+        let test = wast_test(";;! nan_canonicalization = true");
+        assert!(Compiler::Winch.should_fail(&test.config));
+        for compiler in [Compiler::CraneliftNative, Compiler::CraneliftPulley] {
+            assert!(!compiler.should_fail(&test.config));
+        }
+    }
+
+    #[test]
+    fn proposal_filter() {
+        let tests = [
+            "tests/spec_testsuite/proposals/threads/atomic.wast",
+            "tests/spec_testsuite/proposals/gc/array.wast",
+            "tests/spec_testsuite/simd_lane.wast",
+            "tests/misc_testsuite/threads/atomics_wait_address.wast",
+        ]
+        .into_iter()
+        .map(|path| WastTest {
+            path: path.into(),
+            ..wast_test("")
+        })
+        .collect();
+        let threads = filter_by_proposal(tests, "threads");
+        assert_eq!(threads.len(), 1);
+        assert_eq!(
+            threads[0].path,
+            Path::new("tests/spec_testsuite/proposals/threads/atomic.wast")
+        );
+    }
+
+    #[test]
+    fn should_fail_reasons() {
+        let gc_test = wast_test(";;! gc = true");
+        assert_eq!(
+            Compiler::Winch.should_fail_reason(&gc_test.config),
+            Some(SkipReason::UnsupportedProposal("gc"))
+        );
+        assert_eq!(
+            Compiler::CraneliftNative.should_fail_reason(&gc_test.config),
+            None
+        );
+
+        let mut test = wast_test("");
+        let failure = ExpectedFailure {
+            compiler: Some(Compiler::CraneliftPulley),
+            target_arch: None,
+            pooling: Some(true),
+            missing_host_features: Vec::new(),
+        };
+        test.expected_failures.push(failure.clone());
+        assert_eq!(
+            test.should_fail_reason(&wast_config(Compiler::CraneliftPulley, true)),
+            Some(SkipReason::ExpectedFailure(failure))
+        );
+        assert_eq!(
+            test.should_fail_reason(&wast_config(Compiler::CraneliftPulley, false)),
+            None
+        );
+    }
+
+    #[test]
+    fn requires_pooling() {
+        let mut test = wast_test(";;! requires_pooling = true");
+        assert!(test.config.requires_pooling());
+        assert!(test.uses_pooling(&wast_config(Compiler::CraneliftPulley, false)));
+        assert!(!wast_test("").uses_pooling(&wast_config(Compiler::CraneliftPulley, false)));
+
+        // Pooling-only failures apply even without the pooling allocator.
+        let failure = ExpectedFailure {
+            compiler: None,
+            target_arch: None,
+            pooling: Some(true),
+            missing_host_features: Vec::new(),
+        };
+        test.expected_failures.push(failure.clone());
+        assert_eq!(
+            test.should_fail_reason(&wast_config(Compiler::CraneliftPulley, false)),
+            Some(SkipReason::ExpectedFailure(failure))
+        );
+    }
+
+    #[test]
+    fn both_allocators() {
+        let test = wast_test(";;! both_allocators = true");
+        for pooling in [false, true] {
+            let config = wast_config(Compiler::CraneliftPulley, pooling);
+            let pooling: Vec<_> = test
+                .allocator_configs(&config)
+                .iter()
+                .map(|c| c.pooling)
+                .collect();
+            assert_eq!(pooling, [false, true]);
+        }
+
+        let config = wast_config(Compiler::CraneliftPulley, false);
+        assert_eq!(wast_test("").allocator_configs(&config).len(), 1);
+        let test = wast_test(";;! both_allocators = true\n;;! requires_pooling = true");
+        assert_eq!(test.allocator_configs(&config).len(), 1);
+    }
+
+    #[test]
+    fn ignore_skip_lists() {
+        let mut test = wast_test("");
+        test.expected_failures = WastExpectations::parse(
+            r#"
+                [[expected-failure]]
+                compiler = "cranelift-pulley"
+                tests = ["misc_testsuite/test.wast"]
+            "#,
+        )
+        .unwrap()
+        .failures_for(&test.path);
+
+        let mut config = wast_config(Compiler::CraneliftPulley, false);
+        assert!(test.should_fail(&config));
+        config.ignore_skip_lists = true;
+        assert!(!test.should_fail(&config));
+
+        // Compilers that don't support the host are still skipped.
+        let gc_test = wast_test(";;! gc = true");
+        let mut config = wast_config(Compiler::Winch, false);
+        config.ignore_skip_lists = true;
+        assert!(gc_test.should_fail(&config));
+    }
+
+    #[test]
+    fn resolve_collector() {
+        let gc_test = wast_test(";;! gc = true");
+        let non_gc_test = wast_test(";;! simd = true");
+        assert_eq!(
+            Collector::Auto.resolve(&gc_test),
+            Collector::DeferredReferenceCounting
+        );
+        assert_eq!(Collector::Auto.resolve(&non_gc_test), Collector::Null);
+        for collector in [Collector::Null, Collector::DeferredReferenceCounting] {
+            assert_eq!(collector.resolve(&gc_test), collector);
+            assert_eq!(collector.resolve(&non_gc_test), collector);
+        }
+    }
+
+    #[test]
+    fn expectations_select_matching_tests() {
+        let expectations = WastExpectations::parse(
+            r#"
+                [[expected-failure]]
+                compiler = "winch"
+                pooling = true
+                tests = ["misc_testsuite/foo.wast"]
+            "#,
+        )
+        .unwrap();
+
+        let failures = expectations.failures_for("tests/misc_testsuite/foo.wast".as_ref());
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].applies_to(&wast_config(Compiler::Winch, true)));
+        assert!(!failures[0].applies_to(&wast_config(Compiler::Winch, false)));
+        assert!(!failures[0].applies_to(&wast_config(Compiler::CraneliftNative, true)));
+
+        assert!(
+            expectations
+                .failures_for("tests/misc_testsuite/bar.wast".as_ref())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn config_error_location() {
+        let err = parse_test_config::<TestConfig>(
+            ";;! simd = true\n;;! gc = true\n;;! threads = =\n(module)\n",
+            ";;!",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to parse the test configuration at line 3, column 15"
+        );
+    }
+
+    #[test]
+    fn unknown_config_option() {
+        let err = parse_wast_test_config(";;! memroy64 = true\n(module)\n").unwrap_err();
+        let msg = err.to_string();
+        assert!(
+            msg.starts_with("unknown test configuration option"),
+            "{msg}"
+        );
+        assert!(msg.contains("memory64"), "{msg}");
+
+        let err = parse_wast_test_config(";;! memory64 = 1\n(module)\n").unwrap_err();
+        assert!(!err.to_string().contains("valid options"), "{err}");
+    }
+
+    #[test]
+    fn test_patterns() {
+        let exact = TestPattern::new("misc_testsuite/foo.wast");
+        assert!(exact.matches("tests/misc_testsuite/foo.wast".as_ref()));
+        assert!(!exact.matches("tests/misc_testsuite/a/foo.wast".as_ref()));
+        assert!(!exact.matches("tests/misc_testsuite/xfoo.wast".as_ref()));
+
+        let glob = TestPattern::new("spec_testsuite/simd_*.wast");
+        assert!(glob.matches("tests/spec_testsuite/simd_lane.wast".as_ref()));
+        assert!(glob.matches("tests/spec_testsuite/simd_.wast".as_ref()));
+        assert!(!glob.matches("tests/spec_testsuite/select.wast".as_ref()));
+        assert!(!glob.matches("tests/spec_testsuite/simd_lane.wat".as_ref()));
+        assert!(!glob.matches("tests/spec_testsuite/simd/lane.wast".as_ref()));
+        assert!(!glob.matches("spec_testsuite/proposals/a/simd_lane.wast".as_ref()));
+
+        let multi = TestPattern::new("*/simd_*_lane.wast");
+        assert!(multi.matches("tests/spec_testsuite/simd_load8_lane.wast".as_ref()));
+        assert!(!multi.matches("tests/spec_testsuite/simd_lane.wast".as_ref()));
+    }
+
+    #[test]
+    fn builtin_expectations_parse() {
+        let expectations = WastExpectations::builtin();
+        let failures = expectations
+            .failures_for("tests/component-model/test/values/trap-in-post-return.wast".as_ref());
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].applies_to(&wast_config(Compiler::CraneliftNative, false)));
+    }
+
+    #[test]
+    fn winch_skipped_paths() {
+        let config = wast_config(Compiler::Winch, false);
+        let expectations = WastExpectations::builtin();
+        let paths = Compiler::winch_skipped_paths();
+        assert_eq!(paths.len(), expectations.patterns_for(&config).count());
+        assert!(paths.iter().any(|p| p == "spec_testsuite/global.wast"));
+        for path in paths {
+            let path = Path::new("tests").join(path);
+            let failures = expectations.failures_for(&path);
+            assert!(
+                failures.iter().any(|f| f.applies_to(&config)),
+                "{path:?} isn't expected to fail with Winch"
+            );
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use cap_std::time::{Duration, Instant, SystemClock, SystemTime};
 use cap_std::{AmbientAuthority, ambient_authority};
 use cap_time_ext::{MonotonicClockExt as _, SystemClockExt as _};
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use wasmtime::component::{HasData, ResourceTable};
 
 /// A helper struct which implements [`HasData`] for the `wasi:clocks` APIs.
@@ -61,6 +64,66 @@ impl Default for WasiClocksCtx {
     }
 }
 
+impl WasiClocksCtx {
+    /// Wrap both clocks in [`CountingClock`]s, returning the access counts of
+    /// the wall clock and the monotonic clock, in that order.
+    ///
+    /// This lets the host report how often a guest read each clock.
+    pub fn count_accesses(&mut self) -> (Arc<ClockAccessCounts>, Arc<ClockAccessCounts>) {
+        let wall = CountingClock::new(std::mem::replace(&mut self.wall_clock, wall_clock()));
+        let monotonic = CountingClock::new(std::mem::replace(
+            &mut self.monotonic_clock,
+            monotonic_clock(),
+        ));
+        let counts = (wall.counts(), monotonic.counts());
+        self.wall_clock = Box::new(wall);
+        self.monotonic_clock = Box::new(monotonic);
+        counts
+    }
+
+    /// Read the current time and resolution of both clocks.
+    pub fn snapshot(&self) -> ClockSnapshot {
+        ClockSnapshot {
+            wall_now: self.wall_clock.now(),
+            wall_resolution: self.wall_clock.resolution(),
+            monotonic_now: self.monotonic_clock.now(),
+            monotonic_resolution: self.monotonic_clock.resolution(),
+        }
+    }
+
+    /// Create clocks frozen at the readings of `snapshot`, e.g. to replay a
+    /// recorded execution.
+    pub fn from_snapshot(snapshot: ClockSnapshot) -> Self {
+        let ClockSnapshot {
+            wall_now,
+            wall_resolution,
+            monotonic_now,
+            monotonic_resolution,
+        } = snapshot;
+        Self {
+            wall_clock: Box::new(ClosureWallClock::new(wall_resolution, move || wall_now)),
+            monotonic_clock: Box::new(ClosureMonotonicClock::new(
+                monotonic_resolution,
+                move || monotonic_now,
+            )),
+        }
+    }
+}
+
+/// The readings of both clocks of a [`WasiClocksCtx`] at one point in time;
+/// see [`WasiClocksCtx::snapshot`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockSnapshot {
+    /// The wall-clock time, as a duration since the Unix epoch.
+    pub wall_now: Duration,
+    /// The resolution of the wall clock.
+    pub wall_resolution: Duration,
+    /// The monotonic-clock time, in nanoseconds.
+    pub monotonic_now: u64,
+    /// The resolution of the monotonic clock, in nanoseconds.
+    pub monotonic_resolution: u64,
+}
+
 pub trait WasiClocksView: Send {
     fn clocks(&mut self) -> WasiClocksCtxView<'_>;
 }
@@ -78,6 +141,52 @@ pub trait HostWallClock: Send {
 pub trait HostMonotonicClock: Send {
     fn resolution(&self) -> u64;
     fn now(&self) -> u64;
+
+    /// The nanoseconds remaining until this clock reaches `deadline`, or
+    /// `None` if it already has.
+    fn remaining_until(&self, deadline: u64) -> Option<u64> {
+        deadline
+            .checked_sub(self.now())
+            .filter(|&remaining| remaining > 0)
+    }
+
+    /// Fill `out` with successive, non-decreasing readings of `now`.
+    ///
+    /// The default reads the clock once per element; implementations with a
+    /// cheaper way to take many readings can override this.
+    fn now_batch(&self, out: &mut [u64]) {
+        for reading in out {
+            *reading = self.now();
+        }
+    }
+}
+
+impl<T: HostWallClock + ?Sized> HostWallClock for Box<T> {
+    fn resolution(&self) -> Duration {
+        (**self).resolution()
+    }
+
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+impl<T: HostMonotonicClock + ?Sized> HostMonotonicClock for Box<T> {
+    fn resolution(&self) -> u64 {
+        (**self).resolution()
+    }
+
+    fn now(&self) -> u64 {
+        (**self).now()
+    }
+
+    fn remaining_until(&self, deadline: u64) -> Option<u64> {
+        (**self).remaining_until(deadline)
+    }
+
+    fn now_batch(&self, out: &mut [u64]) {
+        (**self).now_batch(out)
+    }
 }
 
 pub struct WallClock {
@@ -97,6 +206,11 @@ impl WallClock {
             clock: cap_std::time::SystemClock::new(ambient_authority),
         }
     }
+
+    /// Creates a wall clock reading from an already-constructed `clock`.
+    pub fn from_clock(clock: SystemClock) -> Self {
+        Self { clock }
+    }
 }
 
 impl HostWallClock for WallClock {
@@ -113,6 +227,99 @@ impl HostWallClock for WallClock {
     }
 }
 
+/// A wall clock whose readings are truncated to a multiple of a granularity.
+///
+/// Unlike a clock which merely reports a coarse resolution, this alters the
+/// times themselves, e.g. so that timestamps a guest writes match the
+/// precision of the host filesystem and round-trip unchanged.
+pub struct TruncatingWallClock<C = WallClock> {
+    clock: C,
+    granularity: Duration,
+}
+
+impl<C: HostWallClock> TruncatingWallClock<C> {
+    /// Wrap `clock` so that its readings are rounded down to a multiple of
+    /// `granularity`, e.g. `Duration::from_secs(1)` for whole seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `granularity` is zero.
+    pub fn new(clock: C, granularity: Duration) -> Self {
+        assert!(!granularity.is_zero(), "granularity must be nonzero");
+        Self { clock, granularity }
+    }
+}
+
+impl<C: HostWallClock> HostWallClock for TruncatingWallClock<C> {
+    fn resolution(&self) -> Duration {
+        self.clock.resolution().max(self.granularity)
+    }
+
+    fn now(&self) -> Duration {
+        const NANOS_PER_SEC: u128 = 1_000_000_000;
+        let now = self.clock.now().as_nanos();
+        let truncated = now - now % self.granularity.as_nanos();
+        Duration::new(
+            (truncated / NANOS_PER_SEC).try_into().unwrap(),
+            (truncated % NANOS_PER_SEC).try_into().unwrap(),
+        )
+    }
+}
+
+/// A wall clock that never goes backward, derived from a monotonic clock.
+///
+/// The wall-clock time is sampled once, at construction, and afterwards
+/// advanced only by the elapsed time reported by the monotonic clock. Unlike
+/// [`WallClock`], this clock is unaffected by steps of the system clock (e.g.
+/// by NTP), at the cost of drifting from it over time.
+pub struct MonotonicWallClock<M = MonotonicClock> {
+    /// The clock measuring elapsed time.
+    monotonic: M,
+
+    /// The wall-clock time when `monotonic` read `start`.
+    epoch: Duration,
+    start: u64,
+}
+
+impl Default for MonotonicWallClock {
+    fn default() -> Self {
+        Self::new(ambient_authority())
+    }
+}
+
+impl MonotonicWallClock {
+    pub fn new(ambient_authority: AmbientAuthority) -> Self {
+        Self::from_clocks(
+            &WallClock::new(ambient_authority),
+            MonotonicClock::new(ambient_authority),
+        )
+    }
+}
+
+impl<M: HostMonotonicClock> MonotonicWallClock<M> {
+    /// Create a clock that starts at the current time of `wall` and advances
+    /// with `monotonic`.
+    pub fn from_clocks(wall: &dyn HostWallClock, monotonic: M) -> Self {
+        let start = monotonic.now();
+        Self {
+            monotonic,
+            epoch: wall.now(),
+            start,
+        }
+    }
+}
+
+impl<M: HostMonotonicClock> HostWallClock for MonotonicWallClock<M> {
+    fn resolution(&self) -> Duration {
+        Duration::from_nanos(self.monotonic.resolution())
+    }
+
+    fn now(&self) -> Duration {
+        let elapsed = self.monotonic.now().saturating_sub(self.start);
+        self.epoch + Duration::from_nanos(elapsed)
+    }
+}
+
 pub struct MonotonicClock {
     /// The underlying system clock.
     clock: cap_std::time::MonotonicClock,
@@ -138,7 +345,7 @@ impl MonotonicClock {
 
 impl HostMonotonicClock for MonotonicClock {
     fn resolution(&self) -> u64 {
-        self.clock.resolution().as_nanos().try_into().unwrap()
+        nonzero_resolution(self.clock.resolution().as_nanos().try_into().unwrap())
     }
 
     fn now(&self) -> u64 {
@@ -153,6 +360,272 @@ impl HostMonotonicClock for MonotonicClock {
     }
 }
 
+/// A monotonic clock which reports a resolution no finer than a configured
+/// floor, such as the OS tick granularity.
+///
+/// Some platforms claim a finer clock resolution than their scheduler can
+/// honor; on Windows, for example, timers fire on a tick of about 1ms. This
+/// wrapper reports the larger of the wrapped clock's resolution and `floor`,
+/// while its readings are those of the wrapped clock.
+pub struct ResolutionFloorClock<C = MonotonicClock> {
+    clock: C,
+    floor: u64,
+}
+
+impl<C: HostMonotonicClock> ResolutionFloorClock<C> {
+    /// Wrap `clock` so that its resolution is reported as at least `floor`
+    /// nanoseconds.
+    pub fn new(clock: C, floor: u64) -> Self {
+        Self { clock, floor }
+    }
+}
+
+impl<C: HostMonotonicClock> HostMonotonicClock for ResolutionFloorClock<C> {
+    fn resolution(&self) -> u64 {
+        self.clock.resolution().max(self.floor)
+    }
+
+    fn now(&self) -> u64 {
+        self.clock.now()
+    }
+}
+
+/// A monotonic clock that limits how often its time can be observed to
+/// advance.
+///
+/// Reads within `interval` nanoseconds of the last fresh reading return that
+/// same, stale reading, so a guest can sample the clock at most once per
+/// interval. This mitigates timing side channels that rely on rapid sampling
+/// while, unlike coarsening the resolution, keeping the values themselves
+/// precise.
+pub struct RateLimitedClock<C> {
+    clock: C,
+    interval: u64,
+    last: Cell<Option<u64>>,
+}
+
+impl<C: HostMonotonicClock> RateLimitedClock<C> {
+    /// Wrap `clock` so that it yields a fresh reading at most once per
+    /// `interval` nanoseconds, as measured by `clock` itself.
+    pub fn new(clock: C, interval: u64) -> Self {
+        Self {
+            clock,
+            interval,
+            last: Cell::new(None),
+        }
+    }
+}
+
+impl<C: HostMonotonicClock> HostMonotonicClock for RateLimitedClock<C> {
+    fn resolution(&self) -> u64 {
+        self.clock.resolution()
+    }
+
+    fn now(&self) -> u64 {
+        let now = self.clock.now();
+        match self.last.get() {
+            Some(last) if now.saturating_sub(last) < self.interval => last,
+            _ => {
+                self.last.set(Some(now));
+                now
+            }
+        }
+    }
+}
+
+/// A monotonic clock which adds pseudo-random jitter to the readings of
+/// another clock, e.g. to fuzz guests which assume evenly spaced readings.
+///
+/// Each reading is delayed by up to `bound` nanoseconds, drawn from a generator
+/// seeded with `seed` so that a failing run can be reproduced. Readings never
+/// go backwards, even when a large delay is followed by a small one.
+pub struct JitterClock<C> {
+    clock: C,
+    bound: u64,
+    state: Cell<u64>,
+    last: Cell<u64>,
+}
+
+impl<C: HostMonotonicClock> JitterClock<C> {
+    /// Wrap `clock`, delaying each of its readings by up to `bound`
+    /// nanoseconds chosen by a generator seeded with `seed`.
+    pub fn new(clock: C, bound: u64, seed: u64) -> Self {
+        Self {
+            clock,
+            bound,
+            state: Cell::new(seed),
+            last: Cell::new(0),
+        }
+    }
+
+    /// Return the next jitter, in `0..=bound`, using SplitMix64.
+    fn jitter(&self) -> u64 {
+        let state = self.state.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+        self.state.set(state);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        match self.bound.checked_add(1) {
+            Some(range) => z % range,
+            None => z,
+        }
+    }
+}
+
+impl<C: HostMonotonicClock> HostMonotonicClock for JitterClock<C> {
+    fn resolution(&self) -> u64 {
+        self.clock.resolution()
+    }
+
+    fn now(&self) -> u64 {
+        let now = self
+            .clock
+            .now()
+            .saturating_add(self.jitter())
+            .max(self.last.get());
+        self.last.set(now);
+        now
+    }
+}
+
+/// A monotonic clock which reads its time, in nanoseconds, from a closure.
+///
+/// This is a shorthand for implementing [`HostMonotonicClock`] for a custom
+/// time source such as a hardware timer.
+pub struct ClosureMonotonicClock<F> {
+    now: F,
+    resolution: u64,
+}
+
+impl<F: Fn() -> u64 + Send> ClosureMonotonicClock<F> {
+    /// Create a clock whose `now` calls `now` and which reports the given
+    /// resolution, in nanoseconds.
+    pub fn new(resolution: u64, now: F) -> Self {
+        Self { now, resolution }
+    }
+}
+
+impl<F: Fn() -> u64 + Send> HostMonotonicClock for ClosureMonotonicClock<F> {
+    fn resolution(&self) -> u64 {
+        nonzero_resolution(self.resolution)
+    }
+
+    fn now(&self) -> u64 {
+        (self.now)()
+    }
+}
+
+/// A wall clock which reads its time, as a duration since the Unix epoch,
+/// from a closure.
+///
+/// This is a shorthand for implementing [`HostWallClock`] for a custom time
+/// source such as a network time service.
+pub struct ClosureWallClock<F> {
+    now: F,
+    resolution: Duration,
+}
+
+impl<F: Fn() -> Duration + Send> ClosureWallClock<F> {
+    /// Create a clock whose `now` calls `now` and which reports the given
+    /// resolution.
+    pub fn new(resolution: Duration, now: F) -> Self {
+        Self { now, resolution }
+    }
+}
+
+impl<F: Fn() -> Duration + Send> HostWallClock for ClosureWallClock<F> {
+    fn resolution(&self) -> Duration {
+        self.resolution
+    }
+
+    fn now(&self) -> Duration {
+        (self.now)()
+    }
+}
+
+/// The number of times a [`CountingClock`] has been read.
+#[derive(Debug, Default)]
+pub struct ClockAccessCounts {
+    now: AtomicU64,
+    resolution: AtomicU64,
+}
+
+impl ClockAccessCounts {
+    /// The number of calls to the clock's `now`.
+    pub fn now(&self) -> u64 {
+        self.now.load(Ordering::Relaxed)
+    }
+
+    /// The number of calls to the clock's `resolution`.
+    pub fn resolution(&self) -> u64 {
+        self.resolution.load(Ordering::Relaxed)
+    }
+}
+
+/// A clock which counts how often it is read, for observability.
+///
+/// The counts are shared, so the host can keep a handle to them (from
+/// [`CountingClock::counts`]) after installing the clock in a
+/// [`WasiClocksCtx`]; see also [`WasiClocksCtx::count_accesses`].
+pub struct CountingClock<C> {
+    clock: C,
+    counts: Arc<ClockAccessCounts>,
+}
+
+impl<C> CountingClock<C> {
+    /// Wrap `clock`, starting all counts at zero.
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            counts: Arc::default(),
+        }
+    }
+
+    /// The counts of reads of this clock so far, which keep updating as the
+    /// clock is read.
+    pub fn counts(&self) -> Arc<ClockAccessCounts> {
+        self.counts.clone()
+    }
+}
+
+impl<C: HostWallClock> HostWallClock for CountingClock<C> {
+    fn resolution(&self) -> Duration {
+        self.counts.resolution.fetch_add(1, Ordering::Relaxed);
+        self.clock.resolution()
+    }
+
+    fn now(&self) -> Duration {
+        self.counts.now.fetch_add(1, Ordering::Relaxed);
+        self.clock.now()
+    }
+}
+
+impl<C: HostMonotonicClock> HostMonotonicClock for CountingClock<C> {
+    fn resolution(&self) -> u64 {
+        self.counts.resolution.fetch_add(1, Ordering::Relaxed);
+        self.clock.resolution()
+    }
+
+    fn now(&self) -> u64 {
+        self.counts.now.fetch_add(1, Ordering::Relaxed);
+        self.clock.now()
+    }
+}
+
+/// Clamp a resolution of zero nanoseconds, which some virtualized
+/// environments report, up to one so that callers can safely divide by it.
+fn nonzero_resolution(nanos: u64) -> u64 {
+    if nanos == 0 {
+        static WARNED: AtomicBool = AtomicBool::new(false);
+        if !WARNED.swap(true, Ordering::Relaxed) {
+            tracing::warn!("monotonic clock reported a zero resolution; using 1ns instead");
+        }
+        return 1;
+    }
+    nanos
+}
+
 pub fn monotonic_clock() -> Box<dyn HostMonotonicClock + Send> {
     Box::new(MonotonicClock::default())
 }
@@ -161,6 +634,242 @@ pub fn wall_clock() -> Box<dyn HostWallClock + Send> {
     Box::new(WallClock::default())
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A clock whose time, in nanoseconds, is set by the test.
+    #[derive(Clone, Default)]
+    struct FakeClock(Arc<AtomicU64>);
+
+    impl FakeClock {
+        fn set(&self, nanos: u64) {
+            self.0.store(nanos, Ordering::SeqCst);
+        }
+    }
+
+    impl HostWallClock for FakeClock {
+        fn resolution(&self) -> Duration {
+            Duration::from_nanos(1)
+        }
+
+        fn now(&self) -> Duration {
+            Duration::from_nanos(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    impl HostMonotonicClock for FakeClock {
+        fn resolution(&self) -> u64 {
+            1
+        }
+
+        fn now(&self) -> u64 {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn monotonic_wall_clock_ignores_system_clock_steps() {
+        let (system, monotonic) = (FakeClock::default(), FakeClock::default());
+        system.set(1_000_000_000_000);
+        monotonic.set(5);
+        let clock = MonotonicWallClock::from_clocks(&system, monotonic.clone());
+        let before = clock.now();
+        assert_eq!(before, Duration::from_secs(1000));
+
+        // Step the system clock back; only the monotonic clock matters now.
+        system.set(500_000_000_000);
+        monotonic.set(1_000_000_005);
+        let after = clock.now();
+        assert!(after >= before);
+        assert_eq!(after, Duration::from_secs(1001));
+    }
+
+    #[test]
+    fn wall_clock_from_clock() {
+        let unix_now = || {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+        };
+        let clock = WallClock::from_clock(SystemClock::new(ambient_authority()));
+        let before = unix_now();
+        let now = clock.now();
+        let after = unix_now();
+        assert!(before <= now && now <= after);
+    }
+
+    #[test]
+    fn truncating_wall_clock() {
+        let inner = FakeClock::default();
+        inner.set(1_700_000_000_123_456_789);
+        let seconds = TruncatingWallClock::new(inner.clone(), Duration::from_secs(1));
+        assert_eq!(seconds.now(), Duration::from_secs(1_700_000_000));
+        assert_eq!(seconds.now().subsec_nanos(), 0);
+        assert_eq!(seconds.resolution(), Duration::from_secs(1));
+
+        let millis = TruncatingWallClock::new(inner, Duration::from_millis(1));
+        assert_eq!(millis.now(), Duration::new(1_700_000_000, 123_000_000));
+    }
+
+    #[test]
+    fn jitter_clock() {
+        let inner = FakeClock::default();
+        let readings = |seed| {
+            let clock = JitterClock::new(inner.clone(), 1_000, seed);
+            (0..100)
+                .map(|i| {
+                    inner.set(i * 100);
+                    HostMonotonicClock::now(&clock)
+                })
+                .collect::<Vec<_>>()
+        };
+        let first = readings(42);
+        assert_eq!(first, readings(42));
+        assert_ne!(first, readings(43));
+        assert!(first.windows(2).all(|w| w[0] <= w[1]));
+        assert!(
+            first
+                .iter()
+                .zip(0..)
+                .all(|(&r, i)| (i * 100..=i * 100 + 1_000).contains(&r))
+        );
+    }
+
+    #[test]
+    fn resolution_floor_clock() {
+        let inner = FakeClock::default();
+        let clock = ResolutionFloorClock::new(inner.clone(), 1_000_000);
+        assert_eq!(HostMonotonicClock::resolution(&clock), 1_000_000);
+        inner.set(1_234);
+        assert_eq!(HostMonotonicClock::now(&clock), 1_234);
+
+        let coarse = ClosureMonotonicClock::new(15_625_000, || 0);
+        let clock = ResolutionFloorClock::new(coarse, 1_000_000);
+        assert_eq!(clock.resolution(), 15_625_000);
+    }
+
+    #[test]
+    fn rate_limited_clock_returns_stale_readings() {
+        let inner = FakeClock::default();
+        let clock = RateLimitedClock::new(inner.clone(), 100);
+        inner.set(1_000);
+        assert_eq!(clock.now(), 1_000);
+        inner.set(1_099);
+        assert_eq!(clock.now(), 1_000);
+        inner.set(1_100);
+        assert_eq!(clock.now(), 1_100);
+        inner.set(1_150);
+        assert_eq!(clock.now(), 1_100);
+    }
+
+    #[test]
+    fn count_accesses() {
+        let mut ctx = WasiClocksCtx::default();
+        let (wall, monotonic) = ctx.count_accesses();
+        for _ in 0..3 {
+            ctx.monotonic_clock.now();
+        }
+        ctx.monotonic_clock.resolution();
+        ctx.wall_clock.now();
+        assert_eq!((monotonic.now(), monotonic.resolution()), (3, 1));
+        assert_eq!((wall.now(), wall.resolution()), (1, 0));
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let clock = FakeClock::default();
+        clock.set(1_000);
+        let ctx = WasiClocksCtx {
+            wall_clock: Box::new(clock.clone()),
+            monotonic_clock: Box::new(clock.clone()),
+        };
+        let snapshot = ctx.snapshot();
+        assert_eq!(snapshot.wall_now, Duration::from_nanos(1_000));
+        assert_eq!(snapshot.monotonic_now, 1_000);
+
+        // Time moves on for the original clocks but not for the restored ones.
+        clock.set(2_000);
+        let restored = WasiClocksCtx::from_snapshot(snapshot);
+        assert_eq!(ctx.snapshot().monotonic_now, 2_000);
+        assert_eq!(restored.snapshot(), snapshot);
+        clock.set(3_000);
+        assert_eq!(restored.snapshot(), snapshot);
+    }
+
+    #[test]
+    fn remaining_until() {
+        let clock = FakeClock::default();
+        clock.set(100);
+        assert_eq!(clock.remaining_until(150), Some(50));
+        assert_eq!(clock.remaining_until(100), None);
+        assert_eq!(clock.remaining_until(0), None);
+        clock.set(u64::MAX);
+        assert_eq!(clock.remaining_until(150), None);
+    }
+
+    #[test]
+    fn now_batch() {
+        let ctx = WasiClocksCtx::default();
+        let mut readings = [0; 16];
+        ctx.monotonic_clock.now_batch(&mut readings);
+        assert!(readings.is_sorted(), "{readings:?}");
+
+        /// A clock that fills a batch with a single reading.
+        struct BatchClock(FakeClock, Arc<AtomicU64>);
+
+        impl HostMonotonicClock for BatchClock {
+            fn resolution(&self) -> u64 {
+                1
+            }
+
+            fn now(&self) -> u64 {
+                self.1.fetch_add(1, Ordering::SeqCst);
+                HostMonotonicClock::now(&self.0)
+            }
+
+            fn now_batch(&self, out: &mut [u64]) {
+                out.fill(self.now());
+            }
+        }
+
+        let (clock, reads) = (FakeClock::default(), Arc::new(AtomicU64::new(0)));
+        clock.set(7);
+        let boxed: Box<dyn HostMonotonicClock> = Box::new(BatchClock(clock, reads.clone()));
+        boxed.now_batch(&mut readings);
+        assert_eq!(readings, [7; 16]);
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn zero_resolution_is_clamped() {
+        let clock = ClosureMonotonicClock::new(0, || 0);
+        assert_eq!(clock.resolution(), 1);
+        let clock = ClosureMonotonicClock::new(5, || 0);
+        assert_eq!(clock.resolution(), 5);
+    }
+
+    #[test]
+    fn closure_clocks() {
+        let counter = Arc::new(AtomicU64::new(42));
+        let ticks = counter.clone();
+        let ctx = WasiClocksCtx {
+            wall_clock: Box::new(ClosureWallClock::new(Duration::from_secs(1), || {
+                Duration::from_secs(1_700_000_000)
+            })),
+            monotonic_clock: Box::new(ClosureMonotonicClock::new(10, move || {
+                ticks.load(Ordering::Relaxed)
+            })),
+        };
+        assert_eq!(ctx.monotonic_clock.resolution(), 10);
+        assert_eq!(ctx.monotonic_clock.now(), 42);
+        counter.store(52, Ordering::Relaxed);
+        assert_eq!(ctx.monotonic_clock.now(), 52);
+        assert_eq!(ctx.wall_clock.resolution(), Duration::from_secs(1));
+        assert_eq!(ctx.wall_clock.now(), Duration::from_secs(1_700_000_000));
+    }
+}
+
 pub(crate) struct Datetime {
     pub seconds: u64,
     pub nanoseconds: u32,
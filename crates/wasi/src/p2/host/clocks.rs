@@ -93,12 +93,8 @@ impl monotonic_clock::Host for WasiClocksCtxView<'_> {
     }
 
     fn subscribe_instant(&mut self, when: Instant) -> anyhow::Result<Resource<DynPollable>> {
-        let clock_now = self.ctx.monotonic_clock.now();
-        let duration = if when > clock_now {
-            Duration::from_nanos(when - clock_now)
-        } else {
-            Duration::from_nanos(0)
-        };
+        let remaining = self.ctx.monotonic_clock.remaining_until(when);
+        let duration = Duration::from_nanos(remaining.unwrap_or(0));
         subscribe_to_duration(self.table, duration)
     }
 
@@ -28,10 +28,10 @@ impl monotonic_clock::HostWithStore for WasiClocks {
         store: &Accessor<U, Self>,
         when: monotonic_clock::Instant,
     ) -> wasmtime::Result<()> {
-        let clock_now = store.with(|mut view| view.get().ctx.monotonic_clock.now());
-        if when > clock_now {
-            sleep(Duration::from_nanos(when - clock_now)).await;
-        };
+        let remaining = store.with(|mut view| view.get().ctx.monotonic_clock.remaining_until(when));
+        if let Some(remaining) = remaining {
+            sleep(Duration::from_nanos(remaining)).await;
+        }
         Ok(())
     }
 
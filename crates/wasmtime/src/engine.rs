@@ -464,8 +464,18 @@ information about this check\
             | "enable_float"
             | "enable_verifier"
             | "enable_pcc"
+            | "pcc_report"
+            | "require_pcc"
             | "regalloc_checker"
             | "regalloc_verbose_logs"
+            | "regalloc_validate_ssa"
+            | "regalloc_checks_disabled"
+            | "regalloc_reject_moves_log2_minus_one"
+            | "regalloc_size_budget_log2_minus_one"
+            | "compile_phase_timing"
+            | "regalloc_block_stats"
+            | "compile_determinism_check"
+            | "dump_vcode"
             | "regalloc_algorithm"
             | "is_pic"
             | "bb_padding_log2_minus_one"
@@ -42,6 +42,14 @@ bindgen!({
 ///
 /// In this case this is relatively simple but there's no restrictions on what
 /// this type can hold other than that it must be `'static + Send`.
+///
+/// Note that the `Send` requirement comes from [`ResourceTable`] and from
+/// driving a store asynchronously, both of which require `Send` data. The
+/// generated bindings themselves only add `Send` bounds when some function is
+/// `async` or when `require_store_data_send: true` is specified, so fully
+/// synchronous bindings can be used with a `!Send` store as well.
+///
+/// [`ResourceTable`]: crate::component::ResourceTable
 pub struct MyLogger {
     pub max_level: example::imported_resources::logging::Level,
 }
@@ -0,0 +1,36 @@
+bindgen!({
+    inline: r#"
+        package example:trappable-errors;
+
+        interface files {
+            enum io-error {
+                not-found,
+                permission-denied,
+            }
+
+            read-to-string: func(path: string) -> result<string, io-error>;
+        }
+
+        world read-files {
+            import files;
+        }
+    "#,
+
+    // Functions must be `trappable` for `trappable_error_type` to take effect.
+    imports: { default: trappable },
+
+    // Bind the WIT `io-error` type to the Rust `MyIoError` type defined below.
+    // This means that `read-to-string` returns `Result<String, MyIoError>` in
+    // Rust instead of `wasmtime::Result<Result<String, IoError>>`.
+    trappable_error_type: {
+        "example:trappable-errors/files/io-error" => MyIoError,
+    },
+});
+
+/// A host-defined error type which is either a WIT-level error or a trap.
+pub enum MyIoError {
+    /// A WIT-level error which is returned to the guest.
+    Wit(example::trappable_errors::files::IoError),
+    /// A host-level error which traps the guest.
+    Trap(std::io::Error),
+}
@@ -263,8 +263,13 @@ pub mod _2_world_exports;
 ///   [`Lower`] derived.
 /// * The generated trait that host's must implement is always called [`Host`]
 ///   and is located in the generated module.
+/// * Each imported interface has its own [`add_to_linker`] function, but the
+///   world-level [`WithImports::add_to_linker`] registers all imported interfaces
+///   of the world at once using a single accessor.
 ///
 /// [module]: _3_interface_imports::example::interface_imports::logging
+/// [`add_to_linker`]: _3_interface_imports::example::interface_imports::logging::add_to_linker
+/// [`WithImports::add_to_linker`]: _3_interface_imports::WithImports::add_to_linker
 /// [`Level`]: _3_interface_imports::example::interface_imports::logging::Level
 /// [`Host`]: _3_interface_imports::example::interface_imports::logging::Host
 /// [`ComponentType`]: crate::component::ComponentType
@@ -502,6 +507,27 @@ pub mod _6_exported_resources;
 /// * async functions are used
 /// * enabled async in bindgen! macro
 ///
+/// Note that `imports` and `exports` are configured independently. A host
+/// whose implementation is entirely synchronous can leave `async` out of
+/// `imports` while still using `exports: { default: async }`. Synchronous host
+/// functions are supported in stores that are driven asynchronously, so no
+/// blocking adapter is necessary and the generated `Host` traits will use plain
+/// `fn` methods.
+///
+/// The generated `async` trait methods return `impl Future<Output = ...> +
+/// Send` rather than a boxed future, so implementations are written with
+/// native `async fn` as below and calling them doesn't allocate. The `Send`
+/// bound, which a plain `async fn` in the trait couldn't express, is what
+/// allows these futures to be driven by an asynchronous [`Store`].
+///
+/// Because the trait only requires some `impl Future`, a method may also be
+/// written as a plain `fn` which builds and returns its future explicitly, as
+/// `log` does below. This gives the host full control over the future it
+/// hands back, e.g. to wrap it in a timeout or to race it against another
+/// future, without needing a separate `bindgen!` option.
+///
+/// [`Store`]: crate::Store
+///
 /// See [wasi_async_example](https://github.com/bytecodealliance/wasmtime/blob/main/examples/wasip1-async/main.rs) for async function calls on a host.
 ///
 /// ```rust
@@ -543,13 +569,21 @@ pub mod _6_exported_resources;
 ///         Ok(())
 ///     }
 ///
-///     async fn log(&mut self, logger: Resource<MyLogger>, level: Level, msg: String) -> Result<()> {
+///     fn log(
+///         &mut self,
+///         logger: Resource<MyLogger>,
+///         level: Level,
+///         msg: String,
+///     ) -> impl Future<Output = Result<()>> + Send {
+///         // This runs when `log` is called, before the future is first polled.
 ///         debug_assert!(!logger.owned());
-///         let logger = self.table.get_mut(&logger)?;
-///         if (level as u32) <= (logger.max_level as u32) {
-///             println!("{msg}");
+///         let max_level = self.table.get(&logger).map(|logger| logger.max_level);
+///         async move {
+///             if (level as u32) <= (max_level? as u32) {
+///                 println!("{msg}");
+///             }
+///             Ok(())
 ///         }
-///         Ok(())
 ///     }
 ///
 ///     async fn drop(&mut self, logger: Resource<MyLogger>) -> Result<()> {
@@ -642,3 +676,59 @@ pub mod _7_async;
 /// ```
 #[cfg(feature = "component-model-async")]
 pub mod _8_store_in_imports;
+
+/// Example of mapping WIT `result` errors to a Rust error type.
+///
+/// This is an example of using the `trappable_error_type` option of the
+/// [`bindgen!`](crate::component::bindgen) macro. Functions with a WIT return
+/// type of `result<T, io-error>` are generated as returning
+/// `Result<T, MyIoError>` in Rust. The [`Host`] trait then has a
+/// [`convert_io_error`] method which turns a `MyIoError` into either the
+/// WIT-level [`IoError`], which is returned to the guest, or a trap.
+///
+/// [`Host`]: _9_trappable_errors::example::trappable_errors::files::Host
+/// [`convert_io_error`]: _9_trappable_errors::example::trappable_errors::files::Host::convert_io_error
+/// [`IoError`]: _9_trappable_errors::example::trappable_errors::files::IoError
+///
+/// ```rust
+/// use wasmtime::Result;
+/// use wasmtime::component::bindgen;
+/// use example::trappable_errors::files::{Host, IoError};
+///
+#[doc = include_str!("./_9_trappable_errors.rs")]
+///
+/// // Using `?` on a `std::io::Error` converts WIT-representable errors into
+/// // `IoError` and everything else into a trap.
+/// impl From<std::io::Error> for MyIoError {
+///     fn from(err: std::io::Error) -> MyIoError {
+///         match err.kind() {
+///             std::io::ErrorKind::NotFound => MyIoError::Wit(IoError::NotFound),
+///             std::io::ErrorKind::PermissionDenied => {
+///                 MyIoError::Wit(IoError::PermissionDenied)
+///             }
+///             _ => MyIoError::Trap(err),
+///         }
+///     }
+/// }
+///
+/// struct MyState;
+///
+/// impl Host for MyState {
+///     fn read_to_string(&mut self, path: String) -> Result<String, MyIoError> {
+///         Ok(std::fs::read_to_string(&path)?)
+///     }
+///
+///     // Invoked by the generated bindings whenever `read_to_string` returns
+///     // an error. Returning `Ok` delivers the error to the guest as the
+///     // `error` case of the WIT `result` and returning `Err` traps.
+///     fn convert_io_error(&mut self, err: MyIoError) -> Result<IoError> {
+///         match err {
+///             MyIoError::Wit(e) => Ok(e),
+///             MyIoError::Trap(e) => Err(e.into()),
+///         }
+///     }
+/// }
+///
+/// # fn main() {}
+/// ```
+pub mod _9_trappable_errors;
@@ -158,8 +158,12 @@ impl TypeChecker<'_> {
                 continue;
             }
             let actual = actual.and_then(|map| map.get(name, self.strings));
-            self.definition(expected, actual)
-                .with_context(|| format!("instance export `{name}` has the wrong type"))?;
+            let result = self.definition(expected, actual);
+            if actual.is_some() {
+                result.with_context(|| format!("instance export `{name}` has the wrong type"))?;
+            } else {
+                result.with_context(|| format!("instance export `{name}` was not found"))?;
+            }
         }
         Ok(())
     }
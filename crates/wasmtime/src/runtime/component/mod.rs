@@ -322,6 +322,13 @@ pub(crate) use self::store::ComponentStoreData;
 ///         // entries and exits into this host API. This can assist with
 ///         // debugging or just generally be used to provide logs for the host.
 ///         //
+///         // Each call is wrapped in a `TRACE`-level span named
+///         // "wit-bindgen import" with `module` and `function` fields naming
+///         // the function being called. For `async` functions the span is
+///         // attached to the returned future so it's entered whenever the
+///         // future is polled. Without this flag no `tracing` code is
+///         // generated at all.
+///         //
 ///         // By default values are traced unless they contain lists, but
 ///         // tracing of lists can be enabled with `verbose_tracing` below.
 ///         "my:local/api/foo": tracing,
@@ -395,6 +402,37 @@ pub(crate) use self::store::ComponentStoreData;
 ///         "wasi:io/streams/stream-error" => RustErrorType,
 ///     },
 ///
+///     // By default `trappable` functions without a `trappable_error_type`
+///     // return `wasmtime::Result<T>` where the error is a trap. This option
+///     // replaces `wasmtime::Error` in those signatures with a custom type
+///     // which must be convertible to `wasmtime::Error` with `?`, for example
+///     // any type implementing `std::error::Error + Send + Sync + 'static`.
+///     //
+///     // By default this option is not specified.
+///     trap_type: MyTrap,
+///
+///     // By default a `trappable` function returning `result<T>`, which has
+///     // no error payload, returns `wasmtime::Result<Result<T, ()>>`. This
+///     // option flattens it to `wasmtime::Result<T>` instead: since the host
+///     // has no structured error to give the guest, its errors trap, and the
+///     // guest only ever sees `ok`. Functions with a `trappable_error_type`
+///     // are unaffected.
+///     //
+///     // This option defaults to `false`.
+///     flatten_unit_errors: false,
+///
+///     // Generates a `MockFoo` type for each imported interface `foo` which
+///     // implements its `Host` trait by recording each call, along with its
+///     // arguments, in a `Vec<FooCall>`. Functions which return values return
+///     // `Default::default()`. This is intended for tests which want to assert
+///     // on what a guest imported. Mocks aren't generated for interfaces with
+///     // resources, trappable error types, functions that take the store, or
+///     // functions returning types without a `Default`, such as records,
+///     // enums, and results.
+///     //
+///     // This option defaults to `false`.
+///     mock: false,
+///
 ///     // All generated bindgen types are "owned" meaning types like `String`
 ///     // are used instead of `&str`, for example. This is the default and
 ///     // ensures that the same type used in both imports and exports uses the
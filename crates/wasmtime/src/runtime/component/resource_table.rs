@@ -442,3 +442,29 @@ pub fn test_free_list() {
     let x = table.push(()).unwrap();
     assert_eq!(x.rep(), 2);
 }
+
+#[test]
+pub fn test_stale_handle() {
+    let mut table = ResourceTable::new();
+
+    // A stale handle to a deleted entry is reported as an error rather than
+    // aliasing whatever is stored next.
+    let x = table.push(1u32).unwrap();
+    let stale = Resource::<u32>::new_borrow(x.rep());
+    table.delete_maybe_debug(x, false).unwrap();
+    assert!(matches!(
+        table.get(&stale),
+        Err(ResourceTableError::NotPresent)
+    ));
+
+    // In debug mode the deleted entry is a tombstone which is never reused.
+    let x = table.push(2u32).unwrap();
+    let stale = Resource::<u32>::new_borrow(x.rep());
+    table.delete_maybe_debug(x, true).unwrap();
+    assert!(matches!(
+        table.get_mut(&stale),
+        Err(ResourceTableError::WrongType)
+    ));
+    let y = table.push(3u32).unwrap();
+    assert_ne!(y.rep(), stale.rep());
+}
@@ -132,6 +132,21 @@ pub struct Opts {
     /// `result<T, E>` found in WIT.
     pub trappable_error_type: Vec<TrappableError>,
 
+    /// The Rust type used to trap from `trappable` functions which don't have
+    /// a `trappable_error_type` mapping, instead of `wasmtime::Error`.
+    ///
+    /// The type must be convertible into `wasmtime::Error` with `?`.
+    pub trap_type: Option<String>,
+
+    /// Whether `trappable` functions returning `result<T>`, with no error
+    /// payload, return just `T` alongside the trap type, trapping instead of
+    /// returning the (empty) error to the guest.
+    pub flatten_unit_errors: bool,
+
+    /// Whether to generate a `Mock*` implementation of the `Host` trait for
+    /// each imported interface, recording every call for use in tests.
+    pub mock: bool,
+
     /// Whether to generate owning or borrowing type definitions.
     pub ownership: Ownership,
 
@@ -363,6 +378,12 @@ impl Wasmtime {
             )
         }
 
+        // Similar to trappable errors above, give the custom trap type, if
+        // any, a name relative to the root of where the macro is invoked.
+        if let Some(trap_type) = &self.opts.trap_type {
+            uwriteln!(self.src, "type _TrapType = {trap_type};");
+        }
+
         // Convert all entries in `with` as relative to the root of where the
         // macro itself is invoked. This emits a `pub use` to bring the name
         // into scope under an "anonymous name" which then replaces the `with`
@@ -976,6 +997,7 @@ impl<_T: Send + 'static> {camel}Pre<_T> {{
             );
         }
         self.world_add_to_linker(resolve, world, world_trait.as_ref());
+        self.world_interfaces_metadata(resolve, world);
 
         for func in self.exports.funcs.iter() {
             self.src.push_str(func);
@@ -986,6 +1008,43 @@ impl<_T: Send + 'static> {camel}Pre<_T> {{
         uwriteln!(self.src, "}};"); // close `const _: () = ...
     }
 
+    /// Emits constants listing the interfaces imported and exported by
+    /// `world` along with the names of their functions.
+    fn world_interfaces_metadata(&mut self, resolve: &Resolve, world: WorldId) {
+        let list = |items: &IndexMap<WorldKey, WorldItem>| {
+            let mut list = String::new();
+            for (key, item) in items {
+                let WorldItem::Interface { id, .. } = item else {
+                    continue;
+                };
+                let name = resolve.name_world_key(key);
+                let funcs = resolve.interfaces[*id]
+                    .functions
+                    .keys()
+                    .map(|f| format!("{f:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                uwrite!(list, "({name:?}, &[{funcs}]),");
+            }
+            list
+        };
+        let world = &resolve.worlds[world];
+        let imports = list(&world.imports);
+        let exports = list(&world.exports);
+        uwriteln!(
+            self.src,
+            "
+                /// The names of the interfaces imported by this world,
+                /// along with the names of their functions.
+                pub const IMPORTED_INTERFACES: &'static [(&'static str, &'static [&'static str])] = &[{imports}];
+
+                /// The names of the interfaces exported by this world,
+                /// along with the names of their functions.
+                pub const EXPORTED_INTERFACES: &'static [(&'static str, &'static [&'static str])] = &[{exports}];
+            "
+        );
+    }
+
     fn finish(&mut self, resolve: &Resolve, world: WorldId) -> anyhow::Result<String> {
         let remapping_keys = self.opts.with.keys().cloned().collect::<HashSet<String>>();
 
@@ -2082,6 +2141,42 @@ impl<'a> InterfaceGenerator<'a> {
         }
         self.push_str("}\n");
 
+        // Conversions to and from the integer discriminant of the enum, along
+        // with a list of all of its cases.
+        let cases = enum_
+            .cases
+            .iter()
+            .map(|case| format!("{name}::{}", case.name.to_upper_camel_case()))
+            .collect::<Vec<_>>();
+        uwriteln!(
+            self.src,
+            "
+                impl {name} {{
+                    pub const ALL: &'static [{name}] = &[{}];
+                }}
+
+                impl From<{name}> for {repr} {{
+                    fn from(e: {name}) -> {repr} {{
+                        e as {repr}
+                    }}
+                }}
+
+                impl TryFrom<{repr}> for {name} {{
+                    type Error = {wt}::Error;
+
+                    fn try_from(value: {repr}) -> {wt}::Result<{name}> {{
+                        match {name}::ALL.get(value as usize) {{
+                            Some(e) => Ok(*e),
+                            None => Err({wt}::component::__internal::anyhow::anyhow!(
+                                \"invalid discriminant for `{name}`: {{value}}\"
+                            )),
+                        }}
+                    }}
+                }}
+            ",
+            cases.join(", "),
+        );
+
         // Auto-synthesize an implementation of the standard `Error` trait for
         // error-looking types based on their name.
         if info.error {
@@ -2247,6 +2342,21 @@ impl<'a> InterfaceGenerator<'a> {
         Some((result, error_typeid, path))
     }
 
+    /// Returns the `ok` type of `func`'s `result<T>` if it has no error
+    /// payload and the `flatten_unit_errors` option flattens it.
+    fn flattened_unit_error(&self, func: &Function) -> Option<Option<Type>> {
+        if !self.generator.opts.flatten_unit_errors {
+            return None;
+        }
+        let Some(Type::Id(id)) = func.result else {
+            return None;
+        };
+        match &self.resolve.types[resolve_type_definition_id(self.resolve, id)].kind {
+            TypeDefKind::Result(Result_ { ok, err: None }) => Some(*ok),
+            _ => None,
+        }
+    }
+
     fn generate_add_to_linker(&mut self, id: InterfaceId, name: &str) {
         let iface = &self.resolve.interfaces[id];
         let owner = TypeOwner::Interface(id);
@@ -2296,22 +2406,19 @@ impl<'a> InterfaceGenerator<'a> {
         // Generate the `pub trait` which represents the host functionality for
         // this import which additionally inherits from all resource traits
         // for this interface defined by `type_resource`.
-        let generated_trait = self.generate_trait(
-            "Host",
-            &iface
-                .functions
-                .iter()
-                .filter_map(|(_, f)| {
-                    if f.kind.resource().is_none() {
-                        Some(f)
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>(),
-            &extra_functions,
-            &get_resources(self.resolve, id).collect::<Vec<_>>(),
-        );
+        let functions = iface
+            .functions
+            .iter()
+            .filter_map(|(_, f)| {
+                if f.kind.resource().is_none() {
+                    Some(f)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let resources = get_resources(self.resolve, id).collect::<Vec<_>>();
+        let generated_trait = self.generate_trait("Host", &functions, &extra_functions, &resources);
 
         let opt_t_send_bound = if generated_trait
             .all_func_flags
@@ -2369,6 +2476,90 @@ impl<'a> InterfaceGenerator<'a> {
         gate.close(&mut self.src);
         uwriteln!(self.src, "Ok(())");
         uwriteln!(self.src, "}}");
+
+        // Mocks only implement `Host` itself, so interfaces which also need
+        // resource traits or error conversions don't get one.
+        if self.generator.opts.mock && resources.is_empty() && extra_functions.is_empty() {
+            let camel = iface.name.as_deref().unwrap_or(name).to_upper_camel_case();
+            self.generate_mock(&camel, &functions);
+        }
+    }
+
+    /// Generates `Mock{camel}`, an implementation of `Host` which records each
+    /// call to `functions` in a `{camel}Call` for use in tests.
+    fn generate_mock(&mut self, camel: &str, functions: &[&Function]) {
+        let wt = self.generator.wasmtime_path();
+        let partition = self.partition_concurrent_funcs(functions.iter().copied());
+
+        // Functions which take the store are part of `HostWithStore` instead,
+        // which a mock can't implement without the store.
+        if !partition.with_store.is_empty() {
+            return;
+        }
+
+        // Results are `Default::default()`, which most generated types, such
+        // as records, enums, and `Result`s, don't implement.
+        if functions.iter().any(|f| {
+            f.result
+                .is_some_and(|ty| !type_has_default(ty, self.resolve))
+        }) {
+            return;
+        }
+
+        uwriteln!(
+            self.src,
+            "
+                /// A mock implementation of [`Host`] which records every call,
+                /// in order, for use in tests.
+                ///
+                /// Functions which return values return `Default::default()`.
+                #[derive(Debug, Default)]
+                pub struct Mock{camel} {{
+                    /// The calls made to this mock, oldest first.
+                    pub calls: {wt}::component::__internal::Vec<{camel}Call>,
+                }}
+
+                /// A call recorded by [`Mock{camel}`], with its arguments.
+                #[derive(Debug)]
+                pub enum {camel}Call {{
+            "
+        );
+        for (func, _) in partition.without_store.iter() {
+            uwrite!(self.src, "{} {{", func.name.to_upper_camel_case());
+            for (name, ty) in func.params.iter() {
+                uwrite!(self.src, "{}: ", to_rust_ident(name));
+                self.print_ty(ty, TypeMode::Owned);
+                self.push_str(",");
+            }
+            self.push_str("},\n");
+        }
+        uwriteln!(self.src, "}}");
+
+        uwriteln!(self.src, "impl Host for Mock{camel} {{");
+        for (func, flags) in partition.without_store.iter() {
+            self.generate_function_trait_sig(func, *flags);
+            uwrite!(
+                self.src,
+                "{{ self.calls.push({camel}Call::{} {{",
+                func.name.to_upper_camel_case()
+            );
+            for (name, _) in func.params.iter() {
+                uwrite!(self.src, "{},", to_rust_ident(name));
+            }
+            self.push_str("});\n");
+            let mut ret = match func.result {
+                Some(_) => "Default::default()".to_string(),
+                None => "()".to_string(),
+            };
+            if flags.contains(FunctionFlags::TRAPPABLE) {
+                ret = format!("Ok({ret})");
+            }
+            if flags.contains(FunctionFlags::ASYNC) {
+                ret = format!("async move {{ {ret} }}");
+            }
+            uwriteln!(self.src, "{ret} }}");
+        }
+        uwriteln!(self.src, "}}");
     }
 
     fn import_resource_drop_flags(&mut self, name: &str) -> FunctionFlags {
@@ -2592,8 +2783,12 @@ impl<'a> InterfaceGenerator<'a> {
                     Err(e) => Err({convert}),
                 }},))"
             );
+        } else if self.flattened_unit_error(func).is_some() {
+            uwrite!(self.src, "Ok((Ok::<_, ()>(r?),))\n");
         } else if func.result.is_some() {
             uwrite!(self.src, "Ok((r?,))\n");
+        } else if self.generator.opts.trap_type.is_some() {
+            uwrite!(self.src, "Ok(r?)\n");
         } else {
             uwrite!(self.src, "r\n");
         }
@@ -2669,11 +2864,24 @@ impl<'a> InterfaceGenerator<'a> {
             self.push_str(">");
         } else {
             // All other functions get their return values wrapped in an wasmtime::Result.
-            // Returning the anyhow::Error case can be used to trap.
-            let wt = self.generator.wasmtime_path();
-            uwrite!(self.src, "{wt}::Result<");
-            self.print_result_ty(func.result, TypeMode::Owned);
-            self.push_str(">");
+            // Returning the anyhow::Error case can be used to trap. A
+            // `result<T>` without an error payload may be flattened to `T`
+            // since its errors can only trap.
+            let result = self.flattened_unit_error(func).unwrap_or(func.result);
+            match &self.generator.opts.trap_type {
+                Some(_) => {
+                    let path = self.path_to_root();
+                    self.push_str("Result<");
+                    self.print_result_ty(result, TypeMode::Owned);
+                    uwrite!(self.src, ", {path}_TrapType>");
+                }
+                None => {
+                    let wt = self.generator.wasmtime_path();
+                    uwrite!(self.src, "{wt}::Result<");
+                    self.print_result_ty(result, TypeMode::Owned);
+                    self.push_str(">");
+                }
+            }
         }
     }
 
@@ -3375,6 +3583,24 @@ fn formatting_for_results(result: Option<Type>, resolve: &Resolve, flags: Functi
     format!("result = tracing::field::debug(&r)")
 }
 
+/// Test whether the Rust type generated for the given type implements
+/// `Default`.
+fn type_has_default(ty: Type, resolve: &Resolve) -> bool {
+    match ty {
+        Type::Id(id) => match &resolve.types[id].kind {
+            TypeDefKind::Option(_) | TypeDefKind::List(_) => true,
+            TypeDefKind::Tuple(tuple) => {
+                tuple.types.len() <= 12
+                    && tuple.types.iter().all(|ty| type_has_default(*ty, resolve))
+            }
+            TypeDefKind::Type(ty) => type_has_default(*ty, resolve),
+            _ => false,
+        },
+        Type::ErrorContext => false,
+        _ => true,
+    }
+}
+
 /// Test whether the given type contains lists.
 ///
 /// Here, a `string` is not considered a list.
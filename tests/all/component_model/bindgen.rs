@@ -200,6 +200,178 @@ mod one_import {
     }
 }
 
+mod custom_trap_type {
+    use super::*;
+    use wasmtime::component::HasSelf;
+
+    wasmtime::component::bindgen!({
+        inline: "
+            package foo:foo;
+
+            world custom-trap {
+                import foo: interface {
+                    foo: func(fail: bool);
+                    bar: func() -> u32;
+                }
+
+                export run: func(fail: bool) -> u32;
+            }
+        ",
+        imports: { default: trappable },
+        trap_type: MyTrap,
+    });
+
+    #[derive(Debug)]
+    pub struct MyTrap;
+
+    impl std::fmt::Display for MyTrap {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "my trap")
+        }
+    }
+
+    impl std::error::Error for MyTrap {}
+
+    #[test]
+    fn run() -> Result<()> {
+        let engine = engine();
+
+        let component = Component::new(
+            &engine,
+            r#"
+                (component
+                    (import "foo" (instance $i
+                        (export "foo" (func (param "fail" bool)))
+                        (export "bar" (func (result u32)))
+                    ))
+                    (core func $foo (canon lower (func $i "foo")))
+                    (core func $bar (canon lower (func $i "bar")))
+                    (core module $m
+                        (import "" "foo" (func $foo (param i32)))
+                        (import "" "bar" (func $bar (result i32)))
+                        (func (export "run") (param i32) (result i32)
+                            (call $foo (local.get 0))
+                            (call $bar))
+                    )
+                    (core instance $i (instantiate $m
+                        (with "" (instance
+                            (export "foo" (func $foo))
+                            (export "bar" (func $bar))
+                        ))
+                    ))
+
+                    (func (export "run") (param "fail" bool) (result u32)
+                        (canon lift (core func $i "run")))
+                )
+            "#,
+        )?;
+
+        struct MyImports;
+
+        impl foo::Host for MyImports {
+            fn foo(&mut self, fail: bool) -> Result<(), MyTrap> {
+                if fail { Err(MyTrap) } else { Ok(()) }
+            }
+
+            fn bar(&mut self) -> Result<u32, MyTrap> {
+                Ok(42)
+            }
+        }
+
+        let mut linker = Linker::new(&engine);
+        foo::add_to_linker::<_, HasSelf<_>>(&mut linker, |f| f)?;
+        let mut store = Store::new(&engine, MyImports);
+        let bindings = CustomTrap::instantiate(&mut store, &component, &linker)?;
+        assert_eq!(bindings.call_run(&mut store, false)?, 42);
+
+        let err = bindings.call_run(&mut store, true).unwrap_err();
+        assert!(err.downcast_ref::<MyTrap>().is_some(), "{err:?}");
+        Ok(())
+    }
+}
+
+mod mock_imports {
+    use super::*;
+    use wasmtime::component::HasSelf;
+
+    wasmtime::component::bindgen!({
+        inline: "
+            package example:interface-imports;
+
+            interface logging {
+                enum level {
+                    debug,
+                    info,
+                    warn,
+                    error,
+                }
+
+                log: func(level: level, msg: string);
+            }
+
+            world with-imports {
+                import logging;
+
+                export run: func();
+            }
+        ",
+        mock: true,
+    });
+
+    use example::interface_imports::logging::{self, Level, LoggingCall, MockLogging};
+
+    #[test]
+    fn run() -> Result<()> {
+        let engine = engine();
+
+        let component = Component::new(
+            &engine,
+            r#"
+                (component
+                    (import "example:interface-imports/logging" (instance $logging
+                        (type $level' (enum "debug" "info" "warn" "error"))
+                        (export "level" (type $level (eq $level')))
+                        (export "log" (func (param "level" $level) (param "msg" string)))
+                    ))
+                    (core module $libc
+                        (memory (export "memory") 1)
+                        (data (i32.const 0) "hello")
+                    )
+                    (core instance $libc (instantiate $libc))
+                    (core func $log (canon lower (func $logging "log") (memory $libc "memory")))
+                    (core module $m
+                        (import "" "log" (func $log (param i32 i32 i32)))
+                        (func (export "run")
+                            (call $log (i32.const 2) (i32.const 0) (i32.const 5)))
+                    )
+                    (core instance $i (instantiate $m
+                        (with "" (instance (export "log" (func $log))))
+                    ))
+
+                    (func (export "run") (canon lift (core func $i "run")))
+                )
+            "#,
+        )?;
+
+        let mut linker = Linker::new(&engine);
+        logging::add_to_linker::<_, HasSelf<_>>(&mut linker, |m| m)?;
+        let mut store = Store::new(&engine, MockLogging::default());
+        let bindings = WithImports::instantiate(&mut store, &component, &linker)?;
+        bindings.call_run(&mut store)?;
+
+        match &store.data().calls[..] {
+            [
+                LoggingCall::Log {
+                    level: Level::Warn,
+                    msg,
+                },
+            ] => assert_eq!(msg, "hello"),
+            calls => panic!("unexpected calls: {calls:?}"),
+        }
+        Ok(())
+    }
+}
+
 mod one_import_concurrent {
     use super::*;
     use wasmtime::component::{Accessor, HasData};
@@ -494,8 +666,107 @@ mod resources_at_interface_level {
     }
 }
 
+mod resources_with_host_type {
+    use super::*;
+    use wasmtime::component::{HasSelf, Resource, ResourceTable};
+
+    wasmtime::component::bindgen!({
+        inline: "
+            package foo:foo;
+
+            interface def {
+                resource x {
+                    constructor(a: u32);
+                }
+            }
+
+            world resources {
+                import def;
+
+                export y: func();
+            }
+        ",
+        with: { "foo:foo/def/x": MyX },
+    });
+
+    pub struct MyX(u32);
+
+    #[test]
+    fn run() -> Result<()> {
+        let engine = engine();
+
+        let component = Component::new(
+            &engine,
+            r#"
+                (component
+                    (import (interface "foo:foo/def") (instance $i
+                        (export "x" (type $x (sub resource)))
+                        (export "[constructor]x" (func (param "a" u32) (result (own $x))))
+                    ))
+                    (alias export $i "x" (type $x))
+                    (core func $dtor (canon resource.drop $x))
+                    (core func $ctor (canon lower (func $i "[constructor]x")))
+
+                    (core module $m
+                        (import "" "ctor" (func $ctor (param i32) (result i32)))
+                        (import "" "dtor" (func $dtor (param i32)))
+
+                        (func (export "y")
+                            (local $a i32)
+                            (local.set $a (call $ctor (i32.const 100)))
+                            (call $dtor (call $ctor (i32.const 200)))
+                            (call $dtor (local.get $a))
+                        )
+                    )
+                    (core instance $i (instantiate $m
+                        (with "" (instance
+                            (export "ctor" (func $ctor))
+                            (export "dtor" (func $dtor))
+                        ))
+                    ))
+                    (func (export "y") (canon lift (core func $i "y")))
+                )
+            "#,
+        )?;
+
+        #[derive(Default)]
+        struct MyImports {
+            table: ResourceTable,
+            drops: Vec<u32>,
+        }
+
+        impl foo::foo::def::HostX for MyImports {
+            fn new(&mut self, a: u32) -> Resource<MyX> {
+                self.table.push(MyX(a)).unwrap()
+            }
+
+            fn drop(&mut self, val: Resource<MyX>) -> Result<()> {
+                assert!(val.owned());
+                let MyX(a) = self.table.delete(val)?;
+                self.drops.push(a);
+                Ok(())
+            }
+        }
+
+        impl foo::foo::def::Host for MyImports {}
+
+        let mut linker = Linker::new(&engine);
+        Resources::add_to_linker::<_, HasSelf<_>>(&mut linker, |f| f)?;
+        let mut store = Store::new(&engine, MyImports::default());
+        let resources = Resources::instantiate(&mut store, &component, &linker)?;
+        resources.call_y(&mut store)?;
+
+        // Each guest-level drop invokes the host's `drop` exactly once with
+        // the representation created by the constructor.
+        assert_eq!(store.data().drops, [200, 100]);
+        assert!(store.data().table.is_empty());
+        Ok(())
+    }
+}
+
 mod async_config {
     use super::*;
+    use wasmtime::component::Resource;
 
     wasmtime::component::bindgen!({
         inline: "
@@ -577,6 +848,53 @@ mod async_config {
     async fn _test_t3(t3: &T3, store: &mut Store<()>) {
         let _ = t3.call_z(&mut *store).await;
     }
+
+    wasmtime::component::bindgen!({
+        inline: "
+            package my:t4;
+
+            interface logging {
+                resource logger {
+                    constructor();
+                    log: func();
+                    flush: func();
+                }
+            }
+
+            world t4 {
+                import logging;
+                export z: func();
+            }
+        ",
+        imports: {
+            "my:t4/logging/[constructor]logger": async,
+            "my:t4/logging/[method]logger.log": async,
+        },
+        exports: { default: async },
+        with: { "my:t4/logging/logger": MyLogger },
+    });
+
+    pub struct MyLogger;
+
+    impl my::t4::logging::Host for T {}
+
+    impl my::t4::logging::HostLogger for T {
+        async fn new(&mut self) -> Resource<MyLogger> {
+            loop {}
+        }
+
+        async fn log(&mut self, _: Resource<MyLogger>) {}
+
+        fn flush(&mut self, _: Resource<MyLogger>) {}
+
+        fn drop(&mut self, _: Resource<MyLogger>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn _test_t4(t4: &T4, store: &mut Store<()>) {
+        let _ = t4.call_z(&mut *store).await;
+    }
 }
 
 mod exported_resources {
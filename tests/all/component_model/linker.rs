@@ -182,3 +182,30 @@ fn linker_fails_to_define_unknown_core_module_imports_as_traps() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn missing_instance_export_is_named() -> Result<()> {
+    let engine = Engine::default();
+    let mut linker = Linker::<()>::new(&engine);
+    let mut logging = linker.instance("example:x/logging")?;
+    logging.func_wrap("flush", |_, (): ()| Ok(()))?;
+
+    let component = Component::new(
+        &engine,
+        r#"(component
+            (import "example:x/logging" (instance
+                (export "flush" (func))
+                (export "log" (func (param "msg" string)))
+            ))
+        )"#,
+    )?;
+
+    let err = match linker.instantiate_pre(&component) {
+        Ok(_) => panic!("expected a link error"),
+        Err(e) => format!("{e:?}"),
+    };
+    assert!(err.contains("example:x/logging"), "{err}");
+    assert!(err.contains("instance export `log` was not found"), "{err}");
+    Ok(())
+}
@@ -0,0 +1,108 @@
+//! Checks that calling host functions through `bindgen!`-generated bindings
+//! doesn't allocate when the arguments themselves don't need to.
+//!
+//! This lives in its own test binary as it replaces the global allocator.
+
+#![cfg(not(miri))]
+
+use anyhow::Result;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use wasmtime::component::{Component, HasSelf, Linker};
+use wasmtime::{Engine, Store};
+
+/// An allocator which counts the allocations made by each thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|a| a.get())
+}
+
+wasmtime::component::bindgen!({
+    inline: "
+        package foo:foo;
+
+        world results {
+            import take: func(r: result<u32, string>);
+
+            export run: func(ok: bool);
+        }
+    ",
+});
+
+struct Host {
+    last: Option<Result<u32, String>>,
+}
+
+impl ResultsImports for Host {
+    fn take(&mut self, r: Result<u32, String>) {
+        self.last = Some(r);
+    }
+}
+
+#[test]
+fn result_param_success_does_not_allocate() -> Result<()> {
+    let engine = Engine::default();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "take" (func $take (param "r" (result u32 (error string)))))
+                (core module $libc
+                    (memory (export "memory") 1)
+                    (data (i32.const 0) "oops")
+                )
+                (core instance $libc (instantiate $libc))
+                (core func $take (canon lower (func $take) (memory $libc "memory")))
+                (core module $m
+                    (import "" "take" (func $take (param i32 i32 i32)))
+                    (func (export "run") (param i32)
+                        (if (local.get 0)
+                            (then (call $take (i32.const 0) (i32.const 7) (i32.const 0)))
+                            (else (call $take (i32.const 1) (i32.const 0) (i32.const 4)))))
+                )
+                (core instance $i (instantiate $m
+                    (with "" (instance (export "take" (func $take))))
+                ))
+
+                (func (export "run") (param "ok" bool) (canon lift (core func $i "run")))
+            )
+        "#,
+    )?;
+
+    let mut linker = Linker::new(&engine);
+    Results::add_to_linker::<_, HasSelf<_>>(&mut linker, |h| h)?;
+    let mut store = Store::new(&engine, Host { last: None });
+    let results = Results::instantiate(&mut store, &component, &linker)?;
+
+    // Warm up any lazily-initialized state before counting.
+    results.call_run(&mut store, true)?;
+
+    let before = allocations();
+    results.call_run(&mut store, true)?;
+    assert_eq!(allocations(), before);
+    assert_eq!(store.data_mut().last.take(), Some(Ok(7)));
+
+    // The error case, by contrast, has to allocate its `String`.
+    results.call_run(&mut store, false)?;
+    assert_eq!(store.data_mut().last.take(), Some(Err("oops".to_string())));
+    Ok(())
+}
@@ -0,0 +1,11 @@
+;;! gc = true
+;;! exceptions = true
+;;! function_references = true
+
+(module $m
+  (global (export "") exnref (ref.null exn)))
+(register "m" $m)
+
+(module
+  (import "m" "" (global exnref))
+  (table 1 exnref (global.get 0)))
@@ -1,10 +1,8 @@
-;;! gc = yes
-;;! exceptions = yes
-;;! function-references = yes
-
-(module $m
-  (global (export "") exnref (ref.null exn))) 
+;;! gc = true
+;;! exceptions = true
+;;! function_references = true
 
 (module
-  (import "m" "" (global exnref))
-  (table 1 exnref (global.get 0)))
+  (global exnref (ref.null exn))
+  (global exnref (global.get 0))
+  (table 1 exnref (ref.null exn)))
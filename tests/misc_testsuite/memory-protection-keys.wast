@@ -0,0 +1,19 @@
+;;! mpk = true
+;;! multi_memory = true
+
+(module
+  (memory $a 1)
+  (memory $b 1)
+  (func (export "store") (param i32 i32)
+    (i32.store $a (local.get 0) (local.get 1))
+    (i32.store $b (local.get 0) (i32.add (local.get 1) (i32.const 1))))
+  (func (export "load-a") (param i32) (result i32)
+    (i32.load $a (local.get 0)))
+  (func (export "load-b") (param i32) (result i32)
+    (i32.load $b (local.get 0))))
+
+(assert_return (invoke "load-a" (i32.const 0)) (i32.const 0))
+(invoke "store" (i32.const 0) (i32.const 42))
+(assert_return (invoke "load-a" (i32.const 0)) (i32.const 42))
+(assert_return (invoke "load-b" (i32.const 0)) (i32.const 43))
+(assert_trap (invoke "load-a" (i32.const 65536)) "out of bounds memory access")
@@ -1,9 +1,8 @@
-use anyhow::{Context, bail};
+use anyhow::Context;
 use libtest_mimic::{Arguments, FormatSetting, Trial};
 use std::sync::{Condvar, LazyLock, Mutex};
-use wasmtime::{Config, Enabled, Engine, InstanceAllocationStrategy, PoolingAllocationConfig};
-use wasmtime_test_util::wast::{Collector, Compiler, WastConfig, WastTest, limits};
-use wasmtime_wast::{Async, SpectestConfig, WastContext};
+use wasmtime::Engine;
+use wasmtime_test_util::wast::{Collector, Compiler, RegallocAlgorithm, WastConfig, WastTest};
 
 fn main() {
     env_logger::init();
@@ -31,7 +30,7 @@ fn main() {
             ),
             {
                 let test = test.clone();
-                move || run_wast(&test, config).map_err(|e| format!("{e:?}").into())
+                move || run_wast_with_retries(&test, &config).map_err(|e| format!("{e:?}").into())
             },
         );
 
@@ -39,11 +38,7 @@ fn main() {
     };
 
     // List of supported compilers, filtered by what our current host supports.
-    let mut compilers = vec![
-        Compiler::CraneliftNative,
-        Compiler::Winch,
-        Compiler::CraneliftPulley,
-    ];
+    let mut compilers = Compiler::ALL.to_vec();
     compilers.retain(|c| c.supports_host());
 
     // Only test one compiler in ASAN since we're mostly interested in testing
@@ -55,21 +50,19 @@ fn main() {
     // Run each wast test in a few interesting configuration combinations, but
     // leave the full combinatorial matrix and such to fuzz testing which
     // configures many more settings than those configured here.
-    for test in tests {
-        let collector = if test.test_uses_gc_types() {
-            Collector::DeferredReferenceCounting
-        } else {
-            Collector::Auto
-        };
+    for test in &tests {
+        let (&collector, other_collectors) = test.collectors().split_first().unwrap();
 
         // Run this test in all supported compilers.
         for compiler in compilers.iter().copied() {
             add_trial(
-                &test,
+                test,
                 WastConfig {
                     compiler,
                     pooling: false,
                     collector,
+                    regalloc_algorithm: RegallocAlgorithm::Auto,
+                    ignore_skip_lists: false,
                 },
             );
         }
@@ -82,30 +75,70 @@ fn main() {
 
         let compiler = compilers[0];
 
-        // Run this test with the pooling allocator under the default compiler.
-        add_trial(
-            &test,
-            WastConfig {
-                compiler,
-                pooling: true,
-                collector,
-            },
-        );
+        // Run this test with the pooling allocator under the default compiler,
+        // unless each of its trials already runs under both allocators.
+        if !test.config.both_allocators() {
+            add_trial(
+                test,
+                WastConfig {
+                    compiler,
+                    pooling: true,
+                    collector,
+                    regalloc_algorithm: RegallocAlgorithm::Auto,
+                    ignore_skip_lists: false,
+                },
+            );
+        }
 
-        // If applicable, also run with the null collector in addition to the
-        // default collector.
-        if test.test_uses_gc_types() {
+        // If applicable, also run with the other collectors in addition to
+        // the default collector.
+        for &collector in other_collectors {
             add_trial(
-                &test,
+                test,
                 WastConfig {
                     compiler,
                     pooling: false,
-                    collector: Collector::Null,
+                    collector,
+                    regalloc_algorithm: RegallocAlgorithm::Auto,
+                    ignore_skip_lists: false,
                 },
             );
         }
     }
 
+    // When requested, also check that every compiler which can run a test
+    // agrees on whether it passes.
+    if std::env::var("WASMTIME_TEST_DIFFERENTIAL").is_ok() {
+        for test in &tests {
+            let runnable: Vec<_> = test
+                .runnable_compilers()
+                .into_iter()
+                .filter(|c| compilers.contains(c))
+                .collect();
+            if runnable.len() < 2 {
+                continue;
+            }
+            let test = test.clone();
+            trials.push(Trial::test(
+                format!("differential/{}", test.path.to_str().unwrap()),
+                move || {
+                    wasmtime_test_util::wasmtime_wast::run_wast_test_differential(
+                        &test,
+                        &runnable,
+                        |config| {
+                            let cfg = wasmtime_test_util::wasmtime_wast::build_engine_config(
+                                config,
+                                &test.config,
+                            );
+                            Engine::new(&cfg)
+                        },
+                    )
+                    .map_err(|e| format!("{e:?}").into())
+                },
+            ));
+        }
+    }
+
     // There's a lot of tests so print only a `.` to keep the output a
     // bit more terse by default.
     let mut args = Arguments::from_args();
@@ -115,10 +148,47 @@ fn main() {
     libtest_mimic::run(&args, trials).exit()
 }
 
+// Number of times that a test marked as `flaky` is run before its failure is
+// reported.
+const FLAKY_ATTEMPTS: u32 = 3;
+
+// Runs `run_wast` under each allocator configuration the test asks for,
+// retrying tests that are marked as `flaky` a bounded number of times if they
+// fail.
+fn run_wast_with_retries(test: &WastTest, config: &WastConfig) -> anyhow::Result<()> {
+    for config in test.allocator_configs(config) {
+        run_wast_with_retries_under(test, &config).with_context(|| {
+            let allocator = if config.pooling {
+                "pooling"
+            } else {
+                "on-demand"
+            };
+            format!("failed with the {allocator} allocator")
+        })?;
+    }
+    Ok(())
+}
+
+fn run_wast_with_retries_under(test: &WastTest, config: &WastConfig) -> anyhow::Result<()> {
+    let mut attempt = 1;
+    loop {
+        match run_wast(test, config) {
+            Err(e) if test.config.flaky() && attempt < FLAKY_ATTEMPTS => {
+                log::warn!(
+                    "flaky test {} failed on attempt {attempt}, retrying: {e:?}",
+                    test.path.display()
+                );
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 // Each of the tests included from `wast_testsuite_tests` will call this
 // function which actually executes the `wast` test suite given the `strategy`
 // to compile it.
-fn run_wast(test: &WastTest, config: WastConfig) -> anyhow::Result<()> {
+fn run_wast(test: &WastTest, config: &WastConfig) -> anyhow::Result<()> {
     let test_config = test.config.clone();
 
     // Determine whether this test is expected to fail or pass. Regardless the
@@ -127,11 +197,13 @@ fn run_wast(test: &WastTest, config: WastConfig) -> anyhow::Result<()> {
     // panic or segfault as a result.
     //
     // Updates to whether a test should pass or fail should be done in the
-    // `crates/wast-util/src/lib.rs` file.
-    let should_fail = test.should_fail(&config);
+    // `tests/wast-expectations.toml` file.
+    let should_fail = test.should_fail(config);
 
-    let multi_memory = test_config.multi_memory();
     let test_hogs_memory = test_config.hogs_memory();
+    let pooling = test.uses_pooling(config);
+    let requires_pooling =
+        test_config.requires_pooling() || (pooling && test_config.both_allocators());
     let relaxed_simd = test_config.relaxed_simd();
 
     let is_cranelift = match config.compiler {
@@ -139,10 +211,8 @@ fn run_wast(test: &WastTest, config: WastConfig) -> anyhow::Result<()> {
         _ => false,
     };
 
-    let mut cfg = Config::new();
+    let mut cfg = wasmtime_test_util::wasmtime_wast::build_engine_config(config, &test_config);
     cfg.async_support(true);
-    wasmtime_test_util::wasmtime_wast::apply_test_config(&mut cfg, &test_config);
-    wasmtime_test_util::wasmtime_wast::apply_wast_config(&mut cfg, &config);
 
     if is_cranelift {
         cfg.cranelift_debug_verifier(true);
@@ -164,8 +234,12 @@ fn run_wast(test: &WastTest, config: WastConfig) -> anyhow::Result<()> {
     // so limited.
     if cfg!(target_pointer_width = "32") || std::env::var("WASMTIME_TEST_NO_HOG_MEMORY").is_ok() {
         // The pooling allocator hogs ~6TB of virtual address space for each
-        // store, so if we don't to hog memory then ignore pooling tests.
-        if config.pooling {
+        // store, so if we don't to hog memory then ignore pooling tests,
+        // unless they can't run without it.
+        if pooling {
+            if requires_pooling {
+                anyhow::bail!("test requires the pooling allocator, which isn't available");
+            }
             return Ok(());
         }
 
@@ -185,49 +259,17 @@ fn run_wast(test: &WastTest, config: WastConfig) -> anyhow::Result<()> {
         cfg.memory_guard_size(small_guard);
     }
 
-    let _pooling_lock = if config.pooling {
+    // The pool itself is configured by `build_engine_config`.
+    let _pooling_lock = if pooling {
         // Some memory64 tests take more than 4gb of resident memory to test,
         // but we don't want to configure the pooling allocator to allow that
         // (that's a ton of memory to reserve), so we skip those tests.
         if test_hogs_memory {
+            if requires_pooling {
+                anyhow::bail!("test requires the pooling allocator but also hogs memory");
+            }
             return Ok(());
         }
-
-        // Reduce the virtual memory required to run multi-memory-based tests.
-        //
-        // The configuration parameters below require that a bare minimum
-        // virtual address space reservation of 450*9*805*65536 == 200G be made
-        // to support each test. If 6G reservations are made for each linear
-        // memory then not that many tests can run concurrently with much else.
-        //
-        // When multiple memories are used and are configured in the pool then
-        // force the usage of static memories without guards to reduce the VM
-        // impact.
-        let max_memory_size = limits::MEMORY_SIZE;
-        if multi_memory {
-            cfg.memory_reservation(max_memory_size as u64);
-            cfg.memory_reservation_for_growth(0);
-            cfg.memory_guard_size(0);
-        }
-
-        let mut pool = PoolingAllocationConfig::default();
-        pool.total_memories(limits::MEMORIES * 2)
-            .max_memory_protection_keys(2)
-            .max_memory_size(max_memory_size)
-            .max_memories_per_module(if multi_memory {
-                limits::MEMORIES_PER_MODULE
-            } else {
-                1
-            })
-            .max_tables_per_module(limits::TABLES_PER_MODULE);
-
-        // When testing, we may choose to start with MPK force-enabled to ensure
-        // we use that functionality.
-        if std::env::var("WASMTIME_TEST_FORCE_MPK").is_ok() {
-            pool.memory_protection_keys(Enabled::Yes);
-        }
-
-        cfg.allocation_strategy(InstanceAllocationStrategy::Pooling(pool));
         Some(lock_pooling())
     } else {
         None
@@ -246,23 +288,29 @@ fn run_wast(test: &WastTest, config: WastConfig) -> anyhow::Result<()> {
     }
 
     for (engine, desc) in engines {
+        // When auditing the expectations, only run tests which are expected to
+        // fail and report those that pass anyway so they can be pruned.
+        if std::env::var("WASMTIME_TEST_AUDIT_EXPECTATIONS").is_ok() {
+            let Ok(engine) = engine else { continue };
+            let stale =
+                wasmtime_test_util::wasmtime_wast::find_stale_expectation(test, config, &engine);
+            if let Some(failure) = stale {
+                anyhow::bail!(
+                    "test passes with {desc} engine but is listed as an expected \
+                     failure in `tests/wast-expectations.toml`: {failure:?}"
+                );
+            }
+            continue;
+        }
+
         let result = engine.and_then(|engine| {
-            let mut wast_context = WastContext::new(&engine, Async::Yes, |_store| {});
-            wast_context.generate_dwarf(true);
-            wast_context.register_spectest(&SpectestConfig {
-                use_shared_memory: true,
-                suppress_prints: true,
-            })?;
-            wast_context
-                .run_wast(test.path.to_str().unwrap(), test.contents.as_bytes())
+            wasmtime_test_util::wasmtime_wast::run_wast_test(test, config, &engine)
                 .with_context(|| format!("failed to run spec test with {desc} engine"))
         });
 
-        if should_fail {
-            if result.is_ok() {
-                bail!("this test is flagged as should-fail but it succeeded")
-            }
-        } else {
+        // Note that `run_wast_test` handles tests that are expected to fail,
+        // but failing to create the engine is also an acceptable failure.
+        if !should_fail {
             result?;
         }
     }